@@ -13,6 +13,7 @@ use arch_pkg_db::{
     MultiTextCollection,
     desc::{EagerQuerier, Query},
     multi::MultiQuerier,
+    text::sync::SyncDir,
     value::{
         Base, Dependency, DependencyList, DependencyName, Description, Name, RepositoryName, Url,
         Version,
@@ -45,6 +46,8 @@ static HELP: &str = text_block! {
     "Usage:"
     "  cargo run [--release] --example=providers_multi -- [REPOSITORIES]..."
     ""
+    "Every sync database in /var/lib/pacman/sync/ is loaded when no repository is specified."
+    ""
     "Syntax to specify a repository:"
     "  * <REPOSITORY_NAME>:<ARCHIVE_PATH>"
     "  * <REPOSITORY_NAME>"
@@ -129,11 +132,18 @@ fn parse_arg(arg: &str) -> Result<RepositorySpec<'_>, ParseArgExit<'_>> {
         .strip_suffix(".db")
         .or_else(|| file_name.strip_suffix(".tar.gz"))
         .or_else(|| file_name.strip_suffix(".tar.xz"))
+        .or_else(|| file_name.strip_suffix(".tar.zst"))
+        .or_else(|| file_name.strip_suffix(".tar.bz2"))
+        .or_else(|| file_name.strip_suffix(".tar.lz4"))
         .or_else(|| file_name.strip_suffix(".tgz"))
         .or_else(|| file_name.strip_suffix(".txz"))
+        .or_else(|| file_name.strip_suffix(".tzst"))
         .or_else(|| file_name.strip_suffix(".tar"))
         .or_else(|| file_name.strip_suffix(".gz"))
         .or_else(|| file_name.strip_suffix(".xz"))
+        .or_else(|| file_name.strip_suffix(".zst"))
+        .or_else(|| file_name.strip_suffix(".bz2"))
+        .or_else(|| file_name.strip_suffix(".lz4"))
         .unwrap_or(file_name)
         .pipe(validate_repository_name)
         .map_err(ParseArgExit::InvalidRepositoryName)?;
@@ -145,11 +155,25 @@ fn main() -> ExitCode {
     let parse_args_result: Result<Vec<RepositorySpec>, ParseArgExit> =
         args.iter().map(String::as_str).map(parse_arg).collect();
 
+    let sync_dir;
     let repositories = match parse_args_result {
         Ok(repositories) if repositories.is_empty() => {
-            eprintln!("error: No repository specified");
-            eprintln!("hint: Run with --help to see usage");
-            return ExitCode::FAILURE;
+            let Some(db_path) = DB_PATH.as_ref() else {
+                eprintln!("error: No repository specified and {DB_PATH_STR} doesn't exist");
+                eprintln!("hint: Run with --help to see usage");
+                return ExitCode::FAILURE;
+            };
+            sync_dir = match SyncDir::read(db_path) {
+                Ok(sync_dir) => sync_dir,
+                Err(error) => {
+                    eprintln!("error: {error}");
+                    return ExitCode::FAILURE;
+                }
+            };
+            sync_dir
+                .iter()
+                .map(|db| RepositorySpec(db.repository(), db.path().to_path_buf()))
+                .collect()
         }
         Ok(repositories) => repositories,
         Err(exit) => {
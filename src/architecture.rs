@@ -0,0 +1,340 @@
+//! Filter packages by the architecture they were built for.
+
+use crate::{
+    BorrowedTextCollection, MultiQueryDatabase, MultiTextCollection, QueryDatabase, TextCollection,
+    multi::{WithParsedVersion, WithParsedVersionUtils},
+    text::archive::{LoadArchiveError, decompress, traverse_tar},
+    value::RepositoryName,
+};
+use arch_pkg_text::{
+    desc::{Query, QueryMut},
+    value::{Architecture, Name},
+};
+use core::cmp::Reverse;
+use std::{ffi::OsStr, io::Read};
+
+/// Filter which accepts packages built for a target architecture or for `any`.
+///
+/// Packages without `%ARCH%` are accepted because their architecture is unknown.
+#[derive(Debug, Clone, Copy)]
+pub struct ArchitectureFilter<'t> {
+    target: Architecture<'t>,
+}
+
+impl<'t> ArchitectureFilter<'t> {
+    /// Create a filter for a target architecture.
+    pub fn new(target: Architecture<'t>) -> Self {
+        ArchitectureFilter { target }
+    }
+
+    /// The target architecture.
+    pub fn target(&self) -> Architecture<'t> {
+        self.target
+    }
+
+    /// Whether a single `%ARCH%` value is `any` or the target architecture.
+    pub fn accepts(&self, architecture: &str) -> bool {
+        architecture == "any" || architecture == self.target.as_str()
+    }
+
+    /// Whether a list of `%ARCH%` values is empty or has an accepted value.
+    fn accepts_list<'s>(&self, architectures: impl IntoIterator<Item = &'s str>) -> bool {
+        let mut architectures = architectures
+            .into_iter()
+            .map(str::trim)
+            .filter(|architecture| !architecture.is_empty())
+            .peekable();
+        architectures.peek().is_none()
+            || architectures.any(|architecture| self.accepts(architecture))
+    }
+
+    /// Whether the package of an [immutable querier](Query) is accepted.
+    pub fn matches<'a>(&self, querier: &impl Query<'a>) -> bool {
+        let architectures = querier.architecture().into_iter().flatten();
+        self.accepts_list(architectures.map(|architecture| architecture.as_str()))
+    }
+
+    /// Whether the package of a [mutable querier](QueryMut) is accepted.
+    pub fn matches_mut<'a>(&self, querier: &mut impl QueryMut<'a>) -> bool {
+        let architectures = querier.architecture_mut().into_iter().flatten();
+        self.accepts_list(architectures.map(|architecture| architecture.as_str()))
+    }
+
+    /// Whether the package of an unparsed `desc` text is accepted.
+    ///
+    /// Only the `%ARCH%` section is scanned, so non-matching texts can be dropped before parsing.
+    pub fn matches_text(&self, text: &str) -> bool {
+        let mut lines = text.lines().map(str::trim);
+        if !lines.any(|line| line == "%ARCH%") {
+            return true;
+        }
+        let architectures = lines
+            .take_while(|line| !line.is_empty())
+            .take_while(|line| !(line.len() > 1 && line.starts_with('%') && line.ends_with('%')));
+        self.accepts_list(architectures)
+    }
+}
+
+/// Database view which only exposes packages accepted by an [`ArchitectureFilter`].
+#[derive(Debug, Clone, Copy)]
+pub struct QueryDatabaseArchitecture<'r, 'a, Querier> {
+    base: &'r QueryDatabase<'a, Querier>,
+    filter: ArchitectureFilter<'r>,
+}
+
+impl<'r, 'a, Querier: Query<'a>> QueryDatabaseArchitecture<'r, 'a, Querier> {
+    /// The filter of the view.
+    pub fn filter(&self) -> ArchitectureFilter<'r> {
+        self.filter
+    }
+
+    /// Get an immutable reference to a querier by package name if it's accepted by the filter.
+    pub fn get(&self, name: Name) -> Option<&'r Querier> {
+        self.base
+            .get(name)
+            .filter(|querier| self.filter.matches(*querier))
+    }
+
+    /// Get an iterator over all accepted pairs of package names and queriers.
+    pub fn entries(&self) -> impl Iterator<Item = (Name<'a>, &'r Querier)> + 'r {
+        let filter = self.filter;
+        self.base
+            .entries()
+            .map(|entry| entry.into_tuple())
+            .filter(move |(_, querier)| filter.matches(*querier))
+    }
+
+    /// Get an iterator over all accepted package names.
+    pub fn names(&self) -> impl Iterator<Item = Name<'a>> + 'r {
+        self.entries().map(|(name, _)| name)
+    }
+
+    /// Get an iterator over all accepted queriers.
+    pub fn queriers(&self) -> impl Iterator<Item = &'r Querier> + 'r {
+        self.entries().map(|(_, querier)| querier)
+    }
+}
+
+impl<'a, Querier> QueryDatabase<'a, Querier> {
+    /// Create a database view which only exposes packages built for `target` or `any`.
+    pub fn for_architecture<'r>(
+        &'r self,
+        target: Architecture<'r>,
+    ) -> QueryDatabaseArchitecture<'r, 'a, Querier> {
+        QueryDatabaseArchitecture {
+            base: self,
+            filter: ArchitectureFilter::new(target),
+        }
+    }
+
+    /// Remove [immutable queriers](Query) of packages which weren't built for `target` or `any`.
+    pub fn retain_architecture(&mut self, target: Architecture)
+    where
+        Querier: Query<'a>,
+    {
+        let filter = ArchitectureFilter::new(target);
+        self.retain(|_, querier| filter.matches(querier));
+    }
+
+    /// Remove [mutable queriers](QueryMut) of packages which weren't built for `target` or `any`.
+    pub fn retain_architecture_mut(&mut self, target: Architecture)
+    where
+        Querier: QueryMut<'a>,
+    {
+        let filter = ArchitectureFilter::new(target);
+        self.retain(|_, querier| filter.matches_mut(querier));
+    }
+}
+
+/// Return type of [`MultiQueryDatabaseArchitecture::get`].
+type RepositoryQuerier<'r, 'a, Querier> = (RepositoryName<'a>, &'r WithParsedVersion<'a, Querier>);
+
+/// Database view which only exposes packages accepted by an [`ArchitectureFilter`].
+#[derive(Debug, Clone, Copy)]
+pub struct MultiQueryDatabaseArchitecture<'r, 'a, Querier> {
+    base: &'r MultiQueryDatabase<'a, Querier>,
+    filter: ArchitectureFilter<'r>,
+}
+
+impl<'r, 'a, Querier: Query<'a>> MultiQueryDatabaseArchitecture<'r, 'a, Querier> {
+    /// The filter of the view.
+    pub fn filter(&self) -> ArchitectureFilter<'r> {
+        self.filter
+    }
+
+    /// Get an iterator over all accepted queriers of a package name, each paired with its repository.
+    pub fn get(&self, name: Name) -> impl Iterator<Item = RepositoryQuerier<'r, 'a, Querier>> + 'r {
+        let filter = self.filter;
+        self.base
+            .get(name)
+            .into_iter()
+            .flat_map(|multi_querier| multi_querier.entries())
+            .filter(move |(_, querier)| filter.matches(*querier))
+    }
+
+    /// Get the accepted querier of a package name whose version is greatest.
+    ///
+    /// Ties are broken in favor of the alphabetically first repository name.
+    pub fn latest(&self, name: Name) -> Option<RepositoryQuerier<'r, 'a, Querier>> {
+        self.get(name).max_by_key(|(repository, querier)| {
+            (querier.parsed_version(), Reverse(repository.as_str()))
+        })
+    }
+
+    /// Get an iterator over all accepted queriers, each paired with its package name and repository.
+    pub fn entries(
+        &self,
+    ) -> impl Iterator<
+        Item = (
+            Name<'a>,
+            RepositoryName<'a>,
+            &'r WithParsedVersion<'a, Querier>,
+        ),
+    > + 'r {
+        let filter = self.filter;
+        self.base
+            .entries()
+            .flat_map(|(name, multi_querier)| {
+                multi_querier
+                    .entries()
+                    .map(move |(repository, querier)| (name, repository, querier))
+            })
+            .filter(move |(_, _, querier)| filter.matches(*querier))
+    }
+}
+
+impl<'a, Querier> MultiQueryDatabase<'a, Querier> {
+    /// Create a database view which only exposes packages built for `target` or `any`.
+    pub fn for_architecture<'r>(
+        &'r self,
+        target: Architecture<'r>,
+    ) -> MultiQueryDatabaseArchitecture<'r, 'a, Querier> {
+        MultiQueryDatabaseArchitecture {
+            base: self,
+            filter: ArchitectureFilter::new(target),
+        }
+    }
+
+    /// Remove [immutable queriers](Query) of packages which weren't built for `target` or `any`.
+    pub fn retain_architecture(&mut self, target: Architecture)
+    where
+        Querier: Query<'a>,
+    {
+        let filter = ArchitectureFilter::new(target);
+        self.retain(|_, _, querier| filter.matches(querier));
+    }
+
+    /// Remove [mutable queriers](QueryMut) of packages which weren't built for `target` or `any`.
+    pub fn retain_architecture_mut(&mut self, target: Architecture)
+    where
+        Querier: QueryMut<'a>,
+    {
+        let filter = ArchitectureFilter::new(target);
+        self.retain(|_, _, querier| filter.matches_mut(querier));
+    }
+}
+
+impl TextCollection {
+    /// Remove texts of packages which weren't built for `target` or `any` before they are parsed.
+    pub fn retain_architecture(&mut self, target: Architecture) {
+        let filter = ArchitectureFilter::new(target);
+        self.retain(|text| filter.matches_text(text.as_str()));
+    }
+
+    /// Detect mime type of an archive from its leading bytes, extract it as a stream,
+    /// and add contents from `desc` files of packages built for `target` or `any` to the text collection.
+    ///
+    /// Texts of other packages are dropped as soon as they are extracted, so they are never collected.
+    pub fn extend_from_archive_for_architecture<Bytes: Read>(
+        &mut self,
+        bytes: Bytes,
+        target: Architecture,
+    ) -> Result<(), LoadArchiveError> {
+        let filter = ArchitectureFilter::new(target);
+        let tar = decompress(bytes)?;
+        traverse_tar(
+            tar,
+            |path| path.file_name() == Some(OsStr::new("desc")),
+            |_, text| {
+                if filter.matches_text(&text) {
+                    self.insert(text.into());
+                }
+            },
+        )?;
+        Ok(())
+    }
+
+    /// Detect mime type of an archive from its leading bytes, extract it as a stream,
+    /// and add contents from `desc` files of packages built for `target` or `any` to the text collection.
+    pub fn add_archive_for_architecture<Bytes: Read>(
+        mut self,
+        bytes: Bytes,
+        target: Architecture,
+    ) -> Result<Self, LoadArchiveError> {
+        self.extend_from_archive_for_architecture(bytes, target)?;
+        Ok(self)
+    }
+
+    /// Detect mime type of an archive from its leading bytes, extract it as a stream,
+    /// and add contents from `desc` files of packages built for `target` or `any` to the text collection.
+    pub fn from_archive_for_architecture<Bytes: Read>(
+        bytes: Bytes,
+        target: Architecture,
+    ) -> Result<Self, LoadArchiveError> {
+        TextCollection::new().add_archive_for_architecture(bytes, target)
+    }
+}
+
+impl<'a> MultiTextCollection<'a> {
+    /// Remove texts of packages which weren't built for `target` or `any` before they are parsed.
+    pub fn retain_architecture(&mut self, target: Architecture) {
+        let filter = ArchitectureFilter::new(target);
+        self.retain(|_, text| filter.matches_text(text.as_str()));
+    }
+
+    /// Detect mime type of an archive from its leading bytes, extract it as a stream,
+    /// and add contents from `desc` files of packages built for `target` or `any` to the multi-collection.
+    ///
+    /// Texts of other packages are dropped as soon as they are extracted, so they are never collected.
+    pub fn extend_from_archive_for_architecture<Bytes: Read>(
+        &mut self,
+        repository: RepositoryName<'a>,
+        bytes: Bytes,
+        target: Architecture,
+    ) -> Result<(), LoadArchiveError> {
+        let collection = TextCollection::from_archive_for_architecture(bytes, target)?;
+        self.insert(repository, collection);
+        Ok(())
+    }
+
+    /// Detect mime type of an archive from its leading bytes, extract it as a stream,
+    /// and add contents from `desc` files of packages built for `target` or `any` to the multi-collection.
+    pub fn add_archive_for_architecture<Bytes: Read>(
+        mut self,
+        repository: RepositoryName<'a>,
+        bytes: Bytes,
+        target: Architecture,
+    ) -> Result<Self, LoadArchiveError> {
+        self.extend_from_archive_for_architecture(repository, bytes, target)?;
+        Ok(self)
+    }
+
+    /// Detect mime type of an archive from its leading bytes, extract it as a stream,
+    /// and add contents from `desc` files of packages built for `target` or `any` to the multi-collection.
+    pub fn from_archive_for_architecture<Bytes: Read>(
+        repository: RepositoryName<'a>,
+        bytes: Bytes,
+        target: Architecture,
+    ) -> Result<Self, LoadArchiveError> {
+        MultiTextCollection::with_capacity(1)
+            .add_archive_for_architecture(repository, bytes, target)
+    }
+}
+
+impl BorrowedTextCollection<'_> {
+    /// Remove texts of packages which weren't built for `target` or `any` before they are parsed.
+    pub fn retain_architecture(&mut self, target: Architecture) {
+        let filter = ArchitectureFilter::new(target);
+        self.retain(|text| filter.matches_text(text));
+    }
+}
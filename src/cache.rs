@@ -0,0 +1,258 @@
+//! Binary snapshot cache of sync databases which skips decompression on repeated loads.
+
+mod error;
+mod format;
+mod stamp;
+
+pub use error::{BuildCacheError, DecodeCacheError, LoadCacheError};
+pub use stamp::SourceStamp;
+
+use crate::{MultiTextCollection, TextCollection, value::RepositoryName};
+use pipe_trait::Pipe;
+use stamp::Comparison;
+use std::{
+    fs, mem,
+    path::{Path, PathBuf},
+};
+
+/// Decompressed `desc` texts of a single repository along with the stamp of its source archive.
+#[derive(Debug, Clone)]
+pub struct CachedRepository {
+    name: String,
+    source: PathBuf,
+    stamp: SourceStamp,
+    texts: TextCollection,
+}
+
+impl CachedRepository {
+    /// Name of the repository.
+    pub fn name(&self) -> RepositoryName<'_> {
+        RepositoryName(&self.name)
+    }
+
+    /// Path to the source archive.
+    pub fn source(&self) -> &Path {
+        &self.source
+    }
+
+    /// Stamp of the source archive at the time it was cached.
+    pub fn stamp(&self) -> &SourceStamp {
+        &self.stamp
+    }
+
+    /// Decompressed `desc` texts.
+    pub fn texts(&self) -> &TextCollection {
+        &self.texts
+    }
+
+    /// Check whether the source archive still matches the recorded stamp.
+    pub fn is_fresh(&self) -> bool {
+        self.stamp.matches_file(&self.source)
+    }
+}
+
+/// Snapshot of the decompressed contents of sync database archives.
+///
+/// Each repository records the size, modification time, and SHA-256 checksum of its source archive.
+/// A repository is stale when the size of the archive changes, or when its modification time changes
+/// along with its checksum.
+///
+/// The encoding stores every text as a length-prefixed UTF-8 string after a fixed header, so it is
+/// independent of the platform. Decoding copies every text out of the encoded bytes once, use
+/// [`SnapshotCache::take_multi_text_collection`] to parse them without copying them again.
+#[derive(Debug, Default, Clone)]
+pub struct SnapshotCache {
+    repositories: Vec<CachedRepository>,
+}
+
+impl SnapshotCache {
+    /// Create an empty cache.
+    pub fn new() -> Self {
+        SnapshotCache::default()
+    }
+
+    /// Read, stamp, and decompress a sync database archive, then add it to the cache.
+    ///
+    /// A repository of the same name is replaced.
+    pub fn insert_archive(
+        &mut self,
+        repository: RepositoryName,
+        source: &Path,
+    ) -> Result<(), BuildCacheError> {
+        let cached =
+            CachedRepository::build(repository.as_str().to_string(), source.to_path_buf())?;
+        match self
+            .repositories
+            .iter_mut()
+            .find(|existing| existing.name == cached.name)
+        {
+            Some(existing) => *existing = cached,
+            None => self.repositories.push(cached),
+        }
+        Ok(())
+    }
+
+    /// Read, stamp, and decompress a sync database archive, then add it to the cache.
+    pub fn add_archive(
+        mut self,
+        repository: RepositoryName,
+        source: &Path,
+    ) -> Result<Self, BuildCacheError> {
+        self.insert_archive(repository, source)?;
+        Ok(self)
+    }
+
+    /// Get an iterator over all cached repositories.
+    pub fn repositories(&self) -> impl Iterator<Item = &CachedRepository> {
+        self.repositories.iter()
+    }
+
+    /// Get a cached repository by name.
+    pub fn get(&self, repository: RepositoryName) -> Option<&CachedRepository> {
+        self.repositories
+            .iter()
+            .find(|cached| cached.name == repository.as_str())
+    }
+
+    /// Check whether all source archives still match their recorded stamps.
+    pub fn is_fresh(&self) -> bool {
+        self.repositories.iter().all(CachedRepository::is_fresh)
+    }
+
+    /// Rebuild the repositories whose source archives have changed.
+    ///
+    /// The stamps of archives whose modification times changed without their contents changing
+    /// record the new modification times, so that their checksums aren't computed again.
+    ///
+    /// Returns the number of rebuilt repositories.
+    pub fn refresh(&mut self) -> Result<usize, BuildCacheError> {
+        let mut count = 0;
+        for cached in &mut self.repositories {
+            match cached.stamp.compare_file(&cached.source) {
+                Comparison::Unchanged => {}
+                Comparison::Touched(modified) => cached.stamp.modified = modified,
+                Comparison::Changed => {
+                    *cached = CachedRepository::build(cached.name.clone(), cached.source.clone())?;
+                    count += 1;
+                }
+            }
+        }
+        Ok(count)
+    }
+
+    /// Create a multi-collection of copies of all cached texts, ready to be parsed.
+    pub fn to_multi_text_collection(&self) -> MultiTextCollection<'_> {
+        let mut multi = MultiTextCollection::with_capacity(self.repositories.len());
+        for cached in &self.repositories {
+            multi.insert(cached.name(), cached.texts.clone());
+        }
+        multi
+    }
+
+    /// Move all cached texts into a multi-collection, ready to be parsed.
+    ///
+    /// Unlike [`SnapshotCache::to_multi_text_collection`], the texts aren't copied. The repository
+    /// names are borrowed from the cache, whose repositories are left without texts and are marked
+    /// stale, so that [`SnapshotCache::refresh`] rebuilds them.
+    pub fn take_multi_text_collection(&mut self) -> MultiTextCollection<'_> {
+        let mut multi = MultiTextCollection::with_capacity(self.repositories.len());
+        for CachedRepository {
+            name, stamp, texts, ..
+        } in &mut self.repositories
+        {
+            stamp.invalidate();
+            multi.insert(RepositoryName(name), mem::take(texts));
+        }
+        multi
+    }
+
+    /// Encode the cache.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        format::encode(self)
+    }
+
+    /// Decode a cache previously encoded by [`SnapshotCache::to_bytes`].
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, DecodeCacheError> {
+        format::decode(bytes)
+    }
+
+    /// Encode the cache and write it to a file.
+    pub fn save(&self, cache_path: &Path) -> std::io::Result<()> {
+        fs::write(cache_path, self.to_bytes())
+    }
+
+    /// Read and decode a cache file.
+    pub fn load(cache_path: &Path) -> Result<Self, LoadCacheError> {
+        cache_path
+            .pipe(fs::read)
+            .map_err(LoadCacheError::Read)?
+            .pipe_as_ref(SnapshotCache::from_bytes)
+            .map_err(LoadCacheError::Decode)
+    }
+
+    /// Load a cache file and [refresh](SnapshotCache::refresh) it against `sources`,
+    /// or build a new cache if the file is missing, unreadable, or records different repositories.
+    ///
+    /// The cache file is rewritten whenever anything was rebuilt or a stamp was updated.
+    pub fn load_or_build(
+        cache_path: &Path,
+        sources: &[(RepositoryName, &Path)],
+    ) -> Result<Self, BuildCacheError> {
+        let same_sources = |cache: &SnapshotCache| {
+            cache.repositories.len() == sources.len()
+                && sources.iter().all(|(repository, source)| {
+                    cache
+                        .get(*repository)
+                        .is_some_and(|cached| cached.source == *source)
+                })
+        };
+
+        if let Ok(mut cache) = SnapshotCache::load(cache_path)
+            && same_sources(&cache)
+        {
+            let stamps: Vec<_> = cache
+                .repositories
+                .iter()
+                .map(|cached| cached.stamp)
+                .collect();
+            // both rebuilt repositories and touched archives change the stamps
+            cache.refresh()?;
+            let changed = cache
+                .repositories
+                .iter()
+                .zip(&stamps)
+                .any(|(cached, stamp)| cached.stamp != *stamp);
+            if changed {
+                cache
+                    .save(cache_path)
+                    .map_err(BuildCacheError::WriteCache)?;
+            }
+            return Ok(cache);
+        }
+
+        let mut cache = SnapshotCache::new();
+        for (repository, source) in sources {
+            cache.insert_archive(*repository, source)?;
+        }
+        cache
+            .save(cache_path)
+            .map_err(BuildCacheError::WriteCache)?;
+        Ok(cache)
+    }
+}
+
+impl CachedRepository {
+    /// Read, stamp, and decompress a sync database archive.
+    fn build(name: String, source: PathBuf) -> Result<Self, BuildCacheError> {
+        let bytes = fs::read(&source).map_err(BuildCacheError::ReadSource)?;
+        let metadata = fs::metadata(&source).map_err(BuildCacheError::ReadSource)?;
+        let stamp = SourceStamp::new(&bytes, &metadata);
+        let texts = TextCollection::from_archive(&bytes).map_err(BuildCacheError::Archive)?;
+        Ok(CachedRepository {
+            name,
+            source,
+            stamp,
+            texts,
+        })
+    }
+}
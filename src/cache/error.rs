@@ -0,0 +1,40 @@
+use crate::text::archive::LoadArchiveError;
+use derive_more::{Display, Error};
+use std::io;
+
+/// Error when trying to decode a [`SnapshotCache`](super::SnapshotCache).
+#[derive(Debug, Display, Clone, Copy, PartialEq, Eq, Error)]
+pub enum DecodeCacheError {
+    #[display("The data isn't a snapshot cache")]
+    InvalidMagic,
+    #[display("Unsupported format version: {_0}")]
+    UnsupportedVersion(#[error(not(source))] u32),
+    #[display("The data ended unexpectedly")]
+    UnexpectedEnd,
+    #[display("The data contains an invalid UTF-8 string")]
+    InvalidUtf8,
+    #[display("The data contains an out-of-range number of nanoseconds: {_0}")]
+    InvalidNanoseconds(#[error(not(source))] u32),
+    #[display("The data has trailing bytes")]
+    TrailingBytes,
+}
+
+/// Error when trying to load a [`SnapshotCache`](super::SnapshotCache) from a file.
+#[derive(Debug, Display, Error)]
+pub enum LoadCacheError {
+    #[display("Failed to read the cache file: {_0}")]
+    Read(io::Error),
+    #[display("Failed to decode the cache file: {_0}")]
+    Decode(DecodeCacheError),
+}
+
+/// Error when trying to build a [`SnapshotCache`](super::SnapshotCache) from source archives.
+#[derive(Debug, Display, Error)]
+pub enum BuildCacheError {
+    #[display("Failed to read the source archive: {_0}")]
+    ReadSource(io::Error),
+    #[display("Failed to load the source archive: {_0}")]
+    Archive(LoadArchiveError),
+    #[display("Failed to write the cache file: {_0}")]
+    WriteCache(io::Error),
+}
@@ -0,0 +1,153 @@
+use super::{CachedRepository, DecodeCacheError, SnapshotCache, SourceStamp};
+use crate::{Text, TextCollection};
+use std::{path::PathBuf, time::Duration};
+
+/// Leading bytes of every encoded cache.
+const MAGIC: &[u8; 16] = b"arch-pkg-db\0snap";
+
+/// Version of the encoding, bumped on every incompatible change.
+const VERSION: u32 = 1;
+
+/// Exclusive upper bound of the nanoseconds of a modification time.
+const NANOS_PER_SEC: u32 = 1_000_000_000;
+
+/// Encode a cache.
+///
+/// Layout, with all integers in little endian and all strings prefixed by their lengths as `u64`:
+/// * [`MAGIC`] and [`VERSION`] as `u32`.
+/// * Number of repositories as `u64`.
+/// * For each repository: name, source path, size as `u64`, modification time as a `u8` flag
+///   followed by seconds as `u64` and nanoseconds as `u32`, SHA-256 checksum as 32 bytes,
+///   number of texts as `u64`, then the texts.
+pub(super) fn encode(cache: &SnapshotCache) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    bytes.extend_from_slice(MAGIC);
+    bytes.extend_from_slice(&VERSION.to_le_bytes());
+    put_u64(&mut bytes, cache.repositories.len() as u64);
+
+    for cached in &cache.repositories {
+        put_str(&mut bytes, &cached.name);
+        put_str(&mut bytes, &cached.source.to_string_lossy());
+        put_u64(&mut bytes, cached.stamp.size);
+        match cached.stamp.modified {
+            Some(modified) => {
+                bytes.push(1);
+                put_u64(&mut bytes, modified.as_secs());
+                bytes.extend_from_slice(&modified.subsec_nanos().to_le_bytes());
+            }
+            None => {
+                bytes.push(0);
+                put_u64(&mut bytes, 0);
+                bytes.extend_from_slice(&0u32.to_le_bytes());
+            }
+        }
+        bytes.extend_from_slice(&cached.stamp.sha256);
+        put_u64(&mut bytes, cached.texts.len() as u64);
+        for text in cached.texts.iter() {
+            put_str(&mut bytes, text);
+        }
+    }
+
+    bytes
+}
+
+/// Decode a cache.
+pub(super) fn decode(bytes: &[u8]) -> Result<SnapshotCache, DecodeCacheError> {
+    let mut reader = Reader { bytes };
+    if reader.take(MAGIC.len())? != MAGIC {
+        return Err(DecodeCacheError::InvalidMagic);
+    }
+    let version = reader.u32()?;
+    if version != VERSION {
+        return Err(DecodeCacheError::UnsupportedVersion(version));
+    }
+
+    let count = reader.u64()?;
+    let mut repositories = Vec::new();
+    for _ in 0..count {
+        let name = reader.str()?.to_string();
+        let source = PathBuf::from(reader.str()?);
+        let size = reader.u64()?;
+        let has_modified = reader.take(1)?[0] != 0;
+        let secs = reader.u64()?;
+        let nanos = reader.u32()?;
+        if nanos >= NANOS_PER_SEC {
+            return Err(DecodeCacheError::InvalidNanoseconds(nanos));
+        }
+        let modified = has_modified.then(|| Duration::new(secs, nanos));
+        let sha256 = reader
+            .take(32)?
+            .try_into()
+            .expect("slice of 32 bytes fits an array of 32 bytes");
+        let text_count = reader.u64()?;
+        let mut texts = TextCollection::new();
+        for _ in 0..text_count {
+            texts.insert(Text::from(reader.str()?));
+        }
+        repositories.push(CachedRepository {
+            name,
+            source,
+            stamp: SourceStamp {
+                size,
+                modified,
+                sha256,
+            },
+            texts,
+        });
+    }
+
+    if !reader.bytes.is_empty() {
+        return Err(DecodeCacheError::TrailingBytes);
+    }
+    Ok(SnapshotCache { repositories })
+}
+
+/// Append a `u64` in little endian.
+fn put_u64(bytes: &mut Vec<u8>, value: u64) {
+    bytes.extend_from_slice(&value.to_le_bytes());
+}
+
+/// Append a length-prefixed string.
+fn put_str(bytes: &mut Vec<u8>, value: &str) {
+    put_u64(bytes, value.len() as u64);
+    bytes.extend_from_slice(value.as_bytes());
+}
+
+/// Cursor over encoded bytes.
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    /// Consume a number of bytes.
+    fn take(&mut self, len: usize) -> Result<&'a [u8], DecodeCacheError> {
+        if self.bytes.len() < len {
+            return Err(DecodeCacheError::UnexpectedEnd);
+        }
+        let (head, tail) = self.bytes.split_at(len);
+        self.bytes = tail;
+        Ok(head)
+    }
+
+    /// Consume a `u32` in little endian.
+    fn u32(&mut self) -> Result<u32, DecodeCacheError> {
+        let bytes = self.take(4)?.try_into().expect("4 bytes");
+        Ok(u32::from_le_bytes(bytes))
+    }
+
+    /// Consume a `u64` in little endian.
+    fn u64(&mut self) -> Result<u64, DecodeCacheError> {
+        let bytes = self.take(8)?.try_into().expect("8 bytes");
+        Ok(u64::from_le_bytes(bytes))
+    }
+
+    /// Consume a length-prefixed string.
+    fn str(&mut self) -> Result<&'a str, DecodeCacheError> {
+        let len = self
+            .u64()?
+            .try_into()
+            .map_err(|_| DecodeCacheError::UnexpectedEnd)?;
+        let bytes = self.take(len)?;
+        str::from_utf8(bytes).map_err(|_| DecodeCacheError::InvalidUtf8)
+    }
+}
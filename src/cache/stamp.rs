@@ -0,0 +1,96 @@
+use sha2::{Digest, Sha256};
+use std::{
+    fs::{self, Metadata},
+    path::Path,
+    time::{Duration, UNIX_EPOCH},
+};
+
+/// Size, modification time, and SHA-256 checksum of a source archive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SourceStamp {
+    pub(super) size: u64,
+    pub(super) modified: Option<Duration>,
+    pub(super) sha256: [u8; 32],
+}
+
+impl SourceStamp {
+    /// Stamp the content of an archive along with its file metadata.
+    pub fn new(bytes: &[u8], metadata: &Metadata) -> Self {
+        SourceStamp {
+            size: bytes.len() as u64,
+            modified: modified(metadata),
+            sha256: Sha256::digest(bytes).into(),
+        }
+    }
+
+    /// Read and stamp a file.
+    pub fn from_file(path: &Path) -> std::io::Result<Self> {
+        let bytes = fs::read(path)?;
+        let metadata = fs::metadata(path)?;
+        Ok(SourceStamp::new(&bytes, &metadata))
+    }
+
+    /// Size of the archive in bytes.
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    /// Modification time of the archive since the Unix epoch, if the platform supports it.
+    pub fn modified(&self) -> Option<Duration> {
+        self.modified
+    }
+
+    /// SHA-256 checksum of the archive.
+    pub fn sha256(&self) -> &[u8; 32] {
+        &self.sha256
+    }
+
+    /// Check whether the file at `path` still matches this stamp.
+    ///
+    /// The checksum is only computed when the size matches but the modification time doesn't.
+    pub fn matches_file(&self, path: &Path) -> bool {
+        !matches!(self.compare_file(path), Comparison::Changed)
+    }
+
+    /// Compare the file at `path` against this stamp.
+    pub(super) fn compare_file(&self, path: &Path) -> Comparison {
+        let Ok(metadata) = fs::metadata(path) else {
+            return Comparison::Changed;
+        };
+        if metadata.len() != self.size {
+            return Comparison::Changed;
+        }
+        let modified = modified(&metadata);
+        if self.modified.is_some() && modified == self.modified {
+            return Comparison::Unchanged;
+        }
+        let same_content = fs::read(path)
+            .is_ok_and(|bytes| <[u8; 32]>::from(Sha256::digest(&bytes)) == self.sha256);
+        if same_content {
+            Comparison::Touched(modified)
+        } else {
+            Comparison::Changed
+        }
+    }
+
+    /// Make the stamp match no file, so that its repository is rebuilt upon refresh.
+    pub(super) fn invalidate(&mut self) {
+        self.modified = None;
+        self.sha256 = [0; 32];
+    }
+}
+
+/// Result of [`SourceStamp::compare_file`].
+pub(super) enum Comparison {
+    /// Neither the size nor the modification time has changed.
+    Unchanged,
+    /// Only the modification time has changed, the checksum still matches.
+    Touched(Option<Duration>),
+    /// The file is missing, or its content has changed.
+    Changed,
+}
+
+/// Get the modification time of a file since the Unix epoch.
+fn modified(metadata: &Metadata) -> Option<Duration> {
+    metadata.modified().ok()?.duration_since(UNIX_EPOCH).ok()
+}
@@ -0,0 +1,120 @@
+//! Parser of `pacman.conf`.
+
+mod load;
+mod parse;
+
+pub use parse::{ParsePacmanConfError, ParsePacmanConfErrorKind};
+
+use crate::value::RepositoryName;
+use std::path::{Path, PathBuf};
+
+/// Default value of `DBPath` when neither `DBPath` nor `RootDir` is set.
+const DEFAULT_DB_PATH: &str = "/var/lib/pacman/";
+
+/// Parsed content of a `pacman.conf` file and the files it includes.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct PacmanConf {
+    options: Options,
+    repositories: Vec<Repository>,
+}
+
+impl PacmanConf {
+    /// Settings under the `[options]` section.
+    pub fn options(&self) -> &Options {
+        &self.options
+    }
+
+    /// Repository sections in the order they were declared.
+    pub fn repositories(&self) -> &[Repository] {
+        &self.repositories
+    }
+
+    /// Get a repository section by name.
+    pub fn repository(&self, name: RepositoryName<'_>) -> Option<&Repository> {
+        self.repositories
+            .iter()
+            .find(|repository| repository.name() == name)
+    }
+
+    /// Names of the repositories in the order they were declared.
+    pub fn repository_names(&self) -> impl Iterator<Item = RepositoryName<'_>> {
+        self.repositories.iter().map(Repository::name)
+    }
+
+    /// Location of the pacman database as seen from inside the system.
+    ///
+    /// Follows pacman's rules: `DBPath` if set, otherwise `RootDir` joined with `var/lib/pacman/`,
+    /// otherwise `/var/lib/pacman/`.
+    pub fn db_path(&self) -> PathBuf {
+        match (&self.options.db_path, &self.options.root_dir) {
+            (Some(db_path), _) => db_path.clone(),
+            (None, Some(root_dir)) => root_dir.join("var/lib/pacman/"),
+            (None, None) => PathBuf::from(DEFAULT_DB_PATH),
+        }
+    }
+
+    /// Location of the sync directory of a system mounted at `root`.
+    ///
+    /// Pass `/` as `root` for the running system.
+    pub fn sync_dir(&self, root: &Path) -> PathBuf {
+        under_root(root, &self.db_path()).join("sync")
+    }
+
+    /// Location of the local database of a system mounted at `root`.
+    ///
+    /// Pass `/` as `root` for the running system.
+    pub fn local_db(&self, root: &Path) -> PathBuf {
+        under_root(root, &self.db_path()).join("local")
+    }
+}
+
+/// Settings under the `[options]` section of `pacman.conf`.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Options {
+    root_dir: Option<PathBuf>,
+    db_path: Option<PathBuf>,
+    architectures: Vec<String>,
+}
+
+impl Options {
+    /// Value of `RootDir`.
+    pub fn root_dir(&self) -> Option<&Path> {
+        self.root_dir.as_deref()
+    }
+
+    /// Value of `DBPath`.
+    pub fn db_path(&self) -> Option<&Path> {
+        self.db_path.as_deref()
+    }
+
+    /// Values of `Architecture`, which may include `auto`.
+    pub fn architectures(&self) -> impl Iterator<Item = &str> {
+        self.architectures.iter().map(String::as_str)
+    }
+}
+
+/// Repository section of `pacman.conf`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Repository {
+    name: String,
+    servers: Vec<String>,
+}
+
+impl Repository {
+    /// Name of the repository.
+    pub fn name(&self) -> RepositoryName<'_> {
+        RepositoryName(&self.name)
+    }
+
+    /// Values of `Server`, including those from included mirror lists, in the order they were declared.
+    pub fn servers(&self) -> impl Iterator<Item = &str> {
+        self.servers.iter().map(String::as_str)
+    }
+}
+
+/// Resolve an absolute path of a system mounted at `root`.
+fn under_root(root: &Path, path: &Path) -> PathBuf {
+    path.strip_prefix("/")
+        .map(|relative| root.join(relative))
+        .unwrap_or_else(|_| root.join(path))
+}
@@ -0,0 +1,161 @@
+use super::PacmanConf;
+use crate::{
+    MultiTextCollection, TextCollection,
+    text::{
+        archive::LoadArchiveError,
+        sync::{LoadSyncDbError, load_sync_db},
+    },
+    value::RepositoryName,
+};
+use rayon::prelude::*;
+use std::{io, path::Path};
+
+/// Load a sync database archive, or return `None` if it doesn't exist.
+fn load_existing_sync_db(path: &Path) -> Result<Option<TextCollection>, LoadSyncDbError> {
+    match load_sync_db(path) {
+        Ok(collection) => Ok(Some(collection)),
+        Err(error)
+            if matches!(
+                error.source(),
+                LoadArchiveError::Read(error) if error.kind() == io::ErrorKind::NotFound,
+            ) =>
+        {
+            Ok(None)
+        }
+        Err(error) => Err(error),
+    }
+}
+
+impl<'a> MultiTextCollection<'a> {
+    /// Load the sync database archive of every repository declared in a [`PacmanConf`] in the declared order.
+    ///
+    /// The archives are looked up in the sync directory of the system mounted at `root`.
+    /// Pass `/` as `root` for the running system.
+    pub fn extend_from_pacman_conf(
+        &mut self,
+        conf: &'a PacmanConf,
+        root: &Path,
+    ) -> Result<(), LoadSyncDbError> {
+        let sync_dir = conf.sync_dir(root);
+        for repository in conf.repository_names() {
+            let collection = load_sync_db(&sync_dir.join(format!("{repository}.db")))?;
+            self.insert(repository, collection);
+        }
+        Ok(())
+    }
+
+    /// Load the sync database archive of every repository declared in a [`PacmanConf`] in the declared order.
+    ///
+    /// The archives are looked up in the sync directory of the system mounted at `root`.
+    /// Pass `/` as `root` for the running system.
+    pub fn add_pacman_conf(
+        mut self,
+        conf: &'a PacmanConf,
+        root: &Path,
+    ) -> Result<Self, LoadSyncDbError> {
+        self.extend_from_pacman_conf(conf, root)?;
+        Ok(self)
+    }
+
+    /// Load the sync database archive of every repository declared in a [`PacmanConf`] in the declared order.
+    ///
+    /// The archives are looked up in the sync directory of the system mounted at `root`.
+    /// Pass `/` as `root` for the running system.
+    pub fn from_pacman_conf(conf: &'a PacmanConf, root: &Path) -> Result<Self, LoadSyncDbError> {
+        MultiTextCollection::with_capacity(conf.repositories().len()).add_pacman_conf(conf, root)
+    }
+
+    /// Load the sync database archive of every repository declared in a [`PacmanConf`] in parallel.
+    ///
+    /// The repositories are inserted in the declared order.
+    pub fn par_extend_from_pacman_conf(
+        &mut self,
+        conf: &'a PacmanConf,
+        root: &Path,
+    ) -> Result<(), LoadSyncDbError> {
+        let sync_dir = conf.sync_dir(root);
+        let collections = conf
+            .repositories()
+            .par_iter()
+            .map(|repository| {
+                let repository = repository.name();
+                load_sync_db(&sync_dir.join(format!("{repository}.db")))
+                    .map(|collection| (repository, collection))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        self.extend(collections);
+        Ok(())
+    }
+
+    /// Load the sync database archive of every repository declared in a [`PacmanConf`] in parallel.
+    ///
+    /// The repositories are inserted in the declared order.
+    pub fn par_add_pacman_conf(
+        mut self,
+        conf: &'a PacmanConf,
+        root: &Path,
+    ) -> Result<Self, LoadSyncDbError> {
+        self.par_extend_from_pacman_conf(conf, root)?;
+        Ok(self)
+    }
+
+    /// Load the sync database archive of every repository declared in a [`PacmanConf`] in parallel.
+    ///
+    /// The repositories are inserted in the declared order.
+    pub fn par_from_pacman_conf(
+        conf: &'a PacmanConf,
+        root: &Path,
+    ) -> Result<Self, LoadSyncDbError> {
+        MultiTextCollection::with_capacity(conf.repositories().len())
+            .par_add_pacman_conf(conf, root)
+    }
+
+    /// Load the sync database archive of every repository declared in a [`PacmanConf`] in the declared order,
+    /// skipping the repositories whose archives don't exist yet (such as before the first `pacman -Sy`).
+    ///
+    /// Return the names of the skipped repositories, like pacman, which only warns about them.
+    pub fn extend_from_pacman_conf_skip_missing(
+        &mut self,
+        conf: &'a PacmanConf,
+        root: &Path,
+    ) -> Result<Vec<RepositoryName<'a>>, LoadSyncDbError> {
+        let sync_dir = conf.sync_dir(root);
+        let mut missing = Vec::new();
+        for repository in conf.repository_names() {
+            match load_existing_sync_db(&sync_dir.join(format!("{repository}.db")))? {
+                Some(collection) => self.insert(repository, collection),
+                None => missing.push(repository),
+            }
+        }
+        Ok(missing)
+    }
+
+    /// Load the sync database archive of every repository declared in a [`PacmanConf`] in parallel,
+    /// skipping the repositories whose archives don't exist yet (such as before the first `pacman -Sy`).
+    ///
+    /// The repositories are inserted in the declared order. Return the names of the skipped repositories.
+    pub fn par_extend_from_pacman_conf_skip_missing(
+        &mut self,
+        conf: &'a PacmanConf,
+        root: &Path,
+    ) -> Result<Vec<RepositoryName<'a>>, LoadSyncDbError> {
+        let sync_dir = conf.sync_dir(root);
+        let collections = conf
+            .repositories()
+            .par_iter()
+            .map(|repository| {
+                let repository = repository.name();
+                load_existing_sync_db(&sync_dir.join(format!("{repository}.db")))
+                    .map(|collection| (repository, collection))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut missing = Vec::new();
+        for (repository, collection) in collections {
+            match collection {
+                Some(collection) => self.insert(repository, collection),
+                None => missing.push(repository),
+            }
+        }
+        Ok(missing)
+    }
+}
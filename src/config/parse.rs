@@ -0,0 +1,246 @@
+use super::{PacmanConf, Repository, under_root};
+use derive_more::{Display, Error};
+use pipe_trait::Pipe;
+use std::{
+    fs::read_to_string,
+    io,
+    path::{Path, PathBuf},
+};
+
+/// Maximum depth of nested `Include` directives, same as pacman's.
+const MAX_INCLUDE_DEPTH: usize = 10;
+
+/// Kind of [`ParsePacmanConfError`].
+#[derive(Debug, Display, Error)]
+pub enum ParsePacmanConfErrorKind {
+    #[display("Failed to read the file: {_0}")]
+    Read(io::Error),
+    #[display("Invalid glob pattern in Include: {_0}")]
+    Pattern(glob::PatternError),
+    #[display("Failed to read a path matched by Include: {_0}")]
+    Glob(glob::GlobError),
+    #[display("Directive doesn't belong to any section")]
+    NoSection,
+    #[display("Section header is not terminated by ']'")]
+    UnterminatedSection,
+    #[display("Directive {_0:?} requires a value")]
+    MissingValue(#[error(not(source))] String),
+    #[display("Include directives are nested too deeply")]
+    IncludeDepth,
+}
+
+/// Error when trying to parse a `pacman.conf` file or the files it includes.
+#[derive(Debug, Display, Error)]
+#[display("{}:{line}: {kind}", path.display())]
+pub struct ParsePacmanConfError {
+    #[error(source)]
+    kind: ParsePacmanConfErrorKind,
+    path: PathBuf,
+    line: usize,
+}
+
+impl ParsePacmanConfError {
+    /// Create an error.
+    fn new(kind: ParsePacmanConfErrorKind, path: &Path, line: usize) -> Self {
+        let path = path.to_path_buf();
+        ParsePacmanConfError { kind, path, line }
+    }
+
+    /// What went wrong.
+    pub fn kind(&self) -> &ParsePacmanConfErrorKind {
+        &self.kind
+    }
+
+    /// Path to the file in which the error occurred.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Line number at which the error occurred, or `0` if the error concerns the whole file.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+}
+
+/// Section which the following directives belong to.
+enum Section {
+    None,
+    Options,
+    Repository(usize),
+}
+
+/// State of parsing a `pacman.conf` and the files it includes.
+struct Parser<'r> {
+    root: &'r Path,
+    conf: PacmanConf,
+    section: Section,
+}
+
+impl Parser<'_> {
+    /// Parse the text of a file.
+    fn parse_text(
+        &mut self,
+        text: &str,
+        path: &Path,
+        depth: usize,
+    ) -> Result<(), ParsePacmanConfError> {
+        for (index, line) in text.lines().enumerate() {
+            let line_number = index + 1;
+            let error = |kind| ParsePacmanConfError::new(kind, path, line_number);
+
+            let line = line.split_once('#').map_or(line, |(line, _)| line).trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            if let Some(header) = line.strip_prefix('[') {
+                let name = header
+                    .strip_suffix(']')
+                    .ok_or_else(|| error(ParsePacmanConfErrorKind::UnterminatedSection))?;
+                self.enter_section(name);
+                continue;
+            }
+
+            let (key, value) = match line.split_once('=') {
+                Some((key, value)) => (key.trim(), Some(value.trim())),
+                None => (line, None),
+            };
+            let require_value = || {
+                value.ok_or_else(|| error(ParsePacmanConfErrorKind::MissingValue(key.to_string())))
+            };
+
+            if key == "Include" {
+                if matches!(self.section, Section::None) {
+                    return Err(error(ParsePacmanConfErrorKind::NoSection));
+                }
+                if depth >= MAX_INCLUDE_DEPTH {
+                    return Err(error(ParsePacmanConfErrorKind::IncludeDepth));
+                }
+                let pattern = require_value()?;
+                self.include(pattern, path, line_number, depth + 1)?;
+                continue;
+            }
+
+            match self.section {
+                Section::None => return Err(error(ParsePacmanConfErrorKind::NoSection)),
+                Section::Options => match key {
+                    "RootDir" => {
+                        self.conf.options.root_dir = require_value()?.pipe(PathBuf::from).pipe(Some)
+                    }
+                    "DBPath" => {
+                        self.conf.options.db_path = require_value()?.pipe(PathBuf::from).pipe(Some)
+                    }
+                    "Architecture" => self
+                        .conf
+                        .options
+                        .architectures
+                        .extend(require_value()?.split_whitespace().map(String::from)),
+                    _ => {}
+                },
+                Section::Repository(index) => {
+                    if key == "Server" {
+                        let server = require_value()?.to_string();
+                        self.conf.repositories[index].servers.push(server);
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Switch to a section, reopening the existing one if the name was already declared.
+    fn enter_section(&mut self, name: &str) {
+        if name == "options" {
+            self.section = Section::Options;
+            return;
+        }
+        let repositories = &mut self.conf.repositories;
+        let index = match repositories
+            .iter()
+            .position(|repository| repository.name == name)
+        {
+            Some(index) => index,
+            None => {
+                repositories.push(Repository {
+                    name: name.to_string(),
+                    servers: Vec::new(),
+                });
+                repositories.len() - 1
+            }
+        };
+        self.section = Section::Repository(index);
+    }
+
+    /// Parse the files matched by the pattern of an `Include` directive in the current section.
+    ///
+    /// Matched files are parsed in alphabetical order, like pacman does.
+    fn include(
+        &mut self,
+        pattern: &str,
+        path: &Path,
+        line: usize,
+        depth: usize,
+    ) -> Result<(), ParsePacmanConfError> {
+        // only the pattern of the directive may contain wildcards, not the root
+        let root = self
+            .root
+            .to_string_lossy()
+            .pipe_as_ref(glob::Pattern::escape);
+        let pattern = under_root(Path::new(&root), Path::new(pattern));
+        let mut included = pattern
+            .to_string_lossy()
+            .pipe_as_ref(glob::glob)
+            .map_err(|error| {
+                ParsePacmanConfError::new(ParsePacmanConfErrorKind::Pattern(error), path, line)
+            })?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|error| {
+                ParsePacmanConfError::new(ParsePacmanConfErrorKind::Glob(error), path, line)
+            })?;
+        included.sort();
+        for included in included {
+            self.parse_file(&included, depth)?;
+        }
+        Ok(())
+    }
+
+    /// Read and parse a file.
+    fn parse_file(&mut self, path: &Path, depth: usize) -> Result<(), ParsePacmanConfError> {
+        let text = read_to_string(path).map_err(|error| {
+            ParsePacmanConfError::new(ParsePacmanConfErrorKind::Read(error), path, 0)
+        })?;
+        self.parse_text(&text, path, depth)
+    }
+}
+
+impl PacmanConf {
+    /// Parse the text of a `pacman.conf` file of a system mounted at `root`.
+    ///
+    /// Paths in `Include` directives are resolved under `root`. Pass `/` as `root` for the running system.
+    /// `path` is only used to locate errors.
+    pub fn parse(text: &str, path: &Path, root: &Path) -> Result<Self, ParsePacmanConfError> {
+        let mut parser = Parser {
+            root,
+            conf: PacmanConf::default(),
+            section: Section::None,
+        };
+        parser.parse_text(text, path, 0)?;
+        Ok(parser.conf)
+    }
+
+    /// Read and parse a `pacman.conf` file of a system mounted at `root`.
+    ///
+    /// `path` is the path to the file as seen from inside the system (such as `/etc/pacman.conf`),
+    /// it and the paths in `Include` directives are resolved under `root`.
+    /// Pass `/` as `root` for the running system.
+    pub fn read(path: &Path, root: &Path) -> Result<Self, ParsePacmanConfError> {
+        let mut parser = Parser {
+            root,
+            conf: PacmanConf::default(),
+            section: Section::None,
+        };
+        parser.parse_file(&under_root(root, path), 0)?;
+        Ok(parser.conf)
+    }
+}
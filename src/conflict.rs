@@ -0,0 +1,213 @@
+//! Detect conflicts between packages to be installed and installed packages.
+
+use crate::{
+    QueryDatabase,
+    satisfy::{package_satisfies, provision_satisfies},
+};
+use arch_pkg_text::{
+    desc::Query,
+    value::{Dependency, Name, Version},
+};
+use derive_more::Display;
+use std::{collections::HashSet, fmt};
+
+/// Where a package in a [`Conflict`] comes from.
+#[derive(Debug, Display, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PackageOrigin {
+    /// The package is about to be installed.
+    #[display("target")]
+    Target,
+    /// The package has been installed.
+    #[display("installed")]
+    Installed,
+}
+
+/// How the conflicting package matched the `%CONFLICTS%` entry.
+#[derive(Debug, Clone, Copy)]
+pub enum ConflictMatch<'a> {
+    /// The entry matched the name and version of the conflicting package.
+    Name,
+    /// The entry matched a [`provides`](Query::provides) entry of the conflicting package.
+    Provision(Dependency<'a>),
+}
+
+/// Pair of packages in conflict because of a `%CONFLICTS%` entry of one of them.
+#[derive(Debug, Clone, Copy)]
+pub struct Conflict<'a> {
+    package: Name<'a>,
+    package_version: Option<Version<'a>>,
+    package_origin: PackageOrigin,
+    constraint: Dependency<'a>,
+    target: Name<'a>,
+    target_version: Option<Version<'a>>,
+    target_origin: PackageOrigin,
+    matched: ConflictMatch<'a>,
+}
+
+impl<'a> Conflict<'a> {
+    /// Name of the package whose `%CONFLICTS%` array lists the entry.
+    pub fn package(&self) -> Name<'a> {
+        self.package
+    }
+
+    /// Version of the package whose `%CONFLICTS%` array lists the entry.
+    pub fn package_version(&self) -> Option<Version<'a>> {
+        self.package_version
+    }
+
+    /// Where the package whose `%CONFLICTS%` array lists the entry comes from.
+    pub fn package_origin(&self) -> PackageOrigin {
+        self.package_origin
+    }
+
+    /// The `%CONFLICTS%` entry, including its version constraint.
+    pub fn constraint(&self) -> Dependency<'a> {
+        self.constraint
+    }
+
+    /// Name of the conflicting package.
+    pub fn target(&self) -> Name<'a> {
+        self.target
+    }
+
+    /// Version of the conflicting package.
+    pub fn target_version(&self) -> Option<Version<'a>> {
+        self.target_version
+    }
+
+    /// Where the conflicting package comes from.
+    pub fn target_origin(&self) -> PackageOrigin {
+        self.target_origin
+    }
+
+    /// How the conflicting package matched the entry.
+    pub fn matched(&self) -> ConflictMatch<'a> {
+        self.matched
+    }
+}
+
+/// Render a pacman-like message, e.g. `foo-1.0-1 and bar-2.1-1 are in conflict (bar>=2)`.
+///
+/// The constraint is omitted when it is merely the name of the conflicting package.
+impl<'a> fmt::Display for Conflict<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let version =
+            |version: Option<Version<'a>>| version.map_or("?", |version| version.as_str());
+        write!(
+            f,
+            "{}-{} and {}-{} are in conflict",
+            self.package,
+            version(self.package_version),
+            self.target,
+            version(self.target_version),
+        )?;
+        if self.constraint.as_str() != self.target.as_str() {
+            write!(f, " ({})", self.constraint)?;
+        }
+        Ok(())
+    }
+}
+
+/// Package taking part in the conflict check.
+struct Package<'a, Querier> {
+    name: Name<'a>,
+    querier: Querier,
+    origin: PackageOrigin,
+}
+
+/// Check whether `package` lists `target` in its `%CONFLICTS%` array.
+fn check_pair<'a>(
+    package: &Package<'a, impl Query<'a>>,
+    target: &Package<'a, impl Query<'a>>,
+    conflicts: &mut Vec<Conflict<'a>>,
+) {
+    if package.name == target.name {
+        return;
+    }
+    let target_version = target.querier.version();
+    for constraint in package.querier.conflicts().into_iter().flatten() {
+        let matched = if package_satisfies(constraint, Some(target.name), target_version, None) {
+            ConflictMatch::Name
+        } else if let Some(provision) = target
+            .querier
+            .provides()
+            .into_iter()
+            .flatten()
+            .find(|provision| provision_satisfies(constraint, *provision))
+        {
+            ConflictMatch::Provision(provision)
+        } else {
+            continue;
+        };
+        conflicts.push(Conflict {
+            package: package.name,
+            package_version: package.querier.version(),
+            package_origin: package.origin,
+            constraint,
+            target: target.name,
+            target_version,
+            target_origin: target.origin,
+            matched,
+        });
+    }
+}
+
+/// Find every pair of packages in conflict within `targets` and between `targets` and `installed`.
+///
+/// Installed packages which share their names with targets, or which match the `%REPLACES%` entries
+/// of targets, are skipped because they would be replaced by the targets. Each direction of a
+/// conflict is reported separately, sorted by the names of the packages.
+pub fn find_conflicts<'a, Target, Installed>(
+    targets: impl IntoIterator<Item = Target>,
+    installed: &'a QueryDatabase<'a, Installed>,
+) -> Vec<Conflict<'a>>
+where
+    Target: Query<'a>,
+    Installed: Query<'a>,
+{
+    let targets: Vec<_> = targets
+        .into_iter()
+        .filter_map(|querier| {
+            Some(Package {
+                name: querier.name()?,
+                querier,
+                origin: PackageOrigin::Target,
+            })
+        })
+        .collect();
+    let target_names: HashSet<_> = targets.iter().map(|target| target.name).collect();
+    let replaces: Vec<_> = targets
+        .iter()
+        .flat_map(|target| target.querier.replaces())
+        .flatten()
+        .collect();
+    let is_replaced = |name: Name<'a>, querier: &Installed| {
+        target_names.contains(&name)
+            || replaces
+                .iter()
+                .any(|replace| package_satisfies(*replace, Some(name), querier.version(), None))
+    };
+    let installed: Vec<_> = installed
+        .entries()
+        .map(|entry| entry.into_tuple())
+        .filter(|(name, querier)| !is_replaced(*name, querier))
+        .map(|(name, querier)| Package {
+            name,
+            querier,
+            origin: PackageOrigin::Installed,
+        })
+        .collect();
+
+    let mut conflicts = Vec::new();
+    for package in &targets {
+        for target in &targets {
+            check_pair(package, target, &mut conflicts);
+        }
+        for target in &installed {
+            check_pair(package, target, &mut conflicts);
+            check_pair(target, package, &mut conflicts);
+        }
+    }
+    conflicts.sort_by_key(|conflict| (conflict.package, conflict.target));
+    conflicts
+}
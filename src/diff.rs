@@ -0,0 +1,484 @@
+//! Compare two snapshots of the same database.
+
+use crate::{
+    MultiQueryDatabase, QueryDatabase, raw::RawFields, satisfy::vercmp, value::RepositoryName,
+};
+use arch_pkg_text::{
+    desc::{FieldName, Query},
+    value::{Name, Version},
+};
+use core::cmp::Ordering;
+use std::collections::HashMap;
+
+#[cfg(feature = "serde")]
+use core::fmt::Display;
+#[cfg(feature = "serde")]
+use serde::{Serialize, Serializer};
+
+/// Fields whose values are sets of dependencies, compared entry by entry.
+pub const DEPENDENCY_FIELDS: [FieldName; 7] = [
+    FieldName::Dependencies,
+    FieldName::MakeDependencies,
+    FieldName::CheckDependencies,
+    FieldName::OptionalDependencies,
+    FieldName::Provides,
+    FieldName::Conflicts,
+    FieldName::Replaces,
+];
+
+/// Fields whose values are compared as whole texts.
+///
+/// `%NAME%` and `%VERSION%` are absent because they are covered by
+/// [`DatabaseDiff::added`], [`DatabaseDiff::removed`], [`DatabaseDiff::upgraded`], and [`DatabaseDiff::downgraded`].
+pub const METADATA_FIELDS: [FieldName; 14] = [
+    FieldName::FileName,
+    FieldName::Base,
+    FieldName::Description,
+    FieldName::Groups,
+    FieldName::CompressedSize,
+    FieldName::InstalledSize,
+    FieldName::Md5Checksum,
+    FieldName::Sha256Checksum,
+    FieldName::PgpSignature,
+    FieldName::Url,
+    FieldName::License,
+    FieldName::Architecture,
+    FieldName::BuildDate,
+    FieldName::Packager,
+];
+
+/// Package which only exists in one of the snapshots.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct PackageEntry<'a> {
+    #[cfg_attr(feature = "serde", serde(serialize_with = "serialize_display"))]
+    name: Name<'a>,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "serialize_option_display"))]
+    version: Option<Version<'a>>,
+}
+
+impl<'a> PackageEntry<'a> {
+    /// Name of the package.
+    pub fn name(&self) -> Name<'a> {
+        self.name
+    }
+
+    /// Version of the package.
+    pub fn version(&self) -> Option<Version<'a>> {
+        self.version
+    }
+}
+
+/// Package whose version differs between the snapshots.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct VersionDiff<'a> {
+    #[cfg_attr(feature = "serde", serde(serialize_with = "serialize_display"))]
+    name: Name<'a>,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "serialize_display"))]
+    old: Version<'a>,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "serialize_display"))]
+    new: Version<'a>,
+}
+
+impl<'a> VersionDiff<'a> {
+    /// Name of the package.
+    pub fn name(&self) -> Name<'a> {
+        self.name
+    }
+
+    /// Version of the package in the old snapshot.
+    pub fn old_version(&self) -> Version<'a> {
+        self.old
+    }
+
+    /// Version of the package in the new snapshot.
+    pub fn new_version(&self) -> Version<'a> {
+        self.new
+    }
+}
+
+/// Entries added to or removed from one of the [`DEPENDENCY_FIELDS`] of a package.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct DependencyDiff<'a> {
+    #[cfg_attr(feature = "serde", serde(serialize_with = "serialize_display"))]
+    field: FieldName,
+    added: Vec<&'a str>,
+    removed: Vec<&'a str>,
+}
+
+impl<'a> DependencyDiff<'a> {
+    /// The field whose entries changed.
+    pub fn field(&self) -> FieldName {
+        self.field
+    }
+
+    /// Entries which only exist in the new snapshot, sorted.
+    pub fn added(&self) -> &[&'a str] {
+        &self.added
+    }
+
+    /// Entries which only exist in the old snapshot, sorted.
+    pub fn removed(&self) -> &[&'a str] {
+        &self.removed
+    }
+}
+
+/// Change of one of the [`METADATA_FIELDS`] of a package.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct FieldDiff<'a> {
+    #[cfg_attr(feature = "serde", serde(serialize_with = "serialize_display"))]
+    field: FieldName,
+    old: Option<&'a str>,
+    new: Option<&'a str>,
+}
+
+impl<'a> FieldDiff<'a> {
+    /// The field whose value changed.
+    pub fn field(&self) -> FieldName {
+        self.field
+    }
+
+    /// Raw value of the field in the old snapshot.
+    pub fn old_value(&self) -> Option<&'a str> {
+        self.old
+    }
+
+    /// Raw value of the field in the new snapshot.
+    pub fn new_value(&self) -> Option<&'a str> {
+        self.new
+    }
+}
+
+/// Package which exists in both snapshots but whose dependencies or metadata changed.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct PackageDiff<'a> {
+    #[cfg_attr(feature = "serde", serde(serialize_with = "serialize_display"))]
+    name: Name<'a>,
+    dependencies: Vec<DependencyDiff<'a>>,
+    fields: Vec<FieldDiff<'a>>,
+}
+
+impl<'a> PackageDiff<'a> {
+    /// Name of the package.
+    pub fn name(&self) -> Name<'a> {
+        self.name
+    }
+
+    /// Changed dependency fields, in the order of [`DEPENDENCY_FIELDS`].
+    pub fn dependencies(&self) -> &[DependencyDiff<'a>] {
+        &self.dependencies
+    }
+
+    /// Changed metadata fields, in the order of [`METADATA_FIELDS`].
+    pub fn fields(&self) -> &[FieldDiff<'a>] {
+        &self.fields
+    }
+}
+
+/// Result of comparing two snapshots of the same database.
+///
+/// All lists are sorted by package names. With the `serde` feature, it serializes into a map of the
+/// lists, so it can be rendered as JSON or YAML.
+#[derive(Debug, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct DatabaseDiff<'a> {
+    added: Vec<PackageEntry<'a>>,
+    removed: Vec<PackageEntry<'a>>,
+    upgraded: Vec<VersionDiff<'a>>,
+    downgraded: Vec<VersionDiff<'a>>,
+    changed: Vec<PackageDiff<'a>>,
+}
+
+impl<'a> DatabaseDiff<'a> {
+    /// Compare `old` against `new`.
+    pub fn new<OldQuerier, NewQuerier>(
+        old: &'a QueryDatabase<'a, OldQuerier>,
+        new: &'a QueryDatabase<'a, NewQuerier>,
+    ) -> Self
+    where
+        OldQuerier: Query<'a>,
+        NewQuerier: Query<'a>,
+    {
+        DatabaseDiff::from_entries(
+            old.entries().map(|entry| entry.into_tuple()),
+            new.entries().map(|entry| entry.into_tuple()),
+        )
+    }
+
+    /// Compare two sets of pairs of package names and queriers.
+    fn from_entries<OldQuerier, NewQuerier>(
+        old: impl IntoIterator<Item = (Name<'a>, OldQuerier)>,
+        new: impl IntoIterator<Item = (Name<'a>, NewQuerier)>,
+    ) -> Self
+    where
+        OldQuerier: Query<'a>,
+        NewQuerier: Query<'a>,
+    {
+        let mut diff = DatabaseDiff::default();
+        let mut old: HashMap<_, _> = old.into_iter().collect();
+
+        for (name, new_querier) in new {
+            let Some(old_querier) = old.remove(&name) else {
+                diff.added.push(PackageEntry {
+                    name,
+                    version: new_querier.version(),
+                });
+                continue;
+            };
+
+            if let (Some(old_version), Some(new_version)) =
+                (old_querier.version(), new_querier.version())
+            {
+                let change = VersionDiff {
+                    name,
+                    old: old_version,
+                    new: new_version,
+                };
+                match vercmp(new_version, old_version) {
+                    Ordering::Greater => diff.upgraded.push(change),
+                    Ordering::Less => diff.downgraded.push(change),
+                    Ordering::Equal => {}
+                }
+            }
+
+            let old_fields = RawFields::new(&old_querier);
+            let new_fields = RawFields::new(&new_querier);
+            let dependencies: Vec<_> = DEPENDENCY_FIELDS
+                .into_iter()
+                .filter_map(|field| {
+                    dependency_diff(field, old_fields.get(field), new_fields.get(field))
+                })
+                .collect();
+            let fields: Vec<_> = METADATA_FIELDS
+                .into_iter()
+                .map(|field| FieldDiff {
+                    field,
+                    old: old_fields.get(field),
+                    new: new_fields.get(field),
+                })
+                .filter(|change| change.old != change.new)
+                .collect();
+            if !dependencies.is_empty() || !fields.is_empty() {
+                diff.changed.push(PackageDiff {
+                    name,
+                    dependencies,
+                    fields,
+                });
+            }
+        }
+
+        diff.removed
+            .extend(old.into_iter().map(|(name, querier)| PackageEntry {
+                name,
+                version: querier.version(),
+            }));
+
+        diff.added.sort_by_key(|package| package.name);
+        diff.removed.sort_by_key(|package| package.name);
+        diff.upgraded.sort_by_key(|change| change.name);
+        diff.downgraded.sort_by_key(|change| change.name);
+        diff.changed.sort_by_key(|package| package.name);
+        diff
+    }
+
+    /// Packages which only exist in the new snapshot.
+    pub fn added(&self) -> &[PackageEntry<'a>] {
+        &self.added
+    }
+
+    /// Packages which only exist in the old snapshot.
+    pub fn removed(&self) -> &[PackageEntry<'a>] {
+        &self.removed
+    }
+
+    /// Packages whose versions in the new snapshot are newer.
+    pub fn upgraded(&self) -> &[VersionDiff<'a>] {
+        &self.upgraded
+    }
+
+    /// Packages whose versions in the new snapshot are older.
+    pub fn downgraded(&self) -> &[VersionDiff<'a>] {
+        &self.downgraded
+    }
+
+    /// Packages whose dependencies or metadata changed, regardless of their versions.
+    pub fn changed(&self) -> &[PackageDiff<'a>] {
+        &self.changed
+    }
+
+    /// Check whether the snapshots are the same.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.removed.is_empty()
+            && self.upgraded.is_empty()
+            && self.downgraded.is_empty()
+            && self.changed.is_empty()
+    }
+}
+
+/// Result of comparing two snapshots of the same set of repositories, one [`DatabaseDiff`] per repository.
+///
+/// Repositories are sorted by names. With the `serde` feature, it serializes into a map of repository
+/// names to their diffs.
+#[derive(Debug, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize), serde(transparent))]
+pub struct MultiDatabaseDiff<'a> {
+    #[cfg_attr(feature = "serde", serde(serialize_with = "serialize_repositories"))]
+    repositories: Vec<(RepositoryName<'a>, DatabaseDiff<'a>)>,
+}
+
+impl<'a> MultiDatabaseDiff<'a> {
+    /// Compare every repository in `old` against the repository of the same name in `new`.
+    ///
+    /// A repository which only exists in one snapshot is compared against an empty repository.
+    pub fn new<OldQuerier, NewQuerier>(
+        old: &'a MultiQueryDatabase<'a, OldQuerier>,
+        new: &'a MultiQueryDatabase<'a, NewQuerier>,
+    ) -> Self
+    where
+        OldQuerier: Query<'a>,
+        NewQuerier: Query<'a>,
+    {
+        fn split<'a, Item>(
+            entries: impl Iterator<Item = (Name<'a>, impl Iterator<Item = (RepositoryName<'a>, Item)>)>,
+        ) -> HashMap<&'a str, Vec<(Name<'a>, Item)>> {
+            let mut repositories: HashMap<_, Vec<_>> = HashMap::new();
+            for (name, queriers) in entries {
+                for (repository, querier) in queriers {
+                    repositories
+                        .entry(repository.as_str())
+                        .or_default()
+                        .push((name, querier));
+                }
+            }
+            repositories
+        }
+
+        let mut old = split(
+            old.entries()
+                .map(|(name, queriers)| (name, queriers.entries())),
+        );
+        let new = split(
+            new.entries()
+                .map(|(name, queriers)| (name, queriers.entries())),
+        );
+
+        let mut repositories: Vec<_> = new
+            .into_iter()
+            .map(|(repository, new)| {
+                let old = old.remove(repository).unwrap_or_default();
+                (
+                    RepositoryName(repository),
+                    DatabaseDiff::from_entries(old, new),
+                )
+            })
+            .collect();
+        repositories.extend(old.into_iter().map(|(repository, old)| {
+            (
+                RepositoryName(repository),
+                DatabaseDiff::from_entries(old, Vec::<(Name, &NewQuerier)>::new()),
+            )
+        }));
+        repositories.sort_by_key(|(repository, _)| repository.as_str());
+        MultiDatabaseDiff { repositories }
+    }
+
+    /// Get the diff of a repository.
+    pub fn get(&self, repository: RepositoryName) -> Option<&DatabaseDiff<'a>> {
+        self.repositories
+            .iter()
+            .find(|(name, _)| *name == repository)
+            .map(|(_, diff)| diff)
+    }
+
+    /// Get an iterator over all pairs of repository names and their diffs.
+    pub fn iter(&self) -> impl Iterator<Item = (RepositoryName<'a>, &DatabaseDiff<'a>)> {
+        self.repositories
+            .iter()
+            .map(|(repository, diff)| (*repository, diff))
+    }
+
+    /// Check whether the snapshots of all repositories are the same.
+    pub fn is_empty(&self) -> bool {
+        self.repositories.iter().all(|(_, diff)| diff.is_empty())
+    }
+}
+
+/// Get the deduplicated and sorted entries of a dependency field.
+fn sorted_entries(raw: Option<&str>) -> Vec<&str> {
+    let mut entries: Vec<_> = raw
+        .into_iter()
+        .flat_map(str::lines)
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect();
+    entries.sort_unstable();
+    entries.dedup();
+    entries
+}
+
+/// Compare the entries of a dependency field.
+fn dependency_diff<'a>(
+    field: FieldName,
+    old: Option<&'a str>,
+    new: Option<&'a str>,
+) -> Option<DependencyDiff<'a>> {
+    let old = sorted_entries(old);
+    let new = sorted_entries(new);
+    let added: Vec<_> = new
+        .iter()
+        .filter(|entry| old.binary_search(entry).is_err())
+        .copied()
+        .collect();
+    let removed: Vec<_> = old
+        .iter()
+        .filter(|entry| new.binary_search(entry).is_err())
+        .copied()
+        .collect();
+    if added.is_empty() && removed.is_empty() {
+        return None;
+    }
+    Some(DependencyDiff {
+        field,
+        added,
+        removed,
+    })
+}
+
+/// Serialize a value as its string representation.
+#[cfg(feature = "serde")]
+fn serialize_display<S: Serializer>(
+    value: &impl Display,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(value)
+}
+
+/// Serialize an optional value as its string representation or as nothing.
+#[cfg(feature = "serde")]
+fn serialize_option_display<S: Serializer>(
+    value: &Option<impl Display>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    match value {
+        Some(value) => serializer.collect_str(value),
+        None => serializer.serialize_none(),
+    }
+}
+
+/// Serialize the diffs of repositories as a map.
+#[cfg(feature = "serde")]
+fn serialize_repositories<S: Serializer>(
+    repositories: &[(RepositoryName, DatabaseDiff)],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.collect_map(
+        repositories
+            .iter()
+            .map(|(repository, diff)| (repository.as_str(), diff)),
+    )
+}
@@ -0,0 +1,107 @@
+//! Database of the files owned by packages.
+
+mod check;
+mod get;
+mod insert;
+mod iter;
+mod misc;
+mod new;
+
+pub use check::{CheckFileError, FileStatus};
+pub use iter::{Names, Owners};
+
+use crate::value::{BackupList, FileList, Mtree};
+use arch_pkg_text::desc::{EagerQuerier, MemoQuerier};
+use derive_more::{AsMut, AsRef, Deref, DerefMut};
+use std::collections::HashMap;
+
+/// Querier attached to a [`FileList`].
+#[derive(Debug, Clone, Copy, AsRef, AsMut, Deref, DerefMut)]
+pub struct WithFileList<'a, Querier> {
+    #[deref]
+    #[deref_mut]
+    querier: Querier,
+
+    #[as_ref(skip)]
+    #[as_mut(skip)]
+    files: FileList<'a>,
+
+    #[as_ref(skip)]
+    #[as_mut(skip)]
+    backup: BackupList<'a>,
+
+    #[as_ref(skip)]
+    #[as_mut(skip)]
+    mtree: Option<Mtree<'a>>,
+}
+
+impl<'a, Querier> WithFileList<'a, Querier> {
+    /// Pair a querier with a file list.
+    pub const fn new(querier: Querier, files: FileList<'a>) -> Self {
+        WithFileList {
+            querier,
+            files,
+            backup: BackupList(""),
+            mtree: None,
+        }
+    }
+
+    /// Attach a list of backup files.
+    pub fn with_backup(self, backup: BackupList<'a>) -> Self {
+        WithFileList { backup, ..self }
+    }
+
+    /// Attach an mtree.
+    pub fn with_mtree(self, mtree: Mtree<'a>) -> Self {
+        WithFileList {
+            mtree: Some(mtree),
+            ..self
+        }
+    }
+
+    /// Separate the querier from the file list.
+    pub fn into_tuple(attached: Self) -> (Querier, FileList<'a>) {
+        (attached.querier, attached.files)
+    }
+
+    /// Get the list of files attached to the querier.
+    pub fn files(attached: &Self) -> FileList<'a> {
+        attached.files
+    }
+
+    /// Get the list of backup files attached to the querier.
+    pub fn backup(attached: &Self) -> BackupList<'a> {
+        attached.backup
+    }
+
+    /// Get the mtree attached to the querier.
+    pub fn mtree(attached: &Self) -> Option<Mtree<'a>> {
+        attached.mtree
+    }
+}
+
+/// Database to lookup packages and the files they own.
+///
+/// This type of database is built from the `.files` archives of sync repositories or from the local pacman database,
+/// and answers questions such as:
+/// * Which packages own a certain file?
+/// * Which files are owned by a certain package?
+/// * Which backup files of the installed packages have been modified?
+#[derive(Debug, Clone)]
+pub struct FileOwnerDatabase<'a, Querier> {
+    /// Map package names to their queriers and file lists.
+    packages: HashMap<&'a str, WithFileList<'a, Querier>>,
+    /// Map normalized file paths to the names of the packages which own them.
+    owners: HashMap<&'a str, Vec<&'a str>>,
+}
+
+/// Database to lookup eager queriers and the files they own.
+pub type EagerFileOwnerDatabase<'a> = FileOwnerDatabase<'a, EagerQuerier<'a>>;
+
+/// Database to lookup memo queriers and the files they own.
+pub type MemoFileOwnerDatabase<'a> = FileOwnerDatabase<'a, MemoQuerier<'a>>;
+
+/// Remove the leading and trailing slashes of a path so that `/usr/bin/`, `/usr/bin`, and `usr/bin` are treated the same.
+fn normalize_path(path: &str) -> &str {
+    path.trim_matches('/')
+}
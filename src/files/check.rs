@@ -0,0 +1,167 @@
+use super::{FileOwnerDatabase, WithFileList};
+use crate::value::{BackupEntry, MtreeEntry};
+use arch_pkg_text::value::Name;
+use derive_more::{Display, Error};
+use md5::Md5;
+use pipe_trait::Pipe;
+use sha2::{Digest, Sha256};
+use std::{
+    fs::{File, read_link, symlink_metadata},
+    io::{self, ErrorKind},
+    path::{Path, PathBuf},
+};
+
+/// Status of a file on the filesystem compared to what was recorded by the package database.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileStatus {
+    /// The file matches the record.
+    Unmodified,
+    /// The file differs from the record.
+    Modified,
+    /// The file doesn't exist.
+    Missing,
+}
+
+/// Error when trying to check a file on the filesystem.
+#[derive(Debug, Display, Error)]
+#[display("Failed to check {path:?}: {error}")]
+pub struct CheckFileError {
+    #[error(source)]
+    error: io::Error,
+    path: PathBuf,
+}
+
+impl CheckFileError {
+    /// Create an error.
+    fn new(error: io::Error, path: PathBuf) -> Self {
+        Self { error, path }
+    }
+
+    /// The source of this error.
+    pub fn source(&self) -> &io::Error {
+        &self.error
+    }
+
+    /// Path to the file being checked.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+/// Compute the lowercase hexadecimal digest of a file.
+fn hex_digest<Hasher: Digest + io::Write>(path: &Path) -> io::Result<String> {
+    let mut hasher = Hasher::new();
+    io::copy(&mut File::open(path)?, &mut hasher)?;
+    hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect::<String>()
+        .pipe(Ok)
+}
+
+/// Turn the result of a check into a [`FileStatus`] with missing files treated as [`FileStatus::Missing`].
+fn handle_missing(
+    result: io::Result<FileStatus>,
+    path: PathBuf,
+) -> Result<FileStatus, CheckFileError> {
+    match result {
+        Ok(status) => Ok(status),
+        Err(error) if error.kind() == ErrorKind::NotFound => Ok(FileStatus::Missing),
+        Err(error) => Err(CheckFileError::new(error, path)),
+    }
+}
+
+impl BackupEntry<'_> {
+    /// Compare the md5 checksum of the backup file under `root` against the recorded checksum.
+    pub fn check(&self, root: &Path) -> Result<FileStatus, CheckFileError> {
+        let path = root.join(self.path().as_str());
+        let result = hex_digest::<Md5>(&path).map(|digest| {
+            if digest.eq_ignore_ascii_case(self.md5()) {
+                FileStatus::Unmodified
+            } else {
+                FileStatus::Modified
+            }
+        });
+        handle_missing(result, path)
+    }
+}
+
+impl MtreeEntry<'_> {
+    /// Compare the file under `root` against the type, size, link target, and checksum recorded by the entry.
+    pub fn check(&self, root: &Path) -> Result<FileStatus, CheckFileError> {
+        let path = root.join(&*self.path());
+        let result = (|| {
+            let metadata = symlink_metadata(&path)?;
+            let matched = match self.entry_type().unwrap_or("file") {
+                "dir" => metadata.is_dir(),
+                "link" => {
+                    metadata.is_symlink()
+                        && self.link().is_none_or(|link| {
+                            read_link(&path).is_ok_and(|target| target == Path::new(link))
+                        })
+                }
+                "file" => {
+                    metadata.is_file()
+                        && self.size().is_none_or(|size| size == metadata.len())
+                        && match (self.sha256_digest(), self.md5_digest()) {
+                            (Some(sha256), _) => {
+                                hex_digest::<Sha256>(&path)?.eq_ignore_ascii_case(sha256)
+                            }
+                            (None, Some(md5)) => {
+                                hex_digest::<Md5>(&path)?.eq_ignore_ascii_case(md5)
+                            }
+                            (None, None) => true,
+                        }
+                }
+                _ => true,
+            };
+            Ok(if matched {
+                FileStatus::Unmodified
+            } else {
+                FileStatus::Modified
+            })
+        })();
+        handle_missing(result, path)
+    }
+}
+
+impl<'a, Querier> FileOwnerDatabase<'a, Querier> {
+    /// Find all backup files under `root` whose checksums differ from the records, similar to `pacman -Qii`.
+    ///
+    /// Unmodified backup files are omitted.
+    pub fn modified_backups(
+        &self,
+        root: &Path,
+    ) -> Result<Vec<(Name<'a>, BackupEntry<'a>, FileStatus)>, CheckFileError> {
+        let mut modified = Vec::new();
+        for (name, attached) in &self.packages {
+            for entry in WithFileList::backup(attached) {
+                let status = entry.check(root)?;
+                if status != FileStatus::Unmodified {
+                    modified.push((Name(name), entry, status));
+                }
+            }
+        }
+        Ok(modified)
+    }
+
+    /// Find all files of a package under `root` which differ from its mtree, similar to `pacman -Qkk`.
+    ///
+    /// Unmodified files and package metadata are omitted.
+    /// Returns `None` if the package doesn't exist or has no mtree.
+    pub fn modified_files(
+        &self,
+        name: Name,
+        root: &Path,
+    ) -> Option<Result<Vec<(MtreeEntry<'a>, FileStatus)>, CheckFileError>> {
+        let mtree = self.mtree(name)?;
+        mtree
+            .entries()
+            .filter(|entry| !entry.is_metadata())
+            .map(|entry| entry.check(root).map(|status| (entry, status)))
+            .filter(|result| !matches!(result, Ok((_, FileStatus::Unmodified))))
+            .collect::<Result<Vec<_>, _>>()
+            .pipe(Some)
+    }
+}
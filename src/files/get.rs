@@ -0,0 +1,30 @@
+use super::{FileOwnerDatabase, WithFileList};
+use crate::value::{BackupList, FileList, Mtree};
+use arch_pkg_text::value::Name;
+
+impl<'a, Querier> FileOwnerDatabase<'a, Querier> {
+    /// Get an immutable reference to a querier and its file list by package name.
+    pub fn get(&self, name: Name) -> Option<&WithFileList<'a, Querier>> {
+        self.packages.get(name.as_str())
+    }
+
+    /// Get a mutable reference to a querier and its file list by package name.
+    pub fn get_mut(&mut self, name: Name) -> Option<&mut WithFileList<'a, Querier>> {
+        self.packages.get_mut(name.as_str())
+    }
+
+    /// Get the list of files owned by a package.
+    pub fn files(&self, name: Name) -> Option<FileList<'a>> {
+        self.get(name).map(WithFileList::files)
+    }
+
+    /// Get the list of backup files of a package.
+    pub fn backup(&self, name: Name) -> Option<BackupList<'a>> {
+        self.get(name).map(WithFileList::backup)
+    }
+
+    /// Get the mtree of a package.
+    pub fn mtree(&self, name: Name) -> Option<Mtree<'a>> {
+        self.get(name).and_then(WithFileList::mtree)
+    }
+}
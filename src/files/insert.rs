@@ -0,0 +1,79 @@
+use super::{FileOwnerDatabase, WithFileList, normalize_path};
+use crate::single::InsertError;
+use arch_pkg_text::{
+    desc::{Query, QueryMut},
+    misc::desc::ShouldReuse,
+    value::Name,
+};
+
+impl<'a, Querier: ShouldReuse> FileOwnerDatabase<'a, Querier> {
+    /// Add a querier of a `desc` file and the data of its `files` file to the database.
+    ///
+    /// If an older querier already occupied the same [name](Name), it will be returned inside `Ok(Some(_))`.
+    fn insert_with<GetName>(
+        &mut self,
+        mut attached: WithFileList<'a, Querier>,
+        get_name: GetName,
+    ) -> Result<Option<WithFileList<'a, Querier>>, InsertError>
+    where
+        GetName: FnOnce(&mut Querier) -> Option<Name<'a>>,
+    {
+        let name = get_name(&mut attached.querier)
+            .ok_or(InsertError::NoName)?
+            .as_str();
+        let files = attached.files;
+        let old = self.packages.insert(name, attached);
+
+        if let Some(old) = &old {
+            for path in WithFileList::files(old) {
+                let path = normalize_path(&path);
+                let Some(owners) = self.owners.get_mut(path) else {
+                    continue;
+                };
+                owners.retain(|owner| *owner != name);
+                if owners.is_empty() {
+                    self.owners.remove(path);
+                }
+            }
+        }
+
+        for path in files {
+            let path = normalize_path(path.as_str());
+            if path.is_empty() {
+                continue;
+            }
+            let owners = self.owners.entry(path).or_default();
+            if !owners.contains(&name) {
+                owners.push(name);
+            }
+        }
+
+        Ok(old)
+    }
+
+    /// Add an [immutable querier](Query) of a `desc` file and the data of its `files` file to the database.
+    ///
+    /// If an older querier already occupied the same [name](Name), it will be returned inside `Ok(Some(_))`.
+    pub fn insert(
+        &mut self,
+        attached: WithFileList<'a, Querier>,
+    ) -> Result<Option<WithFileList<'a, Querier>>, InsertError>
+    where
+        Querier: Query<'a>,
+    {
+        self.insert_with(attached, |querier| querier.name())
+    }
+
+    /// Add a [mutable querier](QueryMut) of a `desc` file and the data of its `files` file to the database.
+    ///
+    /// If an older querier already occupied the same [name](Name), it will be returned inside `Ok(Some(_))`.
+    pub fn insert_mut(
+        &mut self,
+        attached: WithFileList<'a, Querier>,
+    ) -> Result<Option<WithFileList<'a, Querier>>, InsertError>
+    where
+        Querier: QueryMut<'a>,
+    {
+        self.insert_with(attached, Querier::name_mut)
+    }
+}
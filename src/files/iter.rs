@@ -0,0 +1,89 @@
+use super::{FileOwnerDatabase, WithFileList, normalize_path};
+use crate::single::Entry;
+use arch_pkg_text::value::Name;
+use core::{iter::FusedIterator, slice};
+use std::collections::{HashMap, hash_map::Keys};
+
+/// [Iterator] over all [package names](Name) in a [`FileOwnerDatabase`].
+#[derive(Debug, Clone)]
+pub struct Names<'r, 'a, Querier> {
+    internal: Keys<'r, &'a str, WithFileList<'a, Querier>>,
+}
+
+impl<'a, Querier> Iterator for Names<'_, 'a, Querier> {
+    type Item = Name<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.internal.next().copied().map(Name)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.internal.size_hint()
+    }
+
+    fn count(self) -> usize {
+        self.internal.count()
+    }
+}
+
+impl<Querier> ExactSizeIterator for Names<'_, '_, Querier> {
+    fn len(&self) -> usize {
+        self.internal.len()
+    }
+}
+
+impl<Querier> FusedIterator for Names<'_, '_, Querier> {}
+
+/// [Iterator] over all packages which own a certain file in a [`FileOwnerDatabase`].
+#[derive(Debug, Clone)]
+pub struct Owners<'r, 'a, Querier> {
+    names: slice::Iter<'r, &'a str>,
+    packages: &'r HashMap<&'a str, WithFileList<'a, Querier>>,
+}
+
+impl<'r, 'a, Querier> Iterator for Owners<'r, 'a, Querier> {
+    type Item = Entry<'a, &'r Querier>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let name = *self.names.next()?;
+        let querier = self.packages.get(name)?;
+        Some(Entry::new_unchecked(name, &querier.querier))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.names.size_hint()
+    }
+}
+
+impl<Querier> ExactSizeIterator for Owners<'_, '_, Querier> {
+    fn len(&self) -> usize {
+        self.names.len()
+    }
+}
+
+impl<Querier> FusedIterator for Owners<'_, '_, Querier> {}
+
+impl<'a, Querier> FileOwnerDatabase<'a, Querier> {
+    /// Get an iterator over all [package names](Name).
+    pub fn names(&self) -> Names<'_, 'a, Querier> {
+        Names {
+            internal: self.packages.keys(),
+        }
+    }
+
+    /// Get an iterator over all packages which own a file or a directory.
+    ///
+    /// Leading and trailing slashes of `path` are ignored, so `/usr/bin/bash` and `usr/bin/bash` are equivalent.
+    pub fn owners(&self, path: &str) -> Owners<'_, 'a, Querier> {
+        let names = self
+            .owners
+            .get(normalize_path(path))
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+            .iter();
+        Owners {
+            names,
+            packages: &self.packages,
+        }
+    }
+}
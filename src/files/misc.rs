@@ -0,0 +1,13 @@
+use super::FileOwnerDatabase;
+
+impl<Querier> FileOwnerDatabase<'_, Querier> {
+    /// The number of packages within the database.
+    pub fn len(&self) -> usize {
+        self.packages.len()
+    }
+
+    /// Whether the database is empty.
+    pub fn is_empty(&self) -> bool {
+        self.packages.is_empty()
+    }
+}
@@ -0,0 +1,26 @@
+use super::FileOwnerDatabase;
+use std::collections::HashMap;
+
+impl<Querier> FileOwnerDatabase<'_, Querier> {
+    /// Create an empty database.
+    pub fn new() -> Self {
+        FileOwnerDatabase {
+            packages: HashMap::new(),
+            owners: HashMap::new(),
+        }
+    }
+
+    /// Create an empty database with at least the specified capacity of packages.
+    pub fn with_capacity(capacity: usize) -> Self {
+        FileOwnerDatabase {
+            packages: HashMap::with_capacity(capacity),
+            owners: HashMap::new(),
+        }
+    }
+}
+
+impl<Querier> Default for FileOwnerDatabase<'_, Querier> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
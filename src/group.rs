@@ -0,0 +1,163 @@
+//! Package group indices, i.e. `pacman -Sg`.
+
+use crate::{MultiQueryDatabase, QueryDatabase, value::RepositoryName};
+use arch_pkg_text::{
+    desc::Query,
+    value::{Group, Name},
+};
+use core::cell::OnceCell;
+use std::collections::HashMap;
+
+/// Map group names to their members.
+type MemberMap<'a, Member> = HashMap<&'a str, Vec<Member>>;
+
+/// Build a map of group names to members, each list of members sorted by `sort_key`.
+fn build_map<'a, 'q, Member, Querier, Key>(
+    packages: impl IntoIterator<Item = (Member, &'q Querier)>,
+    sort_key: impl Fn(&Member) -> Key,
+) -> MemberMap<'a, Member>
+where
+    Member: Copy + Eq,
+    Querier: Query<'a> + 'q,
+    Key: Ord,
+{
+    let mut map = MemberMap::new();
+    for (member, querier) in packages {
+        for group in querier.groups().into_iter().flatten() {
+            let group = group.as_str().trim();
+            if group.is_empty() {
+                continue;
+            }
+            // each member is visited once, so a repeated group can only be found at the end
+            let members = map.entry(group).or_default();
+            if members.last() != Some(&member) {
+                members.push(member);
+            }
+        }
+    }
+    for members in map.values_mut() {
+        members.sort_by_key(&sort_key);
+    }
+    map
+}
+
+/// Sorted names of all groups in a map.
+fn sorted_groups<'a, Member>(map: &MemberMap<'a, Member>) -> Vec<Group<'a>> {
+    let mut groups: Vec<_> = map.keys().copied().map(Group).collect();
+    groups.sort_by_key(|group| group.as_str());
+    groups
+}
+
+/// Index to lookup the members of package groups in a [`QueryDatabase`].
+///
+/// The index is only built when it is first needed.
+///
+/// This index is created by calling [`QueryDatabase::group_index`].
+#[derive(Debug)]
+pub struct GroupIndex<'a, Querier> {
+    db: &'a QueryDatabase<'a, Querier>,
+    map: OnceCell<MemberMap<'a, Name<'a>>>,
+}
+
+impl<'a, Querier: Query<'a>> GroupIndex<'a, Querier> {
+    /// Get the map of groups, build it if it hasn't been built.
+    fn map(&self) -> &MemberMap<'a, Name<'a>> {
+        self.map.get_or_init(|| {
+            let packages = self.db.entries().map(|entry| entry.into_tuple());
+            build_map(packages, |name| name.as_str())
+        })
+    }
+
+    /// Get the names of all groups, sorted.
+    pub fn groups(&self) -> Vec<Group<'a>> {
+        sorted_groups(self.map())
+    }
+
+    /// Get the packages which belong to `group`, sorted by name.
+    pub fn group_members(&self, group: Group) -> &[Name<'a>] {
+        self.map().get(group.as_str()).map_or(&[], Vec::as_slice)
+    }
+}
+
+impl<'a, Querier> QueryDatabase<'a, Querier> {
+    /// Create an index to lookup the members of package groups.
+    pub fn group_index(&'a self) -> GroupIndex<'a, Querier> {
+        GroupIndex {
+            db: self,
+            map: OnceCell::new(),
+        }
+    }
+}
+
+/// Index to lookup the members of package groups in a [`MultiQueryDatabase`].
+///
+/// Each member is identified by its repository and package name. The index is only built when it is
+/// first needed.
+///
+/// This index is created by calling [`MultiQueryDatabase::group_index`].
+#[derive(Debug)]
+pub struct MultiGroupIndex<'a, Querier> {
+    db: &'a MultiQueryDatabase<'a, Querier>,
+    map: OnceCell<MemberMap<'a, (RepositoryName<'a>, Name<'a>)>>,
+}
+
+impl<'a, Querier: Query<'a>> MultiGroupIndex<'a, Querier> {
+    /// Get the map of groups, build it if it hasn't been built.
+    fn map(&self) -> &MemberMap<'a, (RepositoryName<'a>, Name<'a>)> {
+        self.map.get_or_init(|| {
+            let packages = self.db.entries().flat_map(|(name, queriers)| {
+                queriers
+                    .entries()
+                    .map(move |(repository, querier)| ((repository, name), querier))
+            });
+            build_map(packages, |(repository, name)| {
+                (name.as_str(), repository.as_str())
+            })
+        })
+    }
+
+    /// Get the names of all groups in any repository, sorted.
+    pub fn groups(&self) -> Vec<Group<'a>> {
+        sorted_groups(self.map())
+    }
+
+    /// Get the packages from all repositories which belong to `group`, sorted by name then repository.
+    pub fn group_members(&self, group: Group) -> &[(RepositoryName<'a>, Name<'a>)] {
+        self.map().get(group.as_str()).map_or(&[], Vec::as_slice)
+    }
+
+    /// Get the names of the groups which have members in `repository`, sorted.
+    pub fn repository_groups(&self, repository: RepositoryName) -> Vec<Group<'a>> {
+        let mut groups: Vec<_> = self
+            .map()
+            .iter()
+            .filter(|(_, members)| members.iter().any(|(item, _)| *item == repository))
+            .map(|(group, _)| Group(group))
+            .collect();
+        groups.sort_by_key(|group| group.as_str());
+        groups
+    }
+
+    /// Get the packages from `repository` which belong to `group`, sorted by name.
+    pub fn repository_group_members(
+        &self,
+        repository: RepositoryName,
+        group: Group,
+    ) -> Vec<Name<'a>> {
+        self.group_members(group)
+            .iter()
+            .filter(|(item, _)| *item == repository)
+            .map(|(_, name)| *name)
+            .collect()
+    }
+}
+
+impl<'a, Querier> MultiQueryDatabase<'a, Querier> {
+    /// Create an index to lookup the members of package groups.
+    pub fn group_index(&'a self) -> MultiGroupIndex<'a, Querier> {
+        MultiGroupIndex {
+            db: self,
+            map: OnceCell::new(),
+        }
+    }
+}
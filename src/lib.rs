@@ -1,13 +1,37 @@
 #![doc = include_str!("../README.md")]
 
+pub mod architecture;
+pub mod cache;
+pub mod config;
+pub mod conflict;
+pub mod diff;
+pub mod files;
+pub mod group;
 pub mod misc;
 pub mod multi;
+pub mod orphan;
+pub mod owned;
+pub mod pkginfo;
+mod raw;
+pub mod resolve;
+pub mod reverse;
+pub mod satisfy;
+pub mod search;
+#[cfg(feature = "serde")]
+pub mod serialize;
 pub mod single;
+pub mod srcinfo;
 pub mod text;
+pub mod upgrade;
+pub mod write;
 
+pub use files::{EagerFileOwnerDatabase, FileOwnerDatabase, MemoFileOwnerDatabase};
 pub use multi::{EagerMultiQueryDatabase, MemoMultiQueryDatabase, MultiQueryDatabase};
+pub use owned::PackageDesc;
 pub use single::{EagerQueryDatabase, MemoQueryDatabase, QueryDatabase};
-pub use text::{MultiTextCollection, Text, TextCollection};
+pub use text::{
+    BorrowedTextCollection, FilesCollection, MultiTextCollection, Text, TextCollection,
+};
 
 pub mod desc {
     //! Fields, queriers, and parser of the text format of `desc` files.
@@ -22,8 +46,16 @@ pub mod desc {
 
 pub mod value {
     //! Value types used by the database.
+    mod backup_list;
+    mod file_list;
+    mod install_reason;
+    mod mtree;
     mod repository;
 
     pub use arch_pkg_text::value::*;
+    pub use backup_list::{BackupEntry, BackupList, BackupListIter};
+    pub use file_list::{FileList, FileListIter};
+    pub use install_reason::InstallReason;
+    pub use mtree::{Mtree, MtreeEntries, MtreeEntry};
     pub use repository::RepositoryName;
 }
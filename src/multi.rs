@@ -7,25 +7,33 @@ mod iter;
 mod latest;
 mod misc;
 mod new;
+mod prioritized;
 mod providers;
+mod satisfiers;
 mod with;
 
 pub use insert::{InsertError, InsertNewerReturn};
 pub use iter::{
     Entries, EntriesMut, LatestEntries, LatestEntriesMut, LatestQueriers, LatestQueriersMut,
     MultiEntries, MultiEntriesMut, MultiOwnedEntries, MultiQueriers, MultiQueriersMut, Names,
-    OwnedEntries, Queriers, QueriersMut, RepositoryNames,
+    OwnedEntries, PrioritizedEntries, PrioritizedEntriesMut, PrioritizedQueriers,
+    PrioritizedQueriersMut, Queriers, QueriersMut, RepositoryNames,
 };
-pub use providers::{AlternativeProviders, AlternativeProvidersMut};
+pub use providers::{
+    AlternativeProviders, AlternativeProvidersMut, PrioritizedProviders, PrioritizedProvidersMut,
+};
+pub use satisfiers::{Satisfiers, SatisfiersMut};
 pub use with::{
     IntoWithParsedVersion, IntoWithRepositoryName, WithParsedVersion, WithParsedVersionUtils,
     WithRepositoryName, WithRepositoryNameUtils,
 };
 
+use crate::value::RepositoryName;
 use arch_pkg_text::desc::{EagerQuerier, MemoQuerier};
 use std::collections::HashMap;
 
-/// Return type of [`MultiQuerier::latest`] and [`MultiQuerier::latest_mut`].
+/// Return type of [`MultiQuerier::latest`], [`MultiQuerier::latest_mut`], [`MultiQuerier::prioritized`],
+/// and [`MultiQuerier::prioritized_mut`].
 type LatestQuerier<'a, Querier> = WithRepositoryName<'a, WithParsedVersion<'a, Querier>>;
 
 /// Queriers of multiple same-name packages from different repositories.
@@ -48,7 +56,15 @@ pub struct MultiQueryDatabase<'a, Querier> {
 /// Database view to lookup queriers of the latest packages from their names.
 #[derive(Debug, Clone, Copy)]
 pub struct MultiQueryDatabaseLatest<Ref> {
-    base: Ref,
+    pub(crate) base: Ref,
+}
+
+/// Database view to lookup queriers of packages from their names, preferring repositories
+/// which come first in a specified order.
+#[derive(Debug, Clone, Copy)]
+pub struct MultiQueryDatabasePrioritized<'p, Ref> {
+    pub(crate) base: Ref,
+    pub(crate) order: &'p [RepositoryName<'p>],
 }
 
 /// Database to lookup eager queriers from their package names.
@@ -64,3 +80,11 @@ pub type EagerMultiQueryDatabaseLatest<'r, 'a> =
 /// Database to lookup memo queriers of the latest packages from their names.
 pub type MemoMultiQueryDatabaseLatest<'r, 'a> =
     MultiQueryDatabaseLatest<&'r mut MemoMultiQueryDatabase<'a>>;
+
+/// Database to lookup eager queriers of packages from their names in a specified order of repositories.
+pub type EagerMultiQueryDatabasePrioritized<'r, 'p, 'a> =
+    MultiQueryDatabasePrioritized<'p, &'r EagerMultiQueryDatabase<'a>>;
+
+/// Database to lookup memo queriers of packages from their names in a specified order of repositories.
+pub type MemoMultiQueryDatabasePrioritized<'r, 'p, 'a> =
+    MultiQueryDatabasePrioritized<'p, &'r mut MemoMultiQueryDatabase<'a>>;
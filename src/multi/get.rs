@@ -1,6 +1,6 @@
 use super::{
-    LatestQuerier, MultiQuerier, MultiQueryDatabase, MultiQueryDatabaseLatest, WithParsedVersion,
-    WithParsedVersionUtils,
+    LatestQuerier, MultiQuerier, MultiQueryDatabase, MultiQueryDatabaseLatest,
+    MultiQueryDatabasePrioritized, WithParsedVersion, WithParsedVersionUtils,
 };
 use crate::value::RepositoryName;
 use arch_pkg_text::value::Name;
@@ -51,3 +51,17 @@ impl<'a, Querier> MultiQueryDatabaseLatest<&mut MultiQueryDatabase<'a, Querier>>
         self.base.get_mut(name)?.latest_mut()
     }
 }
+
+impl<'r, 'a, Querier> MultiQueryDatabasePrioritized<'_, &'r MultiQueryDatabase<'a, Querier>> {
+    /// Get an immutable reference to the querier of a package from the most preferred repository by its name.
+    pub fn get(&self, name: Name) -> Option<LatestQuerier<'a, &'r Querier>> {
+        self.base.get(name)?.prioritized(self.order)
+    }
+}
+
+impl<'a, Querier> MultiQueryDatabasePrioritized<'_, &mut MultiQueryDatabase<'a, Querier>> {
+    /// Get a mutable reference to the querier of a package from the most preferred repository by its name.
+    pub fn get_mut(&mut self, name: Name) -> Option<LatestQuerier<'a, &mut Querier>> {
+        self.base.get_mut(name)?.prioritized_mut(self.order)
+    }
+}
@@ -1,7 +1,7 @@
 use crate::{
     multi::{
         LatestQuerier, MultiQuerier, MultiQueryDatabase, MultiQueryDatabaseLatest,
-        WithParsedVersion,
+        MultiQueryDatabasePrioritized, WithParsedVersion,
     },
     value::RepositoryName,
 };
@@ -105,6 +105,30 @@ impl<'a, Querier> MultiQueryDatabase<'a, Querier> {
             internal: self.internal.iter_mut(),
         }
     }
+
+    /// Get an iterator over all pairs of [package names](Name) and immutable queriers of
+    /// each package from the first repository in `order` that has it.
+    pub fn prioritized_entries<'p>(
+        &self,
+        order: &'p [RepositoryName<'p>],
+    ) -> PrioritizedEntries<'_, 'a, 'p, Querier> {
+        PrioritizedEntries {
+            internal: self.internal.iter(),
+            order,
+        }
+    }
+
+    /// Get an iterator over all pairs of [package names](Name) and mutable queriers of
+    /// each package from the first repository in `order` that has it.
+    pub fn prioritized_entries_mut<'p>(
+        &mut self,
+        order: &'p [RepositoryName<'p>],
+    ) -> PrioritizedEntriesMut<'_, 'a, 'p, Querier> {
+        PrioritizedEntriesMut {
+            internal: self.internal.iter_mut(),
+            order,
+        }
+    }
 }
 
 /// [Iterator] over all pairs of [repository names](RepositoryName) and immutable queriers in a [`MultiQuerier`].
@@ -262,3 +286,77 @@ impl<Ref> MultiQueryDatabaseLatest<Ref> {
         self.base.latest_entries_mut()
     }
 }
+
+/// [Iterator] over all pairs of [package names](Name) and immutable queriers in [`MultiQueryDatabasePrioritized`].
+#[derive(Debug, Clone)]
+pub struct PrioritizedEntries<'r, 'query, 'p, Querier> {
+    internal: hash_map::Iter<'r, &'query str, MultiQuerier<'query, Querier>>,
+    order: &'p [RepositoryName<'p>],
+}
+
+impl<'r, 'query, Querier> Iterator for PrioritizedEntries<'r, 'query, '_, Querier> {
+    type Item = (Name<'query>, LatestQuerier<'query, &'r Querier>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (name, querier) = self.internal.next()?;
+        let name = Name(name);
+        let querier = querier.prioritized(self.order)?;
+        Some((name, querier))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.internal.len().pipe(Some))
+    }
+
+    fn count(self) -> usize {
+        self.internal.count()
+    }
+}
+
+impl<Querier> FusedIterator for PrioritizedEntries<'_, '_, '_, Querier> {}
+
+/// [Iterator] over all pairs of [package names](Name) and mutable queriers in [`MultiQueryDatabasePrioritized`].
+#[derive(Debug)]
+pub struct PrioritizedEntriesMut<'r, 'query, 'p, Querier> {
+    internal: hash_map::IterMut<'r, &'query str, MultiQuerier<'query, Querier>>,
+    order: &'p [RepositoryName<'p>],
+}
+
+impl<'r, 'query, Querier> Iterator for PrioritizedEntriesMut<'r, 'query, '_, Querier> {
+    type Item = (Name<'query>, LatestQuerier<'query, &'r mut Querier>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (name, querier) = self.internal.next()?;
+        let name = Name(name);
+        let querier = querier.prioritized_mut(self.order)?;
+        Some((name, querier))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.internal.len().pipe(Some))
+    }
+
+    fn count(self) -> usize {
+        self.internal.count()
+    }
+}
+
+impl<Querier> FusedIterator for PrioritizedEntriesMut<'_, '_, '_, Querier> {}
+
+impl<'p, Ref> MultiQueryDatabasePrioritized<'p, Ref> {
+    /// Get an iterator over all pairs of [package names](Name) and immutable queriers.
+    pub fn entries<'query, Querier>(&self) -> PrioritizedEntries<'_, 'query, 'p, Querier>
+    where
+        Ref: Deref<Target = MultiQueryDatabase<'query, Querier>>,
+    {
+        self.base.prioritized_entries(self.order)
+    }
+
+    /// Get an iterator over all pairs of [package names](Name) and mutable queriers.
+    pub fn entries_mut<'query, Querier>(&mut self) -> PrioritizedEntriesMut<'_, 'query, 'p, Querier>
+    where
+        Ref: DerefMut<Target = MultiQueryDatabase<'query, Querier>>,
+    {
+        self.base.prioritized_entries_mut(self.order)
+    }
+}
@@ -1,4 +1,6 @@
-use crate::multi::{MultiQuerier, MultiQueryDatabase, MultiQueryDatabaseLatest};
+use crate::multi::{
+    MultiQuerier, MultiQueryDatabase, MultiQueryDatabaseLatest, MultiQueryDatabasePrioritized,
+};
 use arch_pkg_text::value::Name;
 use core::iter::FusedIterator;
 use core::ops::Deref;
@@ -52,3 +54,13 @@ impl<Ref> MultiQueryDatabaseLatest<Ref> {
         self.base.names()
     }
 }
+
+impl<Ref> MultiQueryDatabasePrioritized<'_, Ref> {
+    /// Get an iterator over all [package names](Name).
+    pub fn names<'a, Querier>(&self) -> Names<'_, 'a, Querier>
+    where
+        Ref: Deref<Target = MultiQueryDatabase<'a, Querier>>,
+    {
+        self.base.names()
+    }
+}
@@ -1,5 +1,9 @@
-use crate::multi::{
-    LatestQuerier, MultiQuerier, MultiQueryDatabase, MultiQueryDatabaseLatest, WithParsedVersion,
+use crate::{
+    multi::{
+        LatestQuerier, MultiQuerier, MultiQueryDatabase, MultiQueryDatabaseLatest,
+        MultiQueryDatabasePrioritized, WithParsedVersion,
+    },
+    value::RepositoryName,
 };
 use core::{
     iter::FusedIterator,
@@ -96,6 +100,30 @@ impl<'a, Querier> MultiQueryDatabase<'a, Querier> {
             internal: self.internal.values_mut(),
         }
     }
+
+    /// Get an iterator over all immutable queriers of each package from the first repository
+    /// in `order` that has it.
+    pub fn prioritized_queriers<'p>(
+        &self,
+        order: &'p [RepositoryName<'p>],
+    ) -> PrioritizedQueriers<'_, 'a, 'p, Querier> {
+        PrioritizedQueriers {
+            internal: self.internal.values(),
+            order,
+        }
+    }
+
+    /// Get an iterator over all mutable queriers of each package from the first repository
+    /// in `order` that has it.
+    pub fn prioritized_queriers_mut<'p>(
+        &mut self,
+        order: &'p [RepositoryName<'p>],
+    ) -> PrioritizedQueriersMut<'_, 'a, 'p, Querier> {
+        PrioritizedQueriersMut {
+            internal: self.internal.values_mut(),
+            order,
+        }
+    }
 }
 
 /// [Iterator] over all immutable queriers in a [`MultiQuerier`].
@@ -239,3 +267,73 @@ impl<Ref> MultiQueryDatabaseLatest<Ref> {
         self.base.latest_queriers_mut()
     }
 }
+
+/// [Iterator] over all immutable queriers in a [`MultiQueryDatabasePrioritized`].
+#[derive(Debug, Clone)]
+pub struct PrioritizedQueriers<'r, 'query, 'p, Querier> {
+    internal: Values<'r, &'query str, MultiQuerier<'query, Querier>>,
+    order: &'p [RepositoryName<'p>],
+}
+
+impl<'r, 'query, Querier> Iterator for PrioritizedQueriers<'r, 'query, '_, Querier> {
+    type Item = LatestQuerier<'query, &'r Querier>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.internal.next()?.prioritized(self.order)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.internal.len().pipe(Some))
+    }
+
+    fn count(self) -> usize {
+        self.internal.count()
+    }
+}
+
+impl<Querier> FusedIterator for PrioritizedQueriers<'_, '_, '_, Querier> {}
+
+/// [Iterator] over all mutable queriers in a [`MultiQueryDatabasePrioritized`].
+#[derive(Debug)]
+pub struct PrioritizedQueriersMut<'r, 'query, 'p, Querier> {
+    internal: ValuesMut<'r, &'query str, MultiQuerier<'query, Querier>>,
+    order: &'p [RepositoryName<'p>],
+}
+
+impl<'r, 'query, Querier> Iterator for PrioritizedQueriersMut<'r, 'query, '_, Querier> {
+    type Item = LatestQuerier<'query, &'r mut Querier>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.internal.next()?.prioritized_mut(self.order)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.internal.len().pipe(Some))
+    }
+
+    fn count(self) -> usize {
+        self.internal.count()
+    }
+}
+
+impl<Querier> FusedIterator for PrioritizedQueriersMut<'_, '_, '_, Querier> {}
+
+impl<'p, Ref> MultiQueryDatabasePrioritized<'p, Ref> {
+    /// Get an iterator over all immutable queriers.
+    pub fn queriers<'query, Querier>(&self) -> PrioritizedQueriers<'_, 'query, 'p, Querier>
+    where
+        Ref: Deref<Target = MultiQueryDatabase<'query, Querier>>,
+    {
+        self.base.prioritized_queriers(self.order)
+    }
+
+    /// Get an iterator over all mutable queriers.
+    pub fn queriers_mut<'query, Querier>(
+        &mut self,
+    ) -> PrioritizedQueriersMut<'_, 'query, 'p, Querier>
+    where
+        Ref: DerefMut<Target = MultiQueryDatabase<'query, Querier>>,
+    {
+        self.base.prioritized_queriers_mut(self.order)
+    }
+}
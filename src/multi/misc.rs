@@ -1,4 +1,6 @@
-use super::{MultiQuerier, MultiQueryDatabase};
+use super::{MultiQuerier, MultiQueryDatabase, WithParsedVersion};
+use crate::value::RepositoryName;
+use arch_pkg_text::value::Name;
 
 impl<Querier> MultiQuerier<'_, Querier> {
     /// The number of repositories that have a package of this name.
@@ -12,7 +14,7 @@ impl<Querier> MultiQuerier<'_, Querier> {
     }
 }
 
-impl<Querier> MultiQueryDatabase<'_, Querier> {
+impl<'a, Querier> MultiQueryDatabase<'a, Querier> {
     /// The number of package names within the database.
     pub fn len(&self) -> usize {
         self.internal.len()
@@ -22,4 +24,19 @@ impl<Querier> MultiQueryDatabase<'_, Querier> {
     pub fn is_empty(&self) -> bool {
         self.internal.is_empty()
     }
+
+    /// Only keep the queriers for which `keep` returns `true`.
+    ///
+    /// Package names which no longer have any querier are removed.
+    pub fn retain<Keep>(&mut self, mut keep: Keep)
+    where
+        Keep: FnMut(Name<'a>, RepositoryName<'a>, &mut WithParsedVersion<'a, Querier>) -> bool,
+    {
+        self.internal.retain(|name, multi_querier| {
+            multi_querier.internal.retain(|repository, querier| {
+                keep(Name(name), RepositoryName(repository), querier)
+            });
+            !multi_querier.is_empty()
+        })
+    }
 }
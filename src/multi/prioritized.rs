@@ -0,0 +1,88 @@
+use super::{
+    IntoWithRepositoryName, LatestQuerier, MultiQuerier, MultiQueryDatabase,
+    MultiQueryDatabasePrioritized, WithParsedVersionUtils,
+};
+use crate::value::RepositoryName;
+use core::cmp::Reverse;
+use pipe_trait::Pipe;
+
+impl<'a, Querier> MultiQuerier<'a, Querier> {
+    /// Find the name of the repository that should be preferred according to `order`.
+    ///
+    /// If none of the repositories in `order` has the package, fallback to the one whose
+    /// package's version is greatest, ties are broken by picking the first repository name in
+    /// alphabetical order.
+    fn prioritized_repository(&self, order: &[RepositoryName]) -> Option<&'a str> {
+        order
+            .iter()
+            .find_map(|repository| self.internal.get_key_value(repository.as_str()))
+            .or_else(|| {
+                self.internal.iter().max_by_key(|(repository, querier)| {
+                    (querier.parsed_version(), Reverse(*repository))
+                })
+            })
+            .map(|(repository, _)| *repository)
+    }
+
+    /// Get an immutable reference to a querier from the first repository in `order` that has the package.
+    ///
+    /// If none of the repositories in `order` has the package, the querier whose package's version is
+    /// greatest would be returned instead, ties are broken by repository name.
+    pub fn prioritized(&self, order: &[RepositoryName]) -> Option<LatestQuerier<'a, &Querier>> {
+        let repository = self.prioritized_repository(order)?;
+        self.internal
+            .get(repository)?
+            .to_ref()
+            .with_repository_name(RepositoryName(repository))
+            .pipe(Some)
+    }
+
+    /// Get a mutable reference to a querier from the first repository in `order` that has the package.
+    ///
+    /// If none of the repositories in `order` has the package, the querier whose package's version is
+    /// greatest would be returned instead, ties are broken by repository name.
+    pub fn prioritized_mut(
+        &mut self,
+        order: &[RepositoryName],
+    ) -> Option<LatestQuerier<'a, &mut Querier>> {
+        let repository = self.prioritized_repository(order)?;
+        self.internal
+            .get_mut(repository)?
+            .to_ref_mut()
+            .with_repository_name(RepositoryName(repository))
+            .pipe(Some)
+    }
+}
+
+impl<Querier> MultiQueryDatabase<'_, Querier> {
+    /// Combine the different repositories into a database view of immutable queriers
+    /// that lookup packages from repositories in the order of `order`.
+    ///
+    /// Packages which only exist in repositories outside `order` are still visible, their
+    /// latest versions would be chosen.
+    pub fn prioritized<'p>(
+        &self,
+        order: &'p [RepositoryName<'p>],
+    ) -> MultiQueryDatabasePrioritized<'p, &Self> {
+        MultiQueryDatabasePrioritized { base: self, order }
+    }
+
+    /// Combine the different repositories into a database view of mutable queriers
+    /// that lookup packages from repositories in the order of `order`.
+    ///
+    /// Packages which only exist in repositories outside `order` are still visible, their
+    /// latest versions would be chosen.
+    pub fn prioritized_mut<'p>(
+        &mut self,
+        order: &'p [RepositoryName<'p>],
+    ) -> MultiQueryDatabasePrioritized<'p, &mut Self> {
+        MultiQueryDatabasePrioritized { base: self, order }
+    }
+}
+
+impl<'p, Ref> MultiQueryDatabasePrioritized<'p, Ref> {
+    /// Get the order of repositories.
+    pub fn order(&self) -> &'p [RepositoryName<'p>] {
+        self.order
+    }
+}
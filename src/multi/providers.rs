@@ -1,13 +1,19 @@
-use super::{MultiQueryDatabase, WithParsedVersion};
+use super::{LatestQuerier, MultiQueryDatabase, MultiQueryDatabasePrioritized, WithParsedVersion};
 use crate::{
-    multi::{Entries, EntriesMut, MultiQuerier, MultiQueriers, MultiQueriersMut},
+    multi::{
+        Entries, EntriesMut, MultiQuerier, MultiQueriers, MultiQueriersMut, PrioritizedQueriers,
+        PrioritizedQueriersMut,
+    },
     value::RepositoryName,
 };
 use arch_pkg_text::{
     desc::{Query, QueryMut},
     value::DependencyName,
 };
-use core::iter::FusedIterator;
+use core::{
+    iter::FusedIterator,
+    ops::{Deref, DerefMut},
+};
 
 /// [Iterator] over all immutable queriers of packages which list a certain [`DependencyName`] in their
 /// [`provides`](Query::provides) array.
@@ -164,3 +170,100 @@ impl<'query, Querier> MultiQueryDatabase<'query, Querier> {
         AlternativeProvidersMut::new(target, self.queriers_mut())
     }
 }
+
+/// [Iterator] over immutable queriers of packages from their most preferred repositories which list a
+/// certain [`DependencyName`] in their [`provides`](Query::provides) array.
+///
+/// This iterator is created by calling [`MultiQueryDatabasePrioritized::alternative_providers`].
+#[derive(Debug, Clone)]
+pub struct PrioritizedProviders<'r, 'query, 'p, 'name, Querier> {
+    target: DependencyName<'name>,
+    queriers: PrioritizedQueriers<'r, 'query, 'p, Querier>,
+}
+
+impl<'r, 'query, Querier: Query<'r>> Iterator
+    for PrioritizedProviders<'r, 'query, '_, '_, Querier>
+{
+    type Item = LatestQuerier<'query, &'r Querier>;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.queriers.find(|querier| {
+            querier
+                .provides()
+                .into_iter()
+                .flatten()
+                .map(|provide| provide.components())
+                .any(|(name, _)| name == self.target)
+        })
+    }
+}
+
+impl<'r, Querier: Query<'r>> FusedIterator for PrioritizedProviders<'r, '_, '_, '_, Querier> {}
+
+/// [Iterator] over mutable queriers of packages from their most preferred repositories which list a
+/// certain [`DependencyName`] in their [`provides`](QueryMut::provides_mut) array.
+///
+/// This iterator is created by calling [`MultiQueryDatabasePrioritized::alternative_providers_mut`].
+#[derive(Debug)]
+pub struct PrioritizedProvidersMut<'r, 'query, 'p, 'name, Querier> {
+    target: DependencyName<'name>,
+    queriers: PrioritizedQueriersMut<'r, 'query, 'p, Querier>,
+}
+
+impl<'r, 'query, Querier: QueryMut<'r>> Iterator
+    for PrioritizedProvidersMut<'r, 'query, '_, '_, Querier>
+{
+    type Item = LatestQuerier<'query, &'r mut Querier>;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.queriers.find_map(|mut querier| {
+            let found = querier
+                .provides_mut()
+                .into_iter()
+                .flatten()
+                .map(|provide| provide.components())
+                .any(|(name, _)| name == self.target);
+            found.then_some(querier)
+        })
+    }
+}
+
+impl<'r, Querier: QueryMut<'r>> FusedIterator for PrioritizedProvidersMut<'r, '_, '_, '_, Querier> {}
+
+impl<'p, Ref> MultiQueryDatabasePrioritized<'p, Ref> {
+    /// Get an iterator over immutable queriers of packages which list a certain [`DependencyName`] in their
+    /// [`provides`](Query::provides) array.
+    ///
+    /// Each package is visited once, from the first repository in the order that has it. Whether the package
+    /// provides `target` is decided by that querier alone, versions of the same package in less preferred
+    /// repositories are not considered.
+    pub fn alternative_providers<'r, 'query, 'name, Querier>(
+        &'r self,
+        target: DependencyName<'name>,
+    ) -> PrioritizedProviders<'r, 'query, 'p, 'name, Querier>
+    where
+        Ref: Deref<Target = MultiQueryDatabase<'query, Querier>>,
+    {
+        PrioritizedProviders {
+            target,
+            queriers: self.queriers(),
+        }
+    }
+
+    /// Get an iterator over mutable queriers of packages which list a certain [`DependencyName`] in their
+    /// [`provides`](QueryMut::provides_mut) array.
+    ///
+    /// Each package is visited once, from the first repository in the order that has it. Whether the package
+    /// provides `target` is decided by that querier alone, versions of the same package in less preferred
+    /// repositories are not considered.
+    pub fn alternative_providers_mut<'r, 'query, 'name, Querier>(
+        &'r mut self,
+        target: DependencyName<'name>,
+    ) -> PrioritizedProvidersMut<'r, 'query, 'p, 'name, Querier>
+    where
+        Ref: DerefMut<Target = MultiQueryDatabase<'query, Querier>>,
+    {
+        PrioritizedProvidersMut {
+            target,
+            queriers: self.queriers_mut(),
+        }
+    }
+}
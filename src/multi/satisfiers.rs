@@ -0,0 +1,154 @@
+use super::{MultiQueryDatabase, WithParsedVersion};
+use crate::{
+    multi::{Entries, EntriesMut, MultiQuerier, MultiQueriers, MultiQueriersMut},
+    satisfy::package_satisfies,
+    value::RepositoryName,
+};
+use arch_pkg_text::{
+    desc::{Query, QueryMut},
+    value::Dependency,
+};
+use core::iter::FusedIterator;
+
+/// [Iterator] over all immutable queriers of packages which satisfy a certain [`Dependency`].
+///
+/// This iterator is created by calling [`MultiQueryDatabase::satisfiers`].
+#[derive(Debug, Clone)]
+pub struct Satisfiers<'r, 'query, 'dependency, Querier> {
+    target: Dependency<'dependency>,
+    current: Option<Entries<'r, 'query, Querier>>, // always filled if queriers is filled
+    queriers: MultiQueriers<'r, 'query, Querier>,
+}
+
+impl<'r, 'query, 'dependency, Querier> Satisfiers<'r, 'query, 'dependency, Querier> {
+    /// Create the struct in such a way to ensure invariant.
+    fn new(target: Dependency<'dependency>, queriers: MultiQueriers<'r, 'query, Querier>) -> Self {
+        let mut result = Satisfiers {
+            target,
+            current: None,
+            queriers,
+        };
+        result.change_querier();
+        result
+    }
+
+    /// Extract an element from [`Self::queriers`] into [`Self::current`].
+    fn change_querier(&mut self) {
+        self.current = self.queriers.next().map(MultiQuerier::entries);
+    }
+}
+
+impl<'r, 'query, Querier: Query<'r>> Iterator for Satisfiers<'r, 'query, '_, Querier> {
+    type Item = (
+        RepositoryName<'query>,
+        &'r WithParsedVersion<'query, Querier>,
+    );
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if cfg!(debug_assertions) && self.current.is_none() && self.queriers.next().is_some() {
+                panic!("Invariant violated! `current` was emptied before `queriers`");
+            }
+
+            for (repository, querier) in self.current.as_mut()? {
+                let found = package_satisfies(
+                    self.target,
+                    querier.name(),
+                    querier.version(),
+                    querier.provides(),
+                );
+                if found {
+                    return Some((repository, querier));
+                }
+            }
+
+            self.change_querier();
+        }
+    }
+}
+
+impl<'r, Querier: Query<'r>> FusedIterator for Satisfiers<'r, '_, '_, Querier> {}
+
+/// [Iterator] over all mutable queriers of packages which satisfy a certain [`Dependency`].
+///
+/// This iterator is created by calling [`MultiQueryDatabase::satisfiers_mut`].
+#[derive(Debug)]
+pub struct SatisfiersMut<'r, 'query, 'dependency, Querier> {
+    target: Dependency<'dependency>,
+    current: Option<EntriesMut<'r, 'query, Querier>>, // always filled if queriers is filled
+    queriers: MultiQueriersMut<'r, 'query, Querier>,
+}
+
+impl<'r, 'query, 'dependency, Querier> SatisfiersMut<'r, 'query, 'dependency, Querier> {
+    /// Create the struct in such a way to ensure invariant.
+    fn new(
+        target: Dependency<'dependency>,
+        queriers: MultiQueriersMut<'r, 'query, Querier>,
+    ) -> Self {
+        let mut result = SatisfiersMut {
+            target,
+            current: None,
+            queriers,
+        };
+        result.change_querier();
+        result
+    }
+
+    /// Extract an element from [`Self::queriers`] into [`Self::current`].
+    fn change_querier(&mut self) {
+        self.current = self.queriers.next().map(MultiQuerier::entries_mut);
+    }
+}
+
+impl<'r, 'query, Querier: QueryMut<'r>> Iterator for SatisfiersMut<'r, 'query, '_, Querier> {
+    type Item = (
+        RepositoryName<'query>,
+        &'r mut WithParsedVersion<'query, Querier>,
+    );
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if cfg!(debug_assertions) && self.current.is_none() && self.queriers.next().is_some() {
+                panic!("Invariant violated! `current` was emptied before `queriers`");
+            }
+
+            for (repository, querier) in self.current.as_mut()? {
+                let found = package_satisfies(
+                    self.target,
+                    querier.name_mut(),
+                    querier.version_mut(),
+                    querier.provides_mut(),
+                );
+                if found {
+                    return Some((repository, querier));
+                }
+            }
+
+            self.change_querier();
+        }
+    }
+}
+
+impl<'r, Querier: QueryMut<'r>> FusedIterator for SatisfiersMut<'r, '_, '_, Querier> {}
+
+impl<'query, Querier> MultiQueryDatabase<'query, Querier> {
+    /// Get an iterator over all immutable queriers of packages which satisfy a certain [`Dependency`],
+    /// either by their own names and versions or by their [`provides`](Query::provides) arrays.
+    ///
+    /// See [`crate::satisfy`] for the rules.
+    pub fn satisfiers<'r, 'dependency>(
+        &'r self,
+        target: Dependency<'dependency>,
+    ) -> Satisfiers<'r, 'query, 'dependency, Querier> {
+        Satisfiers::new(target, self.queriers())
+    }
+
+    /// Get an iterator over all mutable queriers of packages which satisfy a certain [`Dependency`],
+    /// either by their own names and versions or by their [`provides`](QueryMut::provides_mut) arrays.
+    ///
+    /// See [`crate::satisfy`] for the rules.
+    pub fn satisfiers_mut<'r, 'dependency>(
+        &'r mut self,
+        target: Dependency<'dependency>,
+    ) -> SatisfiersMut<'r, 'query, 'dependency, Querier> {
+        SatisfiersMut::new(target, self.queriers_mut())
+    }
+}
@@ -1,4 +1,5 @@
 mod into;
+mod query;
 mod utils;
 
 pub use into::{IntoWithParsedVersion, IntoWithRepositoryName};
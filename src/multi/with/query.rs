@@ -0,0 +1,26 @@
+use super::{WithParsedVersion, WithRepositoryName};
+use arch_pkg_text::desc::{ParsedField, Query, QueryMut};
+
+impl<'a, Querier: Query<'a>> Query<'a> for WithParsedVersion<'_, Querier> {
+    fn query_raw_text(&self, field: ParsedField) -> Option<&'a str> {
+        self.querier.query_raw_text(field)
+    }
+}
+
+impl<'a, Querier: QueryMut<'a>> QueryMut<'a> for WithParsedVersion<'_, Querier> {
+    fn query_raw_text_mut(&mut self, field: ParsedField) -> Option<&'a str> {
+        self.querier.query_raw_text_mut(field)
+    }
+}
+
+impl<'a, Querier: Query<'a>> Query<'a> for WithRepositoryName<'_, Querier> {
+    fn query_raw_text(&self, field: ParsedField) -> Option<&'a str> {
+        self.querier.query_raw_text(field)
+    }
+}
+
+impl<'a, Querier: QueryMut<'a>> QueryMut<'a> for WithRepositoryName<'_, Querier> {
+    fn query_raw_text_mut(&mut self, field: ParsedField) -> Option<&'a str> {
+        self.querier.query_raw_text_mut(field)
+    }
+}
@@ -0,0 +1,197 @@
+//! Detect orphans, i.e. packages installed as dependencies which are no longer needed.
+
+use crate::{QueryDatabase, TextCollection, reverse::ReverseDependencyIndex, value::InstallReason};
+use arch_pkg_text::{desc::Query, value::Name};
+use itertools::Itertools;
+use std::collections::{HashMap, HashSet};
+
+/// Map package names to their [reasons of installation](InstallReason).
+///
+/// The parsed queriers discard `%REASON%`, so the reasons are read from the `desc` texts instead.
+#[derive(Debug, Default, Clone)]
+pub struct InstallReasons<'a> {
+    internal: HashMap<&'a str, InstallReason>,
+}
+
+impl<'a> InstallReasons<'a> {
+    /// Read the reasons from the `desc` texts of a local pacman database.
+    ///
+    /// Texts without a `%NAME%` section are ignored.
+    pub fn from_texts(texts: &'a TextCollection) -> Self {
+        let internal = texts
+            .iter()
+            .filter_map(|text| {
+                let name = desc_name(text.as_str())?;
+                Some((name, InstallReason::from_desc_text(text.as_str())))
+            })
+            .collect();
+        InstallReasons { internal }
+    }
+
+    /// Get the reason of installation of a package.
+    pub fn get(&self, name: Name) -> Option<InstallReason> {
+        self.internal.get(name.as_str()).copied()
+    }
+
+    /// Set the reason of installation of a package.
+    pub fn insert(&mut self, name: Name<'a>, reason: InstallReason) -> Option<InstallReason> {
+        self.internal.insert(name.as_str(), reason)
+    }
+
+    /// Get the number of packages.
+    pub fn len(&self) -> usize {
+        self.internal.len()
+    }
+
+    /// Check if there are no packages.
+    pub fn is_empty(&self) -> bool {
+        self.internal.is_empty()
+    }
+}
+
+/// Find the package name in a `desc` text.
+fn desc_name(text: &str) -> Option<&str> {
+    let mut lines = text.lines().map(str::trim);
+    lines.find(|line| *line == "%NAME%")?;
+    lines.next().filter(|name| !name.is_empty())
+}
+
+/// Audit of the packages in a local database to find those that could be removed.
+///
+/// This audit is created by calling [`QueryDatabase::orphan_audit`].
+#[derive(Debug)]
+pub struct OrphanAudit<'a, Querier> {
+    db: &'a QueryDatabase<'a, Querier>,
+    reasons: InstallReasons<'a>,
+    index: ReverseDependencyIndex<'a, Querier>,
+}
+
+impl<'a, Querier: Query<'a>> OrphanAudit<'a, Querier> {
+    /// Check whether a package was installed as a dependency.
+    ///
+    /// Packages without known reasons are assumed to be explicitly installed.
+    fn is_dependency(&self, name: Name) -> bool {
+        self.reasons.get(name) == Some(InstallReason::Dependency)
+    }
+
+    /// Get the installed packages which need `name`.
+    fn dependents(&self, name: Name, honor_optional: bool) -> Vec<Name<'a>> {
+        let mut dependents = self.index.required_by(name);
+        if honor_optional {
+            dependents.extend(self.index.optional_for(name));
+        }
+        dependents
+    }
+
+    /// List the orphans, equivalent to `pacman -Qdtt` or `pacman -Qdt`.
+    fn orphans_with(&self, honor_optional: bool) -> Vec<Name<'a>> {
+        self.db
+            .names()
+            .filter(|name| self.is_dependency(*name))
+            .filter(|name| self.dependents(*name, honor_optional).is_empty())
+            .sorted()
+            .collect()
+    }
+
+    /// List the groups of packages which could be removed together.
+    fn orphaned_subgraphs_with(&self, honor_optional: bool) -> Vec<Vec<Name<'a>>> {
+        let dependents: HashMap<Name<'a>, Vec<Name<'a>>> = self
+            .db
+            .names()
+            .filter(|name| self.is_dependency(*name))
+            .map(|name| (name, self.dependents(name, honor_optional)))
+            .collect();
+
+        // shrink the set until every remaining package is only needed by other remaining packages
+        let mut removable: HashSet<Name<'a>> = dependents.keys().copied().collect();
+        loop {
+            let needed: Vec<_> = removable
+                .iter()
+                .copied()
+                .filter(|name| {
+                    dependents[name]
+                        .iter()
+                        .any(|dependent| !removable.contains(dependent))
+                })
+                .collect();
+            if needed.is_empty() {
+                break;
+            }
+            for name in needed {
+                removable.remove(&name);
+            }
+        }
+
+        // split the set into connected components
+        let mut neighbors: HashMap<Name<'a>, Vec<Name<'a>>> = HashMap::new();
+        for name in &removable {
+            for dependent in &dependents[name] {
+                if dependent == name {
+                    continue;
+                }
+                neighbors.entry(*name).or_default().push(*dependent);
+                neighbors.entry(*dependent).or_default().push(*name);
+            }
+        }
+        let mut visited = HashSet::new();
+        let mut subgraphs = Vec::new();
+        for start in removable.iter().copied().sorted() {
+            if !visited.insert(start) {
+                continue;
+            }
+            let mut subgraph = vec![start];
+            let mut stack = vec![start];
+            while let Some(name) = stack.pop() {
+                for neighbor in neighbors.get(&name).into_iter().flatten() {
+                    if visited.insert(*neighbor) {
+                        subgraph.push(*neighbor);
+                        stack.push(*neighbor);
+                    }
+                }
+            }
+            subgraph.sort();
+            subgraphs.push(subgraph);
+        }
+        subgraphs
+    }
+
+    /// List the packages which were installed as dependencies but are no longer required by any
+    /// installed package, equivalent to `pacman -Qdtt`.
+    ///
+    /// Optional dependencies are not taken into account.
+    pub fn orphans(&self) -> Vec<Name<'a>> {
+        self.orphans_with(false)
+    }
+
+    /// List the packages which were installed as dependencies but are neither required nor optionally
+    /// required by any installed package, equivalent to `pacman -Qdt`.
+    pub fn orphans_honoring_optional(&self) -> Vec<Name<'a>> {
+        self.orphans_with(true)
+    }
+
+    /// List the groups of packages installed as dependencies which are only required by each other,
+    /// such that each group could be removed together.
+    ///
+    /// Unlike [`Self::orphans`], packages which depend on each other in a cycle are detected.
+    /// Optional dependencies are not taken into account.
+    pub fn orphaned_subgraphs(&self) -> Vec<Vec<Name<'a>>> {
+        self.orphaned_subgraphs_with(false)
+    }
+
+    /// Like [`Self::orphaned_subgraphs`] but packages which are optionally required by packages outside
+    /// the group are kept.
+    pub fn orphaned_subgraphs_honoring_optional(&self) -> Vec<Vec<Name<'a>>> {
+        self.orphaned_subgraphs_with(true)
+    }
+}
+
+impl<'a, Querier> QueryDatabase<'a, Querier> {
+    /// Create an audit to find orphans with the [reasons of installation](InstallReason) of the packages.
+    pub fn orphan_audit(&'a self, reasons: InstallReasons<'a>) -> OrphanAudit<'a, Querier> {
+        OrphanAudit {
+            db: self,
+            reasons,
+            index: self.reverse_dependencies(),
+        }
+    }
+}
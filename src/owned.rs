@@ -0,0 +1,201 @@
+//! Owned package data which outlives the texts it was parsed from.
+
+use crate::{raw::RawFields, write::DESC_FIELDS};
+use arch_pkg_text::desc::{FieldName, Query};
+use core::{fmt, num::ParseIntError};
+use derive_more::{Display, Error};
+use std::borrow::Cow;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Error when a numeric field of a querier couldn't be parsed.
+#[derive(Debug, Display, Clone, PartialEq, Eq, Error)]
+#[display("Failed to parse %{field}% as a number: {error}")]
+pub struct PackageDescError {
+    #[error(not(source))]
+    field: FieldName,
+    #[error(source)]
+    error: ParseIntError,
+}
+
+impl PackageDescError {
+    /// The field which couldn't be parsed.
+    pub fn field(&self) -> FieldName {
+        self.field
+    }
+
+    /// The source of this error.
+    pub fn source(&self) -> &ParseIntError {
+        &self.error
+    }
+}
+
+/// Owned description of a package with a typed field for every field of a `desc` file.
+///
+/// Unlike queriers, it doesn't borrow from a [`Text`](crate::Text). Its [`Display`](fmt::Display)
+/// implementation renders the canonical `desc` text, with fields in the order of [`DESC_FIELDS`].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct PackageDesc {
+    /// Value of `%FILENAME%`.
+    pub file_name: Option<String>,
+    /// Value of `%NAME%`.
+    pub name: Option<String>,
+    /// Value of `%BASE%`.
+    pub base: Option<String>,
+    /// Value of `%VERSION%`.
+    pub version: Option<String>,
+    /// Value of `%DESC%`.
+    pub description: Option<String>,
+    /// Entries of `%GROUPS%`.
+    pub groups: Vec<String>,
+    /// Value of `%CSIZE%`, `None` if absent or empty.
+    pub compressed_size: Option<u64>,
+    /// Value of `%ISIZE%`, `None` if absent or empty.
+    pub installed_size: Option<u64>,
+    /// Value of `%MD5SUM%`.
+    pub md5_checksum: Option<String>,
+    /// Value of `%SHA256SUM%`.
+    pub sha256_checksum: Option<String>,
+    /// Value of `%PGPSIG%`.
+    pub pgp_signature: Option<String>,
+    /// Value of `%URL%`.
+    pub url: Option<String>,
+    /// Entries of `%LICENSE%`.
+    pub license: Vec<String>,
+    /// Entries of `%ARCH%`.
+    pub architecture: Vec<String>,
+    /// Value of `%BUILDDATE%`, `None` if absent or empty.
+    pub build_date: Option<u64>,
+    /// Value of `%PACKAGER%`.
+    pub packager: Option<String>,
+    /// Entries of `%DEPENDS%`.
+    pub dependencies: Vec<String>,
+    /// Entries of `%MAKEDEPENDS%`.
+    pub make_dependencies: Vec<String>,
+    /// Entries of `%CHECKDEPENDS%`.
+    pub check_dependencies: Vec<String>,
+    /// Entries of `%OPTDEPENDS%`.
+    pub opt_dependencies: Vec<String>,
+    /// Entries of `%PROVIDES%`.
+    pub provides: Vec<String>,
+    /// Entries of `%CONFLICTS%`.
+    pub conflicts: Vec<String>,
+    /// Entries of `%REPLACES%`.
+    pub replaces: Vec<String>,
+}
+
+impl PackageDesc {
+    /// Copy all fields of a querier.
+    pub fn from_query<'a>(querier: &impl Query<'a>) -> Result<Self, PackageDescError> {
+        let raw_fields = RawFields::new(querier);
+        let text = |field| raw_fields.get(field);
+        let single = |field| text(field).map(String::from);
+        let list = |field| -> Vec<String> {
+            text(field)
+                .into_iter()
+                .flat_map(str::lines)
+                .map(str::trim)
+                .filter(|line| !line.is_empty())
+                .map(String::from)
+                .collect()
+        };
+        let number = |field| -> Result<Option<u64>, PackageDescError> {
+            text(field)
+                .filter(|value| !value.is_empty())
+                .map(str::parse)
+                .transpose()
+                .map_err(|error| PackageDescError { field, error })
+        };
+
+        Ok(PackageDesc {
+            file_name: single(FieldName::FileName),
+            name: single(FieldName::Name),
+            base: single(FieldName::Base),
+            version: single(FieldName::Version),
+            description: single(FieldName::Description),
+            groups: list(FieldName::Groups),
+            compressed_size: number(FieldName::CompressedSize)?,
+            installed_size: number(FieldName::InstalledSize)?,
+            md5_checksum: single(FieldName::Md5Checksum),
+            sha256_checksum: single(FieldName::Sha256Checksum),
+            pgp_signature: single(FieldName::PgpSignature),
+            url: single(FieldName::Url),
+            license: list(FieldName::License),
+            architecture: list(FieldName::Architecture),
+            build_date: number(FieldName::BuildDate)?,
+            packager: single(FieldName::Packager),
+            dependencies: list(FieldName::Dependencies),
+            make_dependencies: list(FieldName::MakeDependencies),
+            check_dependencies: list(FieldName::CheckDependencies),
+            opt_dependencies: list(FieldName::OptionalDependencies),
+            provides: list(FieldName::Provides),
+            conflicts: list(FieldName::Conflicts),
+            replaces: list(FieldName::Replaces),
+        })
+    }
+
+    /// Get the lines of a field as they would appear in a `desc` file.
+    fn lines(&self, field: FieldName) -> Vec<Cow<'_, str>> {
+        fn single(value: &Option<String>) -> Vec<Cow<'_, str>> {
+            value.as_deref().map(Cow::Borrowed).into_iter().collect()
+        }
+        fn list(values: &[String]) -> Vec<Cow<'_, str>> {
+            values
+                .iter()
+                .map(String::as_str)
+                .map(Cow::Borrowed)
+                .collect()
+        }
+        fn number(value: Option<u64>) -> Vec<Cow<'static, str>> {
+            value
+                .map(|value| Cow::Owned(value.to_string()))
+                .into_iter()
+                .collect()
+        }
+        match field {
+            FieldName::FileName => single(&self.file_name),
+            FieldName::Name => single(&self.name),
+            FieldName::Base => single(&self.base),
+            FieldName::Version => single(&self.version),
+            FieldName::Description => single(&self.description),
+            FieldName::Groups => list(&self.groups),
+            FieldName::CompressedSize => number(self.compressed_size),
+            FieldName::InstalledSize => number(self.installed_size),
+            FieldName::Md5Checksum => single(&self.md5_checksum),
+            FieldName::Sha256Checksum => single(&self.sha256_checksum),
+            FieldName::PgpSignature => single(&self.pgp_signature),
+            FieldName::Url => single(&self.url),
+            FieldName::License => list(&self.license),
+            FieldName::Architecture => list(&self.architecture),
+            FieldName::BuildDate => number(self.build_date),
+            FieldName::Packager => single(&self.packager),
+            FieldName::Dependencies => list(&self.dependencies),
+            FieldName::MakeDependencies => list(&self.make_dependencies),
+            FieldName::CheckDependencies => list(&self.check_dependencies),
+            FieldName::OptionalDependencies => list(&self.opt_dependencies),
+            FieldName::Provides => list(&self.provides),
+            FieldName::Conflicts => list(&self.conflicts),
+            FieldName::Replaces => list(&self.replaces),
+        }
+    }
+}
+
+impl fmt::Display for PackageDesc {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for field in DESC_FIELDS {
+            let lines = self.lines(field);
+            if lines.iter().all(|line| line.trim().is_empty()) {
+                continue;
+            }
+            writeln!(f, "%{field}%")?;
+            for line in lines {
+                writeln!(f, "{line}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,284 @@
+//! Metadata of package files (`.pkg.tar.*`) read from their `.PKGINFO` entries.
+
+use crate::{
+    PackageDesc, Text, TextCollection,
+    text::archive::{LoadArchiveError, decompress, untag},
+};
+use core::num::ParseIntError;
+use derive_more::{Display, Error};
+use pipe_trait::Pipe;
+use rayon::prelude::*;
+use std::{
+    fs::{File, read_dir},
+    io::{self, BufReader, Read},
+    path::{Path, PathBuf},
+};
+
+/// Error when a `.PKGINFO` text couldn't be parsed.
+#[derive(Debug, Display, Clone, PartialEq, Eq, Error)]
+pub enum ParsePkgInfoError {
+    #[display("Line {line_number} is not a `key = value` pair: {line:?}")]
+    InvalidLine { line_number: usize, line: String },
+    #[display("Failed to parse {key} as a number: {error}")]
+    InvalidNumber {
+        #[error(not(source))]
+        key: &'static str,
+        #[error(source)]
+        error: ParseIntError,
+    },
+}
+
+/// Error when trying to load the metadata of a package archive.
+#[derive(Debug, Display, Error)]
+pub enum LoadPackageError {
+    #[display("Failed to open the package file: {_0}")]
+    Open(io::Error),
+    Archive(LoadArchiveError),
+    #[display("Failed to read the package archive: {_0}")]
+    Tar(io::Error),
+    #[display("The package archive has no .PKGINFO")]
+    MissingPkgInfo,
+    Parse(ParsePkgInfoError),
+}
+
+/// Error when trying to load the metadata of a package file.
+#[derive(Debug, Display, Error)]
+#[display("Failed to load {path:?}: {error}")]
+pub struct LoadPackageFileError {
+    #[error(source)]
+    error: LoadPackageError,
+    path: PathBuf,
+}
+
+impl LoadPackageFileError {
+    /// The source of this error.
+    pub fn source(&self) -> &LoadPackageError {
+        &self.error
+    }
+
+    /// Path to the package file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+/// Error when trying to load the metadata of a directory of package files.
+#[derive(Debug, Display, Error)]
+pub enum LoadPackageDirError {
+    #[display("Failed to read {path:?} as a directory: {error}")]
+    ReadDir {
+        #[error(source)]
+        error: io::Error,
+        #[error(not(source))]
+        path: PathBuf,
+    },
+    Package(LoadPackageFileError),
+}
+
+/// Whether a file name looks like a package file, e.g. `bash-5.2.026-2-x86_64.pkg.tar.zst`.
+///
+/// Detached signatures (`.sig`) are not package files.
+pub fn is_package_file_name(file_name: &str) -> bool {
+    file_name.contains(".pkg.tar") && !file_name.ends_with(".sig")
+}
+
+/// Convert an error of reading the tar archive inside a package archive.
+///
+/// Errors of the decoder underneath are converted into [`LoadPackageError::Archive`].
+fn tar_error(error: io::Error) -> LoadPackageError {
+    match untag(error) {
+        Ok((format, error)) => LoadPackageError::Archive(LoadArchiveError::decoder(format, error)),
+        Err(error) => LoadPackageError::Tar(error),
+    }
+}
+
+/// Extract the `.PKGINFO` text of a package archive.
+///
+/// The compression is detected from the leading bytes of the archive.
+pub fn read_pkginfo<Bytes: Read>(bytes: Bytes) -> Result<String, LoadPackageError> {
+    let tar = decompress(bytes).map_err(LoadPackageError::Archive)?;
+    let mut tar = tar::Archive::new(tar);
+    let entries = tar.entries().map_err(tar_error)?;
+
+    for entry in entries {
+        let mut entry = entry.map_err(tar_error)?;
+        let path = entry.path().map_err(tar_error)?;
+        if path != Path::new(".PKGINFO") {
+            continue;
+        }
+        let mut text = String::new();
+        entry.read_to_string(&mut text).map_err(tar_error)?;
+        return Ok(text);
+    }
+
+    Err(LoadPackageError::MissingPkgInfo)
+}
+
+impl PackageDesc {
+    /// Translate the `key = value` lines of a `.PKGINFO` text into a description.
+    ///
+    /// Keys without a `desc` counterpart (such as `backup` and `xdata`) are ignored.
+    pub fn from_pkginfo(text: &str) -> Result<Self, ParsePkgInfoError> {
+        let mut desc = PackageDesc::default();
+
+        for (index, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let Some((key, value)) = line.split_once('=') else {
+                return Err(ParsePkgInfoError::InvalidLine {
+                    line_number: index + 1,
+                    line: line.to_string(),
+                });
+            };
+            let (key, value) = (key.trim(), value.trim());
+            let number = |key| {
+                value
+                    .parse()
+                    .map_err(|error| ParsePkgInfoError::InvalidNumber { key, error })
+            };
+            let value = value.to_string();
+            match key {
+                "pkgname" => desc.name = Some(value),
+                "pkgbase" => desc.base = Some(value),
+                "pkgver" => desc.version = Some(value),
+                "pkgdesc" => desc.description = Some(value),
+                "url" => desc.url = Some(value),
+                "builddate" => desc.build_date = Some(number("builddate")?),
+                "packager" => desc.packager = Some(value),
+                "size" => desc.installed_size = Some(number("size")?),
+                "arch" => desc.architecture.push(value),
+                "license" => desc.license.push(value),
+                "group" => desc.groups.push(value),
+                "depend" => desc.dependencies.push(value),
+                "optdepend" => desc.opt_dependencies.push(value),
+                "makedepend" => desc.make_dependencies.push(value),
+                "checkdepend" => desc.check_dependencies.push(value),
+                "provides" => desc.provides.push(value),
+                "conflict" => desc.conflicts.push(value),
+                "replaces" => desc.replaces.push(value),
+                _ => continue,
+            }
+        }
+
+        Ok(desc)
+    }
+
+    /// Read the `.PKGINFO` of a package archive and translate it into a description.
+    pub fn from_package_reader<Bytes: Read>(bytes: Bytes) -> Result<Self, LoadPackageError> {
+        bytes
+            .pipe(read_pkginfo)?
+            .as_str()
+            .pipe(PackageDesc::from_pkginfo)
+            .map_err(LoadPackageError::Parse)
+    }
+
+    /// Read the `.PKGINFO` of a package file and translate it into a description.
+    ///
+    /// Unlike [`PackageDesc::from_package_reader`], `%FILENAME%` and `%CSIZE%` are also filled
+    /// from the file itself.
+    pub fn from_package_file(path: &Path) -> Result<Self, LoadPackageFileError> {
+        let error = |error| LoadPackageFileError {
+            error,
+            path: path.to_path_buf(),
+        };
+        let file = File::open(path)
+            .map_err(LoadPackageError::Open)
+            .map_err(error)?;
+        let size = file
+            .metadata()
+            .map_err(LoadPackageError::Open)
+            .map_err(error)?
+            .len();
+        let mut desc = file
+            .pipe(BufReader::new)
+            .pipe(PackageDesc::from_package_reader)
+            .map_err(error)?;
+        desc.file_name = path
+            .file_name()
+            .map(|file_name| file_name.to_string_lossy().into_owned());
+        desc.compressed_size = Some(size);
+        Ok(desc)
+    }
+}
+
+/// List the package files within a directory.
+fn package_files(dir: &Path) -> Result<Vec<PathBuf>, LoadPackageDirError> {
+    let entries = read_dir(dir).map_err(|error| LoadPackageDirError::ReadDir {
+        error,
+        path: dir.to_path_buf(),
+    })?;
+    let mut paths: Vec<_> = entries
+        .flatten()
+        .filter(|entry| {
+            entry
+                .file_type()
+                .ok()
+                .map(|file_type| file_type.is_file())
+                .unwrap_or(false)
+        })
+        .filter(|entry| {
+            entry
+                .file_name()
+                .to_str()
+                .map(is_package_file_name)
+                .unwrap_or(false)
+        })
+        .map(|entry| entry.path())
+        .collect();
+    paths.sort();
+    Ok(paths)
+}
+
+/// Load a package file and render its description as a `desc` text.
+fn load_package_text(path: &Path) -> Result<Text, LoadPackageDirError> {
+    PackageDesc::from_package_file(path)
+        .map(|desc| desc.to_string().into())
+        .map_err(LoadPackageDirError::Package)
+}
+
+impl TextCollection {
+    /// Load the `.PKGINFO` of every package file within a directory as `desc` texts.
+    ///
+    /// Files whose names don't contain `.pkg.tar` as well as detached signatures are skipped.
+    pub fn extend_from_package_dir(&mut self, dir: &Path) -> Result<(), LoadPackageDirError> {
+        for path in package_files(dir)? {
+            let text = load_package_text(&path)?;
+            self.insert(text);
+        }
+        Ok(())
+    }
+
+    /// Load the `.PKGINFO` of every package file within a directory as `desc` texts.
+    pub fn add_package_dir(mut self, dir: &Path) -> Result<Self, LoadPackageDirError> {
+        self.extend_from_package_dir(dir)?;
+        Ok(self)
+    }
+
+    /// Load the `.PKGINFO` of every package file within a directory as `desc` texts.
+    pub fn from_package_dir(dir: &Path) -> Result<Self, LoadPackageDirError> {
+        TextCollection::new().add_package_dir(dir)
+    }
+
+    /// Load the `.PKGINFO` of every package file within a directory as `desc` texts in parallel.
+    pub fn par_extend_from_package_dir(&mut self, dir: &Path) -> Result<(), LoadPackageDirError> {
+        let texts = package_files(dir)?
+            .par_iter()
+            .map(|path| load_package_text(path))
+            .collect::<Result<Vec<_>, _>>()?;
+        self.extend(texts);
+        Ok(())
+    }
+
+    /// Load the `.PKGINFO` of every package file within a directory as `desc` texts in parallel.
+    pub fn par_add_package_dir(mut self, dir: &Path) -> Result<Self, LoadPackageDirError> {
+        self.par_extend_from_package_dir(dir)?;
+        Ok(self)
+    }
+
+    /// Load the `.PKGINFO` of every package file within a directory as `desc` texts in parallel.
+    pub fn par_from_package_dir(dir: &Path) -> Result<Self, LoadPackageDirError> {
+        TextCollection::new().par_add_package_dir(dir)
+    }
+}
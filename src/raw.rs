@@ -0,0 +1,84 @@
+//! Raw values of the fields of `desc` files.
+
+use arch_pkg_text::desc::{FieldName, ParsedField, Query};
+
+/// All fields of a `desc` file in the order `repo-add` writes them.
+pub const DESC_FIELDS: [FieldName; 23] = [
+    FieldName::FileName,
+    FieldName::Name,
+    FieldName::Base,
+    FieldName::Version,
+    FieldName::Description,
+    FieldName::Groups,
+    FieldName::CompressedSize,
+    FieldName::InstalledSize,
+    FieldName::Md5Checksum,
+    FieldName::Sha256Checksum,
+    FieldName::PgpSignature,
+    FieldName::Url,
+    FieldName::License,
+    FieldName::Architecture,
+    FieldName::BuildDate,
+    FieldName::Packager,
+    FieldName::Replaces,
+    FieldName::Conflicts,
+    FieldName::Provides,
+    FieldName::Dependencies,
+    FieldName::OptionalDependencies,
+    FieldName::MakeDependencies,
+    FieldName::CheckDependencies,
+];
+
+/// Trimmed raw values of all fields of a querier, in the order of [`DESC_FIELDS`].
+///
+/// The parser of `arch-pkg-text` doesn't recognize field names with digits such as `%MD5SUM%`
+/// and `%SHA256SUM%`, so their sections end up inside the value of the preceding field.
+/// Such sections are split off and assigned to their own fields.
+pub(crate) struct RawFields<'a> {
+    internal: [Option<&'a str>; DESC_FIELDS.len()],
+}
+
+impl<'a> RawFields<'a> {
+    /// Collect the raw values of all fields of a querier.
+    pub(crate) fn new(querier: &impl Query<'a>) -> Self {
+        let index = |field| DESC_FIELDS.iter().position(|item| *item == field);
+        let mut fields = [None; DESC_FIELDS.len()];
+
+        for (field_index, field) in DESC_FIELDS.into_iter().enumerate() {
+            let Some(raw) = querier.query_raw_text(ParsedField::new(field)) else {
+                continue;
+            };
+            let mut current = (field_index, 0);
+            let mut offset = 0;
+            for line in raw.split_inclusive('\n') {
+                let embedded = line
+                    .trim()
+                    .strip_prefix('%')
+                    .and_then(|name| name.strip_suffix('%'))
+                    .and_then(|name| name.parse::<FieldName>().ok())
+                    .and_then(index);
+                if let Some(embedded) = embedded {
+                    let (index, start) = current;
+                    fields[index].get_or_insert(raw[start..offset].trim());
+                    current = (embedded, offset + line.len());
+                }
+                offset += line.len();
+            }
+            let (index, start) = current;
+            fields[index].get_or_insert(raw[start..].trim());
+        }
+
+        RawFields { internal: fields }
+    }
+
+    /// Get the raw value of a field.
+    pub(crate) fn get(&self, field: FieldName) -> Option<&'a str> {
+        let index = DESC_FIELDS.iter().position(|item| *item == field)?;
+        self.internal[index]
+    }
+
+    /// Get the raw values of all fields, in the order of [`DESC_FIELDS`].
+    pub(crate) fn values(&self) -> [Option<&'a str>; DESC_FIELDS.len()] {
+        self.internal
+    }
+}
@@ -0,0 +1,183 @@
+//! Resolve the dependencies of packages into an install set.
+
+mod error;
+mod installed;
+mod providers;
+mod source;
+
+pub use error::{ResolveError, UnsatisfiedDependency};
+pub use installed::{InstalledPackages, NothingInstalled};
+pub use source::DependencySource;
+
+use arch_pkg_text::{
+    desc::Query,
+    value::{Dependency, Name},
+};
+use core::cell::OnceCell;
+use providers::{ProviderMap, satisfies_by_name};
+
+/// Resolver of transitive dependencies, the equivalent of `pacman -S --print`.
+///
+/// Packages are chosen from a [`DependencySource`]. Dependencies which are already satisfied by
+/// [installed packages](InstalledPackages) are skipped, but the targets themselves are always included.
+#[derive(Debug, Clone, Copy)]
+pub struct Resolver<Source, Installed = NothingInstalled> {
+    source: Source,
+    installed: Installed,
+}
+
+impl<Source> Resolver<Source> {
+    /// Create a resolver which assumes that nothing has been installed.
+    pub fn new(source: Source) -> Self {
+        Resolver {
+            source,
+            installed: NothingInstalled,
+        }
+    }
+}
+
+impl<Source, Installed> Resolver<Source, Installed> {
+    /// Skip dependencies which are satisfied by `installed`.
+    pub fn with_installed<NewInstalled>(
+        self,
+        installed: NewInstalled,
+    ) -> Resolver<Source, NewInstalled> {
+        Resolver {
+            source: self.source,
+            installed,
+        }
+    }
+
+    /// Get the source of packages.
+    pub fn source(&self) -> &Source {
+        &self.source
+    }
+
+    /// Get the installed packages.
+    pub fn installed(&self) -> &Installed {
+        &self.installed
+    }
+}
+
+impl<'a, Source, Installed> Resolver<Source, Installed>
+where
+    Source: DependencySource<'a>,
+    Installed: InstalledPackages<'a>,
+{
+    /// Resolve the targets and their [`depends`](Query::dependencies) recursively.
+    ///
+    /// On success, return the packages to install, every package comes after its dependencies
+    /// (except when the dependencies form a cycle). On failure, return all dependencies that
+    /// couldn't be satisfied.
+    ///
+    /// The packages of the source, the installed packages, and the chosen packages are indexed by the
+    /// names they have or provide, so that each dependency is looked up without scanning every package.
+    pub fn resolve(
+        &self,
+        targets: impl IntoIterator<Item = Dependency<'a>>,
+    ) -> Result<Vec<Source::Querier>, ResolveError<'a>> {
+        let mut state = State {
+            providers: OnceCell::new(),
+            installed: OnceCell::new(),
+            visited: ProviderMap::new([]),
+            picked: Vec::new(),
+            chain: Vec::new(),
+            unsatisfied: Vec::new(),
+        };
+        for target in targets {
+            self.visit(&mut state, target, true);
+        }
+        if state.unsatisfied.is_empty() {
+            Ok(state.picked)
+        } else {
+            Err(ResolveError::new(state.unsatisfied))
+        }
+    }
+
+    /// Resolve a single dependency and its own dependencies.
+    fn visit(
+        &self,
+        state: &mut State<'a, Source::Querier, Installed::Querier>,
+        dependency: Dependency<'a>,
+        target: bool,
+    ) {
+        if !target && self.is_installed(state, dependency) {
+            return;
+        }
+
+        // packages in the chain are visited too, which stops dependency cycles
+        if state.visited.satisfiers(dependency).next().is_some() {
+            return;
+        }
+
+        let Some(querier) = self.find_satisfier(state, dependency) else {
+            let chain = state.chain.iter().filter_map(Query::name).collect();
+            let unsatisfied = UnsatisfiedDependency::new(dependency, chain);
+            state.unsatisfied.push(unsatisfied);
+            return;
+        };
+
+        state.visited.insert(querier);
+        state.chain.push(querier);
+        for dependency in querier.dependencies().into_iter().flatten() {
+            self.visit(state, dependency, false);
+        }
+        state.chain.pop();
+        state.picked.push(querier);
+    }
+
+    /// Check whether an installed package satisfies `dependency`.
+    fn is_installed(
+        &self,
+        state: &State<'a, Source::Querier, Installed::Querier>,
+        dependency: Dependency,
+    ) -> bool {
+        state
+            .installed
+            .get_or_init(|| ProviderMap::new(self.installed.packages()))
+            .satisfiers(dependency)
+            .next()
+            .is_some()
+    }
+
+    /// Choose a package from the source to satisfy `dependency`.
+    ///
+    /// A package whose name is the dependency name is preferred over packages which merely provide it.
+    fn find_satisfier(
+        &self,
+        state: &State<'a, Source::Querier, Installed::Querier>,
+        dependency: Dependency,
+    ) -> Option<Source::Querier> {
+        let providers = state
+            .providers
+            .get_or_init(|| ProviderMap::new(self.source.packages()));
+        let (name, _) = dependency.components();
+        providers
+            .satisfiers(dependency)
+            .find(|querier| {
+                querier.name() == Some(Name(name.as_str()))
+                    && satisfies_by_name(dependency, querier)
+            })
+            .or_else(|| {
+                self.source
+                    .choose_provider(providers.satisfiers(dependency))
+            })
+    }
+}
+
+/// Mutable state of [`Resolver::resolve`].
+struct State<'a, Querier, InstalledQuerier> {
+    /// Packages of the source indexed by the names they have or provide, built upon first use.
+    providers: OnceCell<ProviderMap<'a, Querier>>,
+    /// Installed packages indexed by the names they have or provide, built upon first use.
+    installed: OnceCell<ProviderMap<'a, InstalledQuerier>>,
+    /// Packages of [`picked`](State::picked) and [`chain`](State::chain) indexed by the names they
+    /// have or provide.
+    visited: ProviderMap<'a, Querier>,
+    /// Packages whose dependencies have all been visited.
+    picked: Vec<Querier>,
+    /// Packages whose dependencies are being visited.
+    chain: Vec<Querier>,
+    /// Dependencies which couldn't be satisfied.
+    unsatisfied: Vec<UnsatisfiedDependency<'a>>,
+}
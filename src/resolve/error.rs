@@ -0,0 +1,56 @@
+use arch_pkg_text::value::{Dependency, Name};
+use derive_more::{Display, Error};
+use itertools::Itertools;
+
+/// Dependency which no package could satisfy.
+#[derive(Debug, Display, Clone, Error)]
+#[display("{dependency}{}", RequiredBy(chain))]
+pub struct UnsatisfiedDependency<'a> {
+    #[error(not(source))]
+    dependency: Dependency<'a>,
+    chain: Vec<Name<'a>>,
+}
+
+impl<'a> UnsatisfiedDependency<'a> {
+    /// Create an error.
+    pub(super) fn new(dependency: Dependency<'a>, chain: Vec<Name<'a>>) -> Self {
+        UnsatisfiedDependency { dependency, chain }
+    }
+
+    /// The dependency which couldn't be satisfied.
+    pub fn dependency(&self) -> Dependency<'a> {
+        self.dependency
+    }
+
+    /// Names of the packages which led to the dependency, starting from the target.
+    ///
+    /// The chain is empty if the dependency was a target.
+    pub fn chain(&self) -> &[Name<'a>] {
+        &self.chain
+    }
+}
+
+/// Display the chain of an [`UnsatisfiedDependency`].
+#[derive(Display)]
+#[display("{}", if _0.is_empty() { String::new() } else { format!(" (required by {})", _0.iter().join(" -> ")) })]
+struct RequiredBy<'r, 'a>(&'r [Name<'a>]);
+
+/// Error when some dependencies couldn't be satisfied.
+#[derive(Debug, Display, Clone, Error)]
+#[display("Unsatisfiable dependencies: {}", unsatisfied.iter().join(", "))]
+pub struct ResolveError<'a> {
+    #[error(not(source))]
+    unsatisfied: Vec<UnsatisfiedDependency<'a>>,
+}
+
+impl<'a> ResolveError<'a> {
+    /// Create an error.
+    pub(super) fn new(unsatisfied: Vec<UnsatisfiedDependency<'a>>) -> Self {
+        ResolveError { unsatisfied }
+    }
+
+    /// All dependencies which couldn't be satisfied.
+    pub fn unsatisfied(&self) -> &[UnsatisfiedDependency<'a>] {
+        &self.unsatisfied
+    }
+}
@@ -0,0 +1,33 @@
+use crate::QueryDatabase;
+use arch_pkg_text::desc::{EagerQuerier, Query};
+use core::iter;
+
+/// Set of installed packages which could satisfy dependencies.
+pub trait InstalledPackages<'a> {
+    /// Type of querier of an installed package.
+    type Querier: Query<'a> + Copy;
+
+    /// Get an iterator over all installed packages.
+    fn packages(&self) -> impl Iterator<Item = Self::Querier>;
+}
+
+/// Empty set of installed packages.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct NothingInstalled;
+
+impl<'a> InstalledPackages<'a> for NothingInstalled {
+    type Querier = &'a EagerQuerier<'a>;
+
+    fn packages(&self) -> impl Iterator<Item = Self::Querier> {
+        iter::empty()
+    }
+}
+
+impl<'a, Querier: Query<'a>> InstalledPackages<'a> for &'a QueryDatabase<'a, Querier> {
+    type Querier = &'a Querier;
+
+    fn packages(&self) -> impl Iterator<Item = Self::Querier> {
+        let db: &'a QueryDatabase<'a, Querier> = self;
+        db.queriers()
+    }
+}
@@ -0,0 +1,70 @@
+use crate::satisfy::package_satisfies;
+use arch_pkg_text::{desc::Query, value::Dependency};
+use std::collections::HashMap;
+
+/// Map of names to the packages which have or provide them.
+pub(super) struct ProviderMap<'a, Querier> {
+    internal: HashMap<&'a str, Vec<Querier>>,
+}
+
+impl<'a, Querier: Query<'a> + Copy> ProviderMap<'a, Querier> {
+    /// Index packages by their own names and the names in their [`provides`](Query::provides) arrays.
+    pub(super) fn new(packages: impl IntoIterator<Item = Querier>) -> Self {
+        let mut map = ProviderMap {
+            internal: HashMap::new(),
+        };
+        for querier in packages {
+            map.insert(querier);
+        }
+        map
+    }
+
+    /// Index a package by its own name and the names in its [`provides`](Query::provides) array.
+    pub(super) fn insert(&mut self, querier: Querier) {
+        let provides = querier
+            .provides()
+            .into_iter()
+            .flatten()
+            .map(|provision| provision.components().0.as_str());
+        let mut names: Vec<_> = querier
+            .name()
+            .map(|name| name.as_str())
+            .into_iter()
+            .chain(provides)
+            .collect();
+        names.sort_unstable();
+        names.dedup();
+        for name in names {
+            self.internal.entry(name).or_default().push(querier);
+        }
+    }
+
+    /// Get an iterator over the packages which satisfy `dependency`.
+    pub(super) fn satisfiers<'r>(
+        &'r self,
+        dependency: Dependency<'r>,
+    ) -> impl Iterator<Item = Querier> + 'r {
+        let (name, _) = dependency.components();
+        self.internal
+            .get(name.as_str())
+            .into_iter()
+            .flatten()
+            .copied()
+            .filter(move |querier| satisfies(dependency, querier))
+    }
+}
+
+/// Check whether a querier satisfies a dependency.
+pub(super) fn satisfies<'a>(dependency: Dependency, querier: &impl Query<'a>) -> bool {
+    package_satisfies(
+        dependency,
+        querier.name(),
+        querier.version(),
+        querier.provides(),
+    )
+}
+
+/// Check whether a querier satisfies a dependency by its own name and version.
+pub(super) fn satisfies_by_name<'a>(dependency: Dependency, querier: &impl Query<'a>) -> bool {
+    package_satisfies(dependency, querier.name(), querier.version(), None)
+}
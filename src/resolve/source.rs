@@ -0,0 +1,87 @@
+use crate::{
+    QueryDatabase,
+    multi::{
+        MultiQueryDatabase, MultiQueryDatabaseLatest, MultiQueryDatabasePrioritized,
+        WithParsedVersion, WithParsedVersionUtils, WithRepositoryName, WithRepositoryNameUtils,
+    },
+};
+use arch_pkg_text::desc::Query;
+
+/// Database from which packages could be chosen to satisfy dependencies.
+pub trait DependencySource<'a> {
+    /// Type of querier of a chosen package.
+    type Querier: Query<'a> + Copy;
+
+    /// Get an iterator over all packages which could be chosen, at most one per package name.
+    fn packages(&self) -> impl Iterator<Item = Self::Querier>;
+
+    /// Choose the preferred package among `providers`, all of which satisfy the same dependency.
+    fn choose_provider(
+        &self,
+        providers: impl Iterator<Item = Self::Querier>,
+    ) -> Option<Self::Querier>;
+}
+
+/// Providers are chosen by their names to be deterministic.
+impl<'a, Querier: Query<'a>> DependencySource<'a> for &'a QueryDatabase<'a, Querier> {
+    type Querier = &'a Querier;
+
+    fn packages(&self) -> impl Iterator<Item = Self::Querier> {
+        let db: &'a QueryDatabase<'a, Querier> = self;
+        db.queriers()
+    }
+
+    fn choose_provider(
+        &self,
+        providers: impl Iterator<Item = Self::Querier>,
+    ) -> Option<Self::Querier> {
+        providers.min_by_key(|querier| querier.name())
+    }
+}
+
+/// Providers from repositories which come first in the order are preferred, ties are broken by names.
+impl<'a, Querier: Query<'a>> DependencySource<'a>
+    for MultiQueryDatabasePrioritized<'_, &'a MultiQueryDatabase<'a, Querier>>
+{
+    type Querier = WithRepositoryName<'a, WithParsedVersion<'a, &'a Querier>>;
+
+    fn packages(&self) -> impl Iterator<Item = Self::Querier> {
+        self.base.prioritized_queriers(self.order)
+    }
+
+    fn choose_provider(
+        &self,
+        providers: impl Iterator<Item = Self::Querier>,
+    ) -> Option<Self::Querier> {
+        let rank = |querier: &Self::Querier| {
+            self.order
+                .iter()
+                .position(|repository| *repository == querier.repository_name())
+                .unwrap_or(self.order.len())
+        };
+        providers.min_by_key(|querier| (rank(querier), querier.name()))
+    }
+}
+
+/// Providers of greater versions are preferred, ties are broken by names.
+impl<'a, Querier: Query<'a>> DependencySource<'a>
+    for MultiQueryDatabaseLatest<&'a MultiQueryDatabase<'a, Querier>>
+{
+    type Querier = WithRepositoryName<'a, WithParsedVersion<'a, &'a Querier>>;
+
+    fn packages(&self) -> impl Iterator<Item = Self::Querier> {
+        self.base.latest_queriers()
+    }
+
+    fn choose_provider(
+        &self,
+        providers: impl Iterator<Item = Self::Querier>,
+    ) -> Option<Self::Querier> {
+        providers.min_by(|left, right| {
+            right
+                .parsed_version()
+                .cmp(&left.parsed_version())
+                .then_with(|| left.name().cmp(&right.name()))
+        })
+    }
+}
@@ -0,0 +1,156 @@
+//! Reverse dependency indices, i.e. "required by" and "optional for".
+
+mod multi;
+mod single;
+
+pub use multi::MultiReverseDependencyIndex;
+pub use single::ReverseDependencyIndex;
+
+use arch_pkg_text::{
+    desc::Query,
+    value::{DependencyName, Name},
+};
+use core::cell::OnceCell;
+use std::collections::HashMap;
+
+/// Kind of dependency array.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DependencyKind {
+    /// Packages listed in [`depends`](Query::dependencies).
+    Depends,
+    /// Packages listed in [`optdepends`](Query::opt_dependencies).
+    OptDepends,
+    /// Packages listed in [`makedepends`](Query::make_dependencies).
+    MakeDepends,
+    /// Packages listed in [`checkdepends`](Query::check_dependencies).
+    CheckDepends,
+}
+
+impl DependencyKind {
+    /// All kinds of dependency arrays.
+    pub const ALL: [Self; 4] = [
+        DependencyKind::Depends,
+        DependencyKind::OptDepends,
+        DependencyKind::MakeDepends,
+        DependencyKind::CheckDepends,
+    ];
+
+    /// Position of the kind in [`DependencyKind::ALL`].
+    const fn index(self) -> usize {
+        self as usize
+    }
+
+    /// List the names of the dependencies of a querier in the array of this kind.
+    fn dependency_names<'a>(self, querier: &impl Query<'a>) -> Vec<DependencyName<'a>> {
+        match self {
+            DependencyKind::Depends => querier
+                .dependencies()
+                .into_iter()
+                .flatten()
+                .map(|dependency| dependency.components().0)
+                .collect(),
+            DependencyKind::OptDepends => querier
+                .opt_dependencies()
+                .into_iter()
+                .flatten()
+                .map(|dependency| dependency.components().0.components().0)
+                .collect(),
+            DependencyKind::MakeDepends => querier
+                .make_dependencies()
+                .into_iter()
+                .flatten()
+                .map(|dependency| dependency.components().0)
+                .collect(),
+            DependencyKind::CheckDepends => querier
+                .check_dependencies()
+                .into_iter()
+                .flatten()
+                .map(|dependency| dependency.components().0)
+                .collect(),
+        }
+    }
+}
+
+/// Dependent packages stored in a reverse dependency index.
+trait Dependent: Copy + Eq {
+    /// Key to sort the dependents of a dependency name.
+    fn sort_key(&self) -> (&str, &str);
+}
+
+/// Map dependency names to dependents.
+type DependentMap<'a, Item> = HashMap<&'a str, Vec<Item>>;
+
+/// Maps of dependency names to dependents, each built upon first use.
+#[derive(Debug)]
+struct LazyMaps<'a, Item> {
+    internal: [OnceCell<DependentMap<'a, Item>>; DependencyKind::ALL.len()],
+}
+
+impl<'a, Item: Dependent> LazyMaps<'a, Item> {
+    /// Create maps which haven't been built.
+    fn new() -> Self {
+        LazyMaps {
+            internal: Default::default(),
+        }
+    }
+
+    /// Get the map of a kind, build it from `packages` if it hasn't been built.
+    fn get<'q, Querier, Packages>(
+        &self,
+        kind: DependencyKind,
+        packages: impl FnOnce() -> Packages,
+    ) -> &DependentMap<'a, Item>
+    where
+        Querier: Query<'a> + 'q,
+        Packages: IntoIterator<Item = (Item, &'q Querier)>,
+    {
+        self.internal[kind.index()].get_or_init(|| {
+            let mut map = DependentMap::<Item>::new();
+            for (dependent, querier) in packages() {
+                for name in kind.dependency_names(querier) {
+                    // every dependent is visited once, so a duplicate could only be the last one
+                    let dependents = map.entry(name.as_str()).or_default();
+                    if dependents.last() != Some(&dependent) {
+                        dependents.push(dependent);
+                    }
+                }
+            }
+            for dependents in map.values_mut() {
+                dependents.sort_by(|a, b| a.sort_key().cmp(&b.sort_key()));
+            }
+            map
+        })
+    }
+}
+
+/// Look up the dependents of every name in `names` and combine them without duplication.
+fn combine<'n, Item: Dependent>(
+    map: &DependentMap<'_, Item>,
+    names: impl IntoIterator<Item = &'n str>,
+) -> Vec<Item> {
+    let mut result: Vec<_> = names
+        .into_iter()
+        .filter_map(|name| map.get(name))
+        .flatten()
+        .copied()
+        .collect();
+    result.sort_by(|a, b| a.sort_key().cmp(&b.sort_key()));
+    result.dedup();
+    result
+}
+
+/// Get the own name and provided names of a package.
+fn provided_names<'n, 'a: 'n, Querier: Query<'a>>(
+    name: Name<'n>,
+    queriers: impl IntoIterator<Item = Querier>,
+) -> Vec<&'n str> {
+    let provides = queriers
+        .into_iter()
+        .filter_map(|querier| querier.provides())
+        .flatten()
+        .map(|provision| provision.components().0.as_str());
+    let mut names: Vec<_> = [name.as_str()].into_iter().chain(provides).collect();
+    names.sort_unstable();
+    names.dedup();
+    names
+}
@@ -0,0 +1,84 @@
+use super::{DependencyKind, Dependent, DependentMap, LazyMaps, combine, provided_names};
+use crate::{MultiQueryDatabase, value::RepositoryName};
+use arch_pkg_text::{
+    desc::Query,
+    value::{DependencyName, Name},
+};
+
+impl Dependent for (RepositoryName<'_>, Name<'_>) {
+    fn sort_key(&self) -> (&str, &str) {
+        let (repository, name) = self;
+        (name.as_str(), repository.as_str())
+    }
+}
+
+/// Index to lookup the packages in a [`MultiQueryDatabase`] which depend on a certain name.
+///
+/// Each dependent is identified by its repository and package name. The map of each [`DependencyKind`]
+/// is only built when it is first needed.
+///
+/// This index is created by calling [`MultiQueryDatabase::reverse_dependencies`].
+#[derive(Debug)]
+pub struct MultiReverseDependencyIndex<'a, Querier> {
+    db: &'a MultiQueryDatabase<'a, Querier>,
+    maps: LazyMaps<'a, (RepositoryName<'a>, Name<'a>)>,
+}
+
+impl<'a, Querier: Query<'a>> MultiReverseDependencyIndex<'a, Querier> {
+    /// Get the map of a kind, build it if it hasn't been built.
+    fn map(&self, kind: DependencyKind) -> &DependentMap<'a, (RepositoryName<'a>, Name<'a>)> {
+        self.maps.get(kind, || {
+            self.db.entries().flat_map(|(name, queriers)| {
+                queriers
+                    .entries()
+                    .map(move |(repository, querier)| ((repository, name), querier))
+            })
+        })
+    }
+
+    /// Get the packages which list `name` in their dependency arrays of `kind`.
+    ///
+    /// Packages which provide `name` are not taken into account, use [`Self::dependents_of`] for that.
+    pub fn dependents(
+        &self,
+        kind: DependencyKind,
+        name: DependencyName,
+    ) -> &[(RepositoryName<'a>, Name<'a>)] {
+        self.map(kind).get(name.as_str()).map_or(&[], Vec::as_slice)
+    }
+
+    /// Get the packages which list `package` or anything [provided](Query::provides) by any version of
+    /// `package` in their dependency arrays of `kind`.
+    pub fn dependents_of(
+        &self,
+        kind: DependencyKind,
+        package: Name,
+    ) -> Vec<(RepositoryName<'a>, Name<'a>)> {
+        let queriers = self
+            .db
+            .get(package)
+            .into_iter()
+            .flat_map(|queriers| queriers.queriers());
+        combine(self.map(kind), provided_names(package, queriers))
+    }
+
+    /// Get the packages which list `package` or anything it provides in their `depends` arrays.
+    pub fn required_by(&self, package: Name) -> Vec<(RepositoryName<'a>, Name<'a>)> {
+        self.dependents_of(DependencyKind::Depends, package)
+    }
+
+    /// Get the packages which list `package` or anything it provides in their `optdepends` arrays.
+    pub fn optional_for(&self, package: Name) -> Vec<(RepositoryName<'a>, Name<'a>)> {
+        self.dependents_of(DependencyKind::OptDepends, package)
+    }
+}
+
+impl<'a, Querier> MultiQueryDatabase<'a, Querier> {
+    /// Create an index to lookup the packages which depend on a certain name.
+    pub fn reverse_dependencies(&'a self) -> MultiReverseDependencyIndex<'a, Querier> {
+        MultiReverseDependencyIndex {
+            db: self,
+            maps: LazyMaps::new(),
+        }
+    }
+}
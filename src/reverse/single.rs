@@ -0,0 +1,67 @@
+use super::{DependencyKind, Dependent, LazyMaps, combine, provided_names};
+use crate::QueryDatabase;
+use arch_pkg_text::{
+    desc::Query,
+    value::{DependencyName, Name},
+};
+
+impl Dependent for Name<'_> {
+    fn sort_key(&self) -> (&str, &str) {
+        (self.as_str(), "")
+    }
+}
+
+/// Index to lookup the packages in a [`QueryDatabase`] which depend on a certain name.
+///
+/// The map of each [`DependencyKind`] is only built when it is first needed.
+///
+/// This index is created by calling [`QueryDatabase::reverse_dependencies`].
+#[derive(Debug)]
+pub struct ReverseDependencyIndex<'a, Querier> {
+    db: &'a QueryDatabase<'a, Querier>,
+    maps: LazyMaps<'a, Name<'a>>,
+}
+
+impl<'a, Querier: Query<'a>> ReverseDependencyIndex<'a, Querier> {
+    /// Get the packages which list `name` in their dependency arrays of `kind`.
+    ///
+    /// Packages which provide `name` are not taken into account, use [`Self::dependents_of`] for that.
+    pub fn dependents(&self, kind: DependencyKind, name: DependencyName) -> &[Name<'a>] {
+        self.maps
+            .get(kind, || self.db.entries().map(|entry| entry.into_tuple()))
+            .get(name.as_str())
+            .map_or(&[], Vec::as_slice)
+    }
+
+    /// Get the packages which list `package` or anything it [`provides`](Query::provides) in their
+    /// dependency arrays of `kind`.
+    pub fn dependents_of(&self, kind: DependencyKind, package: Name) -> Vec<Name<'a>> {
+        let map = self
+            .maps
+            .get(kind, || self.db.entries().map(|entry| entry.into_tuple()));
+        let names = provided_names(package, self.db.get(package));
+        combine(map, names)
+    }
+
+    /// Get the packages which list `package` or anything it provides in their `depends` arrays,
+    /// equivalent to "Required By" of `pacman -Qi`.
+    pub fn required_by(&self, package: Name) -> Vec<Name<'a>> {
+        self.dependents_of(DependencyKind::Depends, package)
+    }
+
+    /// Get the packages which list `package` or anything it provides in their `optdepends` arrays,
+    /// equivalent to "Optional For" of `pacman -Qi`.
+    pub fn optional_for(&self, package: Name) -> Vec<Name<'a>> {
+        self.dependents_of(DependencyKind::OptDepends, package)
+    }
+}
+
+impl<'a, Querier> QueryDatabase<'a, Querier> {
+    /// Create an index to lookup the packages which depend on a certain name.
+    pub fn reverse_dependencies(&'a self) -> ReverseDependencyIndex<'a, Querier> {
+        ReverseDependencyIndex {
+            db: self,
+            maps: LazyMaps::new(),
+        }
+    }
+}
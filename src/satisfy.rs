@@ -0,0 +1,82 @@
+//! Check whether packages satisfy dependencies.
+//!
+//! The rules are those of pacman:
+//! * A package satisfies a dependency of its own name if its version satisfies the version constraint.
+//! * A `provides` entry satisfies an unversioned dependency of its name regardless of its own version.
+//! * A `provides` entry satisfies a versioned dependency only if it was declared with `=` and its
+//!   version satisfies the version constraint.
+
+mod vercmp;
+
+pub use vercmp::vercmp;
+
+use arch_pkg_text::value::{
+    Dependency, DependencyList, DependencySpecification, DependencySpecificationOperator, Name,
+    Version,
+};
+use core::cmp::Ordering;
+
+/// Check whether a `version` satisfies a [`DependencySpecification`].
+///
+/// An empty specification is satisfied by any version. A specification that could not be parsed is
+/// satisfied by none.
+pub fn version_satisfies(specification: DependencySpecification, version: Version) -> bool {
+    if specification.is_empty() {
+        return true;
+    }
+    let Some((operator, required)) = specification.components() else {
+        return false;
+    };
+    let ordering = vercmp(version, required);
+    match operator {
+        DependencySpecificationOperator::Less => ordering == Ordering::Less,
+        DependencySpecificationOperator::LessOrEqual => ordering != Ordering::Greater,
+        DependencySpecificationOperator::Equal => ordering == Ordering::Equal,
+        DependencySpecificationOperator::GreaterOrEqual => ordering != Ordering::Less,
+        DependencySpecificationOperator::Greater => ordering == Ordering::Greater,
+    }
+}
+
+/// Check whether a `provides` entry satisfies a `dependency`.
+pub fn provision_satisfies(dependency: Dependency, provision: Dependency) -> bool {
+    let (name, specification) = dependency.components();
+    let (provided_name, provided_specification) = provision.components();
+    if name != provided_name {
+        return false;
+    }
+    if specification.is_empty() {
+        return true;
+    }
+    match provided_specification.components() {
+        Some((DependencySpecificationOperator::Equal, version)) => {
+            version_satisfies(specification, version)
+        }
+        _ => false,
+    }
+}
+
+/// Check whether a package with a certain `name`, `version`, and `provides` satisfies a `dependency`.
+///
+/// A package without a version could only satisfy a dependency of its own name if the dependency is unversioned.
+pub fn package_satisfies(
+    dependency: Dependency,
+    name: Option<Name>,
+    version: Option<Version>,
+    provides: Option<DependencyList>,
+) -> bool {
+    let (dependency_name, specification) = dependency.components();
+    let name_matched = name.is_some_and(|name| name.as_str() == dependency_name.as_str());
+    if name_matched {
+        let version_matched = match version {
+            Some(version) => version_satisfies(specification, version),
+            None => specification.is_empty(),
+        };
+        if version_matched {
+            return true;
+        }
+    }
+    provides
+        .into_iter()
+        .flatten()
+        .any(|provision| provision_satisfies(dependency, provision))
+}
@@ -0,0 +1,129 @@
+use arch_pkg_text::value::Version;
+use core::cmp::Ordering;
+
+/// Compare two versions the way pacman's `vercmp` does.
+///
+/// Unlike [`Version::parse`], this function accepts versions without a release suffix (such as `2.38`
+/// in `glibc>=2.38`). The releases are only compared when both versions have them. A missing epoch is
+/// treated as `0`.
+pub fn vercmp(left: Version, right: Version) -> Ordering {
+    if left.as_str() == right.as_str() {
+        return Ordering::Equal;
+    }
+    let (left_epoch, left_version, left_release) = split_evr(left.as_str());
+    let (right_epoch, right_version, right_release) = split_evr(right.as_str());
+    rpmvercmp(left_epoch, right_epoch)
+        .then_with(|| rpmvercmp(left_version, right_version))
+        .then_with(|| match (left_release, right_release) {
+            (Some(left), Some(right)) => rpmvercmp(left, right),
+            _ => Ordering::Equal,
+        })
+}
+
+/// Split a version string into epoch, version, and optional release.
+fn split_evr(evr: &str) -> (&str, &str, Option<&str>) {
+    let digits = evr.bytes().take_while(u8::is_ascii_digit).count();
+    let (epoch, rest) = match evr[digits..].strip_prefix(':') {
+        Some(rest) if digits == 0 => ("0", rest),
+        Some(rest) => (&evr[..digits], rest),
+        None => ("0", evr),
+    };
+    match rest.rsplit_once('-') {
+        Some((version, release)) => (epoch, version, Some(release)),
+        None => (epoch, rest, None),
+    }
+}
+
+/// Compare two version segments with the algorithm of rpm (as adopted by pacman).
+fn rpmvercmp(left: &str, right: &str) -> Ordering {
+    if left == right {
+        return Ordering::Equal;
+    }
+
+    let left = left.as_bytes();
+    let right = right.as_bytes();
+    let (mut one, mut two) = (0, 0);
+
+    while one < left.len() && two < right.len() {
+        let (start_one, start_two) = (one, two);
+        while one < left.len() && !left[one].is_ascii_alphanumeric() {
+            one += 1;
+        }
+        while two < right.len() && !right[two].is_ascii_alphanumeric() {
+            two += 1;
+        }
+
+        if one == left.len() || two == right.len() {
+            break;
+        }
+
+        // the separators of different lengths decide the order
+        let separator_cmp = (one - start_one).cmp(&(two - start_two));
+        if separator_cmp != Ordering::Equal {
+            return separator_cmp;
+        }
+
+        let is_num = left[one].is_ascii_digit();
+        let segment_end = |bytes: &[u8], start: usize| {
+            let matches: fn(&u8) -> bool = if is_num {
+                u8::is_ascii_digit
+            } else {
+                u8::is_ascii_alphabetic
+            };
+            start
+                + bytes[start..]
+                    .iter()
+                    .take_while(|char| matches(char))
+                    .count()
+        };
+        let (segment_one, segment_two) = (one, two);
+        one = segment_end(left, one);
+        two = segment_end(right, two);
+
+        // numeric segments are always newer than alpha segments
+        if two == segment_two {
+            return if is_num {
+                Ordering::Greater
+            } else {
+                Ordering::Less
+            };
+        }
+
+        let mut segment_one = &left[segment_one..one];
+        let mut segment_two = &right[segment_two..two];
+        if is_num {
+            segment_one = trim_leading_zeros(segment_one);
+            segment_two = trim_leading_zeros(segment_two);
+            let length_cmp = segment_one.len().cmp(&segment_two.len());
+            if length_cmp != Ordering::Equal {
+                return length_cmp;
+            }
+        }
+
+        let segment_cmp = segment_one.cmp(segment_two);
+        if segment_cmp != Ordering::Equal {
+            return segment_cmp;
+        }
+    }
+
+    let one_ended = one == left.len();
+    let two_ended = two == right.len();
+    if one_ended && two_ended {
+        return Ordering::Equal;
+    }
+
+    // a remaining alpha segment never beats an empty string
+    let two_is_alpha = !two_ended && right[two].is_ascii_alphabetic();
+    let one_is_alpha = !one_ended && left[one].is_ascii_alphabetic();
+    if (one_ended && !two_is_alpha) || one_is_alpha {
+        Ordering::Less
+    } else {
+        Ordering::Greater
+    }
+}
+
+/// Remove leading zeros from a numeric segment.
+fn trim_leading_zeros(segment: &[u8]) -> &[u8] {
+    let zeros = segment.iter().take_while(|char| **char == b'0').count();
+    &segment[zeros..]
+}
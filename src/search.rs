@@ -0,0 +1,261 @@
+//! Search packages by their metadata, i.e. `pacman -Ss`.
+
+use crate::{MultiQueryDatabase, QueryDatabase, multi::WithParsedVersion, value::RepositoryName};
+use arch_pkg_text::desc::Query;
+use std::borrow::Cow;
+
+/// Whether letter case matters when matching.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CaseSensitivity {
+    /// Letters of both the query and the metadata are folded to lowercase, like `pacman -Ss`.
+    #[default]
+    Insensitive,
+    /// Letters must match exactly.
+    Sensitive,
+}
+
+/// How well a package matches a [`SearchQuery`], better ranks compare lower.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SearchRank {
+    /// The name of the package is a term, or is entirely matched by the regex.
+    ExactName,
+    /// The name of the package starts with a match.
+    NamePrefix,
+    /// The name of the package contains a match.
+    Name,
+    /// The description of the package contains a match.
+    Description,
+    /// Only the provisions or groups of the package contain matches.
+    Other,
+}
+
+/// Pattern of a [`SearchQuery`].
+#[derive(Debug, Clone)]
+enum Pattern {
+    /// Every term must be found in at least one field.
+    Terms(Vec<String>),
+    /// The regex must match at least one field.
+    #[cfg(feature = "regex")]
+    Regex {
+        /// The regex as given.
+        regex: regex::Regex,
+        /// The regex anchored at both ends, to check whether it matches a whole name.
+        exact: regex_automata::meta::Regex,
+    },
+}
+
+/// Query to search packages by their names, descriptions, provisions, and groups.
+#[derive(Debug, Clone)]
+pub struct SearchQuery {
+    pattern: Pattern,
+    case: CaseSensitivity,
+}
+
+/// Searchable fields of a package, folded according to [`CaseSensitivity`].
+struct Fields<'a> {
+    name: Cow<'a, str>,
+    description: Option<Cow<'a, str>>,
+    others: Vec<Cow<'a, str>>,
+}
+
+impl<'a> Fields<'a> {
+    /// Collect the searchable fields of a querier.
+    fn new(querier: &impl Query<'a>, case: CaseSensitivity) -> Option<Self> {
+        let fold = |text: &'a str| match case {
+            CaseSensitivity::Insensitive => Cow::Owned(text.to_lowercase()),
+            CaseSensitivity::Sensitive => Cow::Borrowed(text),
+        };
+        let name = fold(querier.name()?.as_str());
+        let description = querier
+            .description()
+            .map(|description| fold(description.as_str()));
+        let provides = querier
+            .provides()
+            .into_iter()
+            .flatten()
+            .map(|provision| provision.components().0.as_str());
+        let groups = querier
+            .groups()
+            .into_iter()
+            .flatten()
+            .map(|group| group.as_str());
+        let others = provides
+            .chain(groups)
+            .map(str::trim)
+            .filter(|text| !text.is_empty())
+            .map(fold)
+            .collect();
+        Some(Fields {
+            name,
+            description,
+            others,
+        })
+    }
+
+    /// Iterate over all fields.
+    fn iter(&self) -> impl Iterator<Item = &str> {
+        [Some(&self.name), self.description.as_ref()]
+            .into_iter()
+            .flatten()
+            .chain(&self.others)
+            .map(|text| text.as_ref())
+    }
+}
+
+impl SearchQuery {
+    /// Create a query of whitespace-separated terms, all of which must be found in a package.
+    ///
+    /// A query without any term matches every package.
+    pub fn terms(text: &str, case: CaseSensitivity) -> Self {
+        let terms = text
+            .split_whitespace()
+            .map(|term| match case {
+                CaseSensitivity::Insensitive => term.to_lowercase(),
+                CaseSensitivity::Sensitive => term.to_string(),
+            })
+            .collect();
+        SearchQuery {
+            pattern: Pattern::Terms(terms),
+            case,
+        }
+    }
+
+    /// Create a query of a regular expression which must match a field of a package.
+    #[cfg(feature = "regex")]
+    pub fn regex(pattern: &str, case: CaseSensitivity) -> Result<Self, regex::Error> {
+        let case_insensitive = case == CaseSensitivity::Insensitive;
+        let regex = regex::RegexBuilder::new(pattern)
+            .case_insensitive(case_insensitive)
+            .build()?;
+        let exact = exact_regex(pattern, case_insensitive)?;
+        Ok(SearchQuery {
+            pattern: Pattern::Regex { regex, exact },
+            case,
+        })
+    }
+
+    /// Whether letter case matters when matching.
+    pub fn case(&self) -> CaseSensitivity {
+        self.case
+    }
+
+    /// Rank how well the package of a querier matches, or return `None` if it doesn't match.
+    pub fn rank<'a>(&self, querier: &impl Query<'a>) -> Option<SearchRank> {
+        match &self.pattern {
+            Pattern::Terms(terms) => {
+                let fields = Fields::new(querier, self.case)?;
+                let found =
+                    |term: &String| fields.iter().any(|field| field.contains(term.as_str()));
+                if !terms.iter().all(found) {
+                    return None;
+                }
+                let name = fields.name.as_ref();
+                let description = fields.description.as_deref().unwrap_or_default();
+                Some(if terms.iter().any(|term| name == term) {
+                    SearchRank::ExactName
+                } else if terms.iter().any(|term| name.starts_with(term.as_str())) {
+                    SearchRank::NamePrefix
+                } else if terms.iter().any(|term| name.contains(term.as_str())) {
+                    SearchRank::Name
+                } else if terms.iter().any(|term| description.contains(term.as_str())) {
+                    SearchRank::Description
+                } else {
+                    SearchRank::Other
+                })
+            }
+            #[cfg(feature = "regex")]
+            Pattern::Regex { regex, exact } => {
+                // the regex handles case folding by itself
+                let fields = Fields::new(querier, CaseSensitivity::Sensitive)?;
+                let name = fields.name.as_ref();
+                if let Some(found) = regex.find(name) {
+                    // the leftmost-first match may be shorter than a match of the whole name
+                    return Some(if exact.is_match(name) {
+                        SearchRank::ExactName
+                    } else if found.start() == 0 {
+                        SearchRank::NamePrefix
+                    } else {
+                        SearchRank::Name
+                    });
+                }
+                if fields
+                    .description
+                    .is_some_and(|description| regex.is_match(&description))
+                {
+                    return Some(SearchRank::Description);
+                }
+                fields
+                    .others
+                    .iter()
+                    .any(|other| regex.is_match(other))
+                    .then_some(SearchRank::Other)
+            }
+        }
+    }
+}
+
+/// Build a regex which only matches whole strings.
+///
+/// The anchors surround the parsed pattern rather than its source text, which a trailing comment of
+/// a verbose pattern such as `(?x)bash # shell` would swallow.
+#[cfg(feature = "regex")]
+fn exact_regex(
+    pattern: &str,
+    case_insensitive: bool,
+) -> Result<regex_automata::meta::Regex, regex::Error> {
+    use regex_syntax::hir::{Hir, Look};
+    let hir = regex_syntax::ParserBuilder::new()
+        .case_insensitive(case_insensitive)
+        .build()
+        .parse(pattern)
+        .map_err(|error| regex::Error::Syntax(error.to_string()))?;
+    let hir = Hir::concat(vec![Hir::look(Look::Start), hir, Hir::look(Look::End)]);
+    regex_automata::meta::Regex::builder()
+        .build_from_hir(&hir)
+        .map_err(|error| match error.size_limit() {
+            Some(limit) => regex::Error::CompiledTooBig(limit),
+            None => regex::Error::Syntax(error.to_string()),
+        })
+}
+
+impl<'a, Querier: Query<'a>> QueryDatabase<'a, Querier> {
+    /// Search packages whose metadata match `query`.
+    ///
+    /// The queriers are sorted by [`SearchRank`], then by package name.
+    pub fn search(&self, query: &SearchQuery) -> Vec<&Querier> {
+        let mut hits: Vec<_> = self
+            .entries()
+            .map(|entry| entry.into_tuple())
+            .filter_map(|(name, querier)| Some((query.rank(querier)?, name.as_str(), querier)))
+            .collect();
+        hits.sort_by_key(|(rank, name, _)| (*rank, *name));
+        hits.into_iter().map(|(_, _, querier)| querier).collect()
+    }
+}
+
+impl<'a, Querier: Query<'a>> MultiQueryDatabase<'a, Querier> {
+    /// Search packages whose metadata match `query`.
+    ///
+    /// The queriers are paired with their repository names and sorted by [`SearchRank`], then by
+    /// package name, then by repository name.
+    pub fn search(
+        &self,
+        query: &SearchQuery,
+    ) -> Vec<(RepositoryName<'a>, &WithParsedVersion<'a, Querier>)> {
+        let mut hits: Vec<_> = self
+            .entries()
+            .flat_map(|(name, queriers)| {
+                queriers
+                    .entries()
+                    .map(move |(repository, querier)| (name, repository, querier))
+            })
+            .filter_map(|(name, repository, querier)| {
+                Some((query.rank(querier)?, name, repository, querier))
+            })
+            .collect();
+        hits.sort_by_key(|(rank, name, repository, _)| (*rank, name.as_str(), repository.as_str()));
+        hits.into_iter()
+            .map(|(_, _, repository, querier)| (repository, querier))
+            .collect()
+    }
+}
@@ -0,0 +1,123 @@
+//! Serialization of queriers and databases with [`serde`].
+//!
+//! Queriers are serialized as maps from pacman field names (such as `NAME`, `VERSION`, and `DEPENDS`)
+//! to their values in the order of [`DESC_FIELDS`]. Fields which hold lists of entries are serialized
+//! as sequences of strings, the others as strings. Absent and empty fields are omitted. Databases are
+//! serialized as maps sorted by package names and repository names, so the output is stable.
+
+use crate::{
+    MultiQueryDatabase, QueryDatabase,
+    multi::{MultiQuerier, WithParsedVersion, WithRepositoryName, WithRepositoryNameUtils},
+    raw::RawFields,
+    write::DESC_FIELDS,
+};
+use arch_pkg_text::desc::{FieldName, Query};
+use serde::{Serialize, Serializer, ser::SerializeMap};
+
+/// Wrapper which serializes any [querier](Query) as a map of pacman field names to values.
+#[derive(Debug, Clone, Copy)]
+pub struct SerializeQuerier<Querier>(pub Querier);
+
+/// Whether a field holds a list of entries, one per line.
+fn is_list(field: FieldName) -> bool {
+    matches!(
+        field,
+        FieldName::Groups
+            | FieldName::License
+            | FieldName::Architecture
+            | FieldName::Dependencies
+            | FieldName::MakeDependencies
+            | FieldName::CheckDependencies
+            | FieldName::OptionalDependencies
+            | FieldName::Provides
+            | FieldName::Conflicts
+            | FieldName::Replaces
+    )
+}
+
+/// Write the fields of a querier into a map which is being serialized.
+fn serialize_fields<'a, Map: SerializeMap>(
+    map: &mut Map,
+    querier: &impl Query<'a>,
+) -> Result<(), Map::Error> {
+    for (field, value) in DESC_FIELDS
+        .into_iter()
+        .zip(RawFields::new(querier).values())
+    {
+        let Some(value) = value.filter(|value| !value.is_empty()) else {
+            continue;
+        };
+        let key: &'static str = field.into();
+        if is_list(field) {
+            let entries: Vec<_> = value
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty())
+                .collect();
+            map.serialize_entry(key, &entries)?;
+        } else {
+            map.serialize_entry(key, value)?;
+        }
+    }
+    Ok(())
+}
+
+impl<'a, Querier: Query<'a>> Serialize for SerializeQuerier<Querier> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(None)?;
+        serialize_fields(&mut map, &self.0)?;
+        map.end()
+    }
+}
+
+impl<'a, Querier: Query<'a>> Serialize for WithParsedVersion<'_, Querier> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        SerializeQuerier(self).serialize(serializer)
+    }
+}
+
+/// The repository name is serialized as an additional `REPOSITORY` field in front of the other fields.
+impl<'a, Querier: Query<'a>> Serialize for WithRepositoryName<'_, Querier> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry("REPOSITORY", self.repository_name().as_str())?;
+        serialize_fields(&mut map, self)?;
+        map.end()
+    }
+}
+
+impl<'a, Querier: Query<'a>> Serialize for QueryDatabase<'a, Querier> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut entries: Vec<_> = self.entries().map(|entry| entry.into_tuple()).collect();
+        entries.sort_by_key(|(name, _)| *name);
+        let mut map = serializer.serialize_map(Some(entries.len()))?;
+        for (name, querier) in entries {
+            map.serialize_entry(name.as_str(), &SerializeQuerier(querier))?;
+        }
+        map.end()
+    }
+}
+
+impl<'a, Querier: Query<'a>> Serialize for MultiQuerier<'a, Querier> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut entries: Vec<_> = self.entries().collect();
+        entries.sort_by_key(|(repository, _)| repository.as_str());
+        let mut map = serializer.serialize_map(Some(entries.len()))?;
+        for (repository, querier) in entries {
+            map.serialize_entry(repository.as_str(), querier)?;
+        }
+        map.end()
+    }
+}
+
+impl<'a, Querier: Query<'a>> Serialize for MultiQueryDatabase<'a, Querier> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut entries: Vec<_> = self.entries().collect();
+        entries.sort_by_key(|(name, _)| *name);
+        let mut map = serializer.serialize_map(Some(entries.len()))?;
+        for (name, queriers) in entries {
+            map.serialize_entry(name.as_str(), queriers)?;
+        }
+        map.end()
+    }
+}
@@ -7,10 +7,12 @@ mod iter;
 mod misc;
 mod new;
 mod providers;
+mod satisfiers;
 
 pub use insert::{InsertError, InsertNewerError, InsertNewerReturn};
 pub use iter::{Entries, EntriesMut, Entry, Names, OwnedEntries, Queriers, QueriersMut};
 pub use providers::{AlternativeProviders, AlternativeProvidersMut};
+pub use satisfiers::{Satisfiers, SatisfiersMut};
 
 use arch_pkg_text::desc::{EagerQuerier, MemoQuerier};
 use std::collections::HashMap;
@@ -16,7 +16,7 @@ pub struct Entry<'a, Querier> {
 
 impl<'a, Querier> Entry<'a, Querier> {
     /// Create an entry without checking whether the names match.
-    pub(super) fn new_unchecked(name: &'a str, querier: Querier) -> Self {
+    pub(crate) fn new_unchecked(name: &'a str, querier: Querier) -> Self {
         Entry {
             name: Name(name),
             querier,
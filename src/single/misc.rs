@@ -1,4 +1,5 @@
 use super::QueryDatabase;
+use arch_pkg_text::value::Name;
 
 impl<'a, Querier> QueryDatabase<'a, Querier> {
     /// The number of queriers within the database.
@@ -10,4 +11,13 @@ impl<'a, Querier> QueryDatabase<'a, Querier> {
     pub fn is_empty(&self) -> bool {
         self.internal.is_empty()
     }
+
+    /// Only keep the queriers for which `keep` returns `true`.
+    pub fn retain<Keep>(&mut self, mut keep: Keep)
+    where
+        Keep: FnMut(Name<'a>, &mut Querier) -> bool,
+    {
+        self.internal
+            .retain(|name, querier| keep(Name(name), querier))
+    }
 }
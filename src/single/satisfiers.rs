@@ -0,0 +1,99 @@
+use super::QueryDatabase;
+use crate::{
+    satisfy::package_satisfies,
+    single::{Queriers, QueriersMut},
+};
+use arch_pkg_text::{
+    desc::{Query, QueryMut},
+    value::Dependency,
+};
+use core::iter::FusedIterator;
+
+/// [Iterator] over all immutable queriers of packages which satisfy a certain [`Dependency`].
+///
+/// This iterator is created by calling [`QueryDatabase::satisfiers`].
+#[derive(Debug, Clone)]
+pub struct Satisfiers<'r, 'query, 'dependency, Querier> {
+    target: Dependency<'dependency>,
+    queriers: Queriers<'r, 'query, Querier>,
+}
+
+impl<'r, Querier: Query<'r>> Iterator for Satisfiers<'r, '_, '_, Querier> {
+    type Item = &'r Querier;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.queriers.find(|querier| {
+            package_satisfies(
+                self.target,
+                querier.name(),
+                querier.version(),
+                querier.provides(),
+            )
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (_, max) = self.queriers.size_hint();
+        (0, max)
+    }
+}
+
+impl<'r, Querier: Query<'r>> FusedIterator for Satisfiers<'r, '_, '_, Querier> {}
+
+/// [Iterator] over all mutable queriers of packages which satisfy a certain [`Dependency`].
+///
+/// This iterator is created by calling [`QueryDatabase::satisfiers_mut`].
+#[derive(Debug)]
+pub struct SatisfiersMut<'r, 'query, 'dependency, Querier> {
+    target: Dependency<'dependency>,
+    queriers: QueriersMut<'r, 'query, Querier>,
+}
+
+impl<'r, Querier: QueryMut<'r>> Iterator for SatisfiersMut<'r, '_, '_, Querier> {
+    type Item = &'r mut Querier;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.queriers.find_map(|querier| {
+            package_satisfies(
+                self.target,
+                querier.name_mut(),
+                querier.version_mut(),
+                querier.provides_mut(),
+            )
+            .then_some(querier)
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (_, max) = self.queriers.size_hint();
+        (0, max)
+    }
+}
+
+impl<'r, Querier: QueryMut<'r>> FusedIterator for SatisfiersMut<'r, '_, '_, Querier> {}
+
+impl<'query, Querier> QueryDatabase<'query, Querier> {
+    /// Get an iterator over all immutable queriers of packages which satisfy a certain [`Dependency`],
+    /// either by their own names and versions or by their [`provides`](Query::provides) arrays.
+    ///
+    /// See [`crate::satisfy`] for the rules.
+    pub fn satisfiers<'r, 'dependency>(
+        &'r self,
+        target: Dependency<'dependency>,
+    ) -> Satisfiers<'r, 'query, 'dependency, Querier> {
+        let queriers = self.queriers();
+        Satisfiers { target, queriers }
+    }
+
+    /// Get an iterator over all mutable queriers of packages which satisfy a certain [`Dependency`],
+    /// either by their own names and versions or by their [`provides`](QueryMut::provides_mut) arrays.
+    ///
+    /// See [`crate::satisfy`] for the rules.
+    pub fn satisfiers_mut<'r, 'dependency>(
+        &'r mut self,
+        target: Dependency<'dependency>,
+    ) -> SatisfiersMut<'r, 'query, 'dependency, Querier> {
+        let queriers = self.queriers_mut();
+        SatisfiersMut { target, queriers }
+    }
+}
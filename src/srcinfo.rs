@@ -0,0 +1,180 @@
+//! Descriptions of split packages built from `.SRCINFO` files.
+
+use crate::{MultiTextCollection, PackageDesc, TextCollection, value::RepositoryName};
+use arch_pkg_text::value::{Architecture, Name};
+
+pub use arch_pkg_text::{
+    parse::{ParsedSrcinfo, SrcinfoParseError},
+    srcinfo::{FieldName, Query, QueryMut, Section},
+};
+
+/// Collect the values of a field of a split package.
+///
+/// Values in the section of the split package override the ones in the `pkgbase` section,
+/// and values for `architecture` (e.g. `depends_x86_64`) are appended to the generic ones,
+/// with the same kind of overriding.
+fn values<'a>(
+    srcinfo: &impl Query<'a>,
+    field: FieldName,
+    name: Name<'a>,
+    architecture: Option<Architecture<'_>>,
+) -> Vec<String> {
+    let items: Vec<_> = srcinfo.query_raw_text(field).collect();
+    let select = |suffix: Option<&str>| -> Vec<&'a str> {
+        let of_section = |section| {
+            items
+                .iter()
+                .filter(move |item| item.section == section)
+                .filter(move |item| item.architecture.map(|arch| arch.as_str()) == suffix)
+                .map(|item| item.value)
+                .collect::<Vec<_>>()
+        };
+        let derivative = of_section(Section::Derivative(name));
+        if derivative.is_empty() {
+            of_section(Section::Base)
+        } else {
+            derivative
+        }
+    };
+    let generic = select(None);
+    let specific = architecture
+        .map(|architecture| select(Some(architecture.as_str())))
+        .unwrap_or_default();
+    generic
+        .into_iter()
+        .chain(specific)
+        .map(String::from)
+        .collect()
+}
+
+/// Build the descriptions of all split packages of a `.SRCINFO`.
+///
+/// Values from the `pkgbase` section are inherited by every split package unless overridden.
+/// Architecture-specific values (e.g. `depends_x86_64`) are only included when `architecture`
+/// is specified and matches the suffix.
+///
+/// The `.SRCINFO` parser drops empty values, so a split package can't clear an inherited field.
+pub fn split_packages<'a>(
+    srcinfo: &impl Query<'a>,
+    architecture: Option<Architecture<'_>>,
+) -> Vec<PackageDesc> {
+    let base = srcinfo.base_name().map(|base| base.as_str().to_string());
+    let version = srcinfo.version().map(|version| {
+        let version = version.as_str();
+        let epoch = srcinfo.epoch().map(|epoch| epoch.as_str());
+        let release = srcinfo.release().map(|release| release.as_str());
+        match (epoch, release) {
+            (Some(epoch), Some(release)) => format!("{epoch}:{version}-{release}"),
+            (Some(epoch), None) => format!("{epoch}:{version}"),
+            (None, Some(release)) => format!("{version}-{release}"),
+            (None, None) => version.to_string(),
+        }
+    });
+
+    srcinfo
+        .derivative_names()
+        .map(|name| {
+            let list = |field| values(srcinfo, field, name, architecture);
+            let single = |field| list(field).into_iter().next();
+            PackageDesc {
+                name: Some(name.as_str().to_string()),
+                base: base.clone(),
+                version: version.clone(),
+                description: single(FieldName::Description),
+                url: single(FieldName::Url),
+                groups: list(FieldName::Groups),
+                license: list(FieldName::License),
+                architecture: list(FieldName::Architecture),
+                dependencies: list(FieldName::Dependencies),
+                make_dependencies: list(FieldName::MakeDependencies),
+                check_dependencies: list(FieldName::CheckDependencies),
+                opt_dependencies: list(FieldName::OptionalDependencies),
+                provides: list(FieldName::Provides),
+                conflicts: list(FieldName::Conflicts),
+                replaces: list(FieldName::Replaces),
+                ..PackageDesc::default()
+            }
+        })
+        .collect()
+}
+
+/// Parse a `.SRCINFO` text and build the descriptions of all its split packages.
+///
+/// See [`split_packages`] for how the values are resolved.
+pub fn parse_split_packages<'a>(
+    text: &'a str,
+    architecture: Option<Architecture<'_>>,
+) -> Result<Vec<PackageDesc>, SrcinfoParseError<'a>> {
+    let srcinfo = ParsedSrcinfo::try_from(text)?;
+    Ok(split_packages(&srcinfo, architecture))
+}
+
+impl TextCollection {
+    /// Parse a `.SRCINFO` text and add the `desc` texts of its split packages to the text collection.
+    pub fn extend_from_srcinfo<'a>(
+        &mut self,
+        text: &'a str,
+        architecture: Option<Architecture<'_>>,
+    ) -> Result<(), SrcinfoParseError<'a>> {
+        let texts = parse_split_packages(text, architecture)?
+            .into_iter()
+            .map(|desc| desc.to_string().into());
+        self.extend(texts);
+        Ok(())
+    }
+
+    /// Parse a `.SRCINFO` text and add the `desc` texts of its split packages to the text collection.
+    pub fn add_srcinfo<'a>(
+        mut self,
+        text: &'a str,
+        architecture: Option<Architecture<'_>>,
+    ) -> Result<Self, SrcinfoParseError<'a>> {
+        self.extend_from_srcinfo(text, architecture)?;
+        Ok(self)
+    }
+
+    /// Parse a `.SRCINFO` text and add the `desc` texts of its split packages to the text collection.
+    pub fn from_srcinfo<'a>(
+        text: &'a str,
+        architecture: Option<Architecture<'_>>,
+    ) -> Result<Self, SrcinfoParseError<'a>> {
+        TextCollection::new().add_srcinfo(text, architecture)
+    }
+}
+
+impl<'a> MultiTextCollection<'a> {
+    /// Parse a `.SRCINFO` text and add the `desc` texts of its split packages to the multi-collection
+    /// under a synthetic repository name such as `aur` or `local-src`.
+    pub fn extend_from_srcinfo<'text>(
+        &mut self,
+        repository: RepositoryName<'a>,
+        text: &'text str,
+        architecture: Option<Architecture<'_>>,
+    ) -> Result<(), SrcinfoParseError<'text>> {
+        let collection = TextCollection::from_srcinfo(text, architecture)?;
+        self.insert(repository, collection);
+        Ok(())
+    }
+
+    /// Parse a `.SRCINFO` text and add the `desc` texts of its split packages to the multi-collection
+    /// under a synthetic repository name such as `aur` or `local-src`.
+    pub fn add_srcinfo<'text>(
+        mut self,
+        repository: RepositoryName<'a>,
+        text: &'text str,
+        architecture: Option<Architecture<'_>>,
+    ) -> Result<Self, SrcinfoParseError<'text>> {
+        self.extend_from_srcinfo(repository, text, architecture)?;
+        Ok(self)
+    }
+
+    /// Parse a `.SRCINFO` text and add the `desc` texts of its split packages to the multi-collection
+    /// under a synthetic repository name such as `aur` or `local-src`.
+    pub fn from_srcinfo<'text>(
+        repository: RepositoryName<'a>,
+        text: &'text str,
+        architecture: Option<Architecture<'_>>,
+    ) -> Result<Self, SrcinfoParseError<'text>> {
+        MultiTextCollection::with_capacity(1).add_srcinfo(repository, text, architecture)
+    }
+}
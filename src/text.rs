@@ -1,12 +1,17 @@
 //! Collections of `desc` texts from which queriers may access data.
 
+mod borrowed;
+mod files;
 mod item;
 mod multi;
 mod single;
 
 pub mod archive;
 pub mod local;
+pub mod sync;
 
+pub use borrowed::{BorrowedTextCollection, LoadBorrowedTarError};
+pub use files::{FilesCollection, FilesText};
 pub use item::Text;
 pub use multi::MultiTextCollection;
 pub use single::TextCollection;
@@ -19,5 +24,8 @@ pub mod iter {
 }
 
 pub mod parse {
-    pub use super::{multi::MultiTextCollectionParseError, single::TextCollectionParseError};
+    pub use super::{
+        files::FilesCollectionParseError, multi::MultiTextCollectionParseError,
+        single::TextCollectionParseError,
+    };
 }
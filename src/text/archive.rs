@@ -1,24 +1,39 @@
+mod bz2;
 mod gz;
+mod lz4;
 mod mime;
+mod peek;
+mod tagged;
 mod tar;
 mod uncompressed;
 mod xz;
+mod zst;
 
+pub use bz2::LoadBz2Error;
 pub use gz::LoadGzError;
-pub use lzma_rs::error::Error as LzmaError;
+pub use lz4::LoadLz4Error;
 pub use tar::LoadTarError;
 pub use uncompressed::LoadUncompressedArchiveError;
 pub use xz::LoadXzError;
+pub use zst::LoadZstError;
 
 use super::{MultiTextCollection, TextCollection};
 use crate::value::RepositoryName;
 use derive_more::{Display, Error};
-use mime::SupportedCompressedArchiveType;
-use std::io;
+use mime::{SupportedCompressedArchiveType, SupportedUncompressedArchiveType};
+use peek::Peeked;
+use pipe_trait::Pipe;
+use std::io::{self, Read};
+use tagged::{Format, Tagged};
+
+pub(crate) use tagged::untag;
+pub(crate) use tar::traverse_tar;
 
 /// Error when trying to load data from an archive.
 #[derive(Debug, Display, Error)]
 pub enum LoadArchiveError {
+    #[display("Failed to read the archive: {_0}")]
+    Read(io::Error),
     #[display("Cannot detect mime type")]
     GetMime,
     #[display("Mime type not supported: {_0}")]
@@ -27,21 +42,97 @@ pub enum LoadArchiveError {
     #[display("Failed to load the gzip archive: {_0}")]
     Gzip(io::Error),
     #[display("Failed to load the xz archive: {_0}")]
-    Xz(LzmaError),
+    Xz(io::Error),
+    #[display("Failed to load the zstd archive: {_0}")]
+    Zstd(io::Error),
+    #[display("Failed to load the bzip2 archive: {_0}")]
+    Bzip2(io::Error),
+    #[display("Failed to load the lz4 archive: {_0}")]
+    Lz4(io::Error),
     #[display("Failed to extract data from the internal archive: {_0}")]
     InternalArchive(LoadUncompressedArchiveError),
 }
 
+impl LoadArchiveError {
+    /// Create the error of the decoder of a compression format.
+    pub(crate) fn decoder(format: Format, error: io::Error) -> Self {
+        match format {
+            Format::Gzip => LoadArchiveError::Gzip(error),
+            Format::Xz => LoadArchiveError::Xz(error),
+            Format::Zstd => LoadArchiveError::Zstd(error),
+            Format::Bzip2 => LoadArchiveError::Bzip2(error),
+            Format::Lz4 => LoadArchiveError::Lz4(error),
+        }
+    }
+}
+
+/// Tag the errors of a streaming decoder with its compression format,
+/// and check that its uncompressed content is a tar archive.
+fn internal_tar<Decoder: Read>(
+    format: Format,
+    decoder: io::Result<Decoder>,
+) -> Result<Peeked<Tagged<Decoder>>, LoadArchiveError> {
+    let bytes = decoder
+        .map(|decoder| Tagged::new(format, decoder))
+        .and_then(Peeked::new)
+        .map_err(tagged::strip)
+        .map_err(|error| LoadArchiveError::decoder(format, error))?;
+    match SupportedUncompressedArchiveType::check(bytes.head()) {
+        Ok(SupportedUncompressedArchiveType::Tar) => Ok(bytes),
+        Err(Some(mime)) => Err(LoadArchiveError::InternalArchive(
+            LoadUncompressedArchiveError::UnsupportedMimeType(mime),
+        )),
+        Err(None) => Err(LoadArchiveError::InternalArchive(
+            LoadUncompressedArchiveError::GetMime,
+        )),
+    }
+}
+
+/// Detect the mime type of an archive and create a streaming reader of the uncompressed archive inside.
+///
+/// Errors of the decoder are tagged, so [`LoadTarError`] converts them back into the variants of their formats.
+pub(crate) fn decompress<'r, Bytes: Read + 'r>(
+    bytes: Bytes,
+) -> Result<Box<dyn Read + 'r>, LoadArchiveError> {
+    let bytes = Peeked::new(bytes).map_err(LoadArchiveError::Read)?;
+    Ok(match SupportedCompressedArchiveType::check(bytes.head()) {
+        Ok(SupportedCompressedArchiveType::Tar) => Box::new(bytes),
+        Ok(SupportedCompressedArchiveType::Gzip) => {
+            internal_tar(Format::Gzip, gz::decoder(bytes))?.pipe(Box::new)
+        }
+        Ok(SupportedCompressedArchiveType::Xz) => {
+            internal_tar(Format::Xz, xz::decoder(bytes))?.pipe(Box::new)
+        }
+        Ok(SupportedCompressedArchiveType::Zstd) => {
+            internal_tar(Format::Zstd, zst::decoder(bytes))?.pipe(Box::new)
+        }
+        Ok(SupportedCompressedArchiveType::Bzip2) => {
+            internal_tar(Format::Bzip2, bz2::decoder(bytes))?.pipe(Box::new)
+        }
+        Ok(SupportedCompressedArchiveType::Lz4) => {
+            internal_tar(Format::Lz4, lz4::decoder(bytes))?.pipe(Box::new)
+        }
+        Err(Some(mime)) => return Err(LoadArchiveError::UnsupportedMimeType(mime)),
+        Err(None) => return Err(LoadArchiveError::GetMime),
+    })
+}
+
 impl TextCollection {
     /// Detect mime type of an archive, extract it, and add contents from `desc` files to the text collection.
     pub fn extend_from_archive(&mut self, bytes: &[u8]) -> Result<(), LoadArchiveError> {
-        match SupportedCompressedArchiveType::check(bytes) {
-            Ok(SupportedCompressedArchiveType::Tar) => self.extend_from_tar(bytes)?,
-            Ok(SupportedCompressedArchiveType::Gzip) => self.extend_from_gz(bytes)?,
-            Ok(SupportedCompressedArchiveType::Xz) => self.extend_from_xz(bytes)?,
-            Err(Some(mime)) => return Err(LoadArchiveError::UnsupportedMimeType(mime)),
-            Err(None) => return Err(LoadArchiveError::GetMime),
-        }
+        self.extend_from_archive_reader(bytes)
+    }
+
+    /// Detect mime type of an archive from its leading bytes, extract it as a stream,
+    /// and add contents from `desc` files to the text collection.
+    ///
+    /// Unlike [`TextCollection::extend_from_archive`], the archive doesn't need to be loaded into memory beforehand.
+    pub fn extend_from_archive_reader<Bytes: Read>(
+        &mut self,
+        bytes: Bytes,
+    ) -> Result<(), LoadArchiveError> {
+        let tar = decompress(bytes)?;
+        self.extend_from_tar(tar)?;
         Ok(())
     }
 
@@ -55,6 +146,22 @@ impl TextCollection {
     pub fn from_archive(bytes: &[u8]) -> Result<Self, LoadArchiveError> {
         TextCollection::new().add_archive(bytes)
     }
+
+    /// Detect mime type of an archive from its leading bytes, extract it as a stream,
+    /// and add contents from `desc` files to the text collection.
+    pub fn add_archive_reader<Bytes: Read>(
+        mut self,
+        bytes: Bytes,
+    ) -> Result<Self, LoadArchiveError> {
+        self.extend_from_archive_reader(bytes)?;
+        Ok(self)
+    }
+
+    /// Detect mime type of an archive from its leading bytes, extract it as a stream,
+    /// and add contents from `desc` files to the text collection.
+    pub fn from_archive_reader<Bytes: Read>(bytes: Bytes) -> Result<Self, LoadArchiveError> {
+        TextCollection::new().add_archive_reader(bytes)
+    }
 }
 
 impl<'a> MultiTextCollection<'a> {
@@ -86,4 +193,36 @@ impl<'a> MultiTextCollection<'a> {
     ) -> Result<Self, LoadArchiveError> {
         MultiTextCollection::with_capacity(1).add_archive(repository, bytes)
     }
+
+    /// Detect mime type of an archive from its leading bytes, extract it as a stream,
+    /// and add contents from `desc` files to the multi-collection.
+    pub fn extend_from_archive_reader<Bytes: Read>(
+        &mut self,
+        repository: RepositoryName<'a>,
+        bytes: Bytes,
+    ) -> Result<(), LoadArchiveError> {
+        let collection = TextCollection::from_archive_reader(bytes)?;
+        self.insert(repository, collection);
+        Ok(())
+    }
+
+    /// Detect mime type of an archive from its leading bytes, extract it as a stream,
+    /// and add contents from `desc` files to the multi-collection.
+    pub fn add_archive_reader<Bytes: Read>(
+        mut self,
+        repository: RepositoryName<'a>,
+        bytes: Bytes,
+    ) -> Result<Self, LoadArchiveError> {
+        self.extend_from_archive_reader(repository, bytes)?;
+        Ok(self)
+    }
+
+    /// Detect mime type of an archive from its leading bytes, extract it as a stream,
+    /// and add contents from `desc` files to the multi-collection.
+    pub fn from_archive_reader<Bytes: Read>(
+        repository: RepositoryName<'a>,
+        bytes: Bytes,
+    ) -> Result<Self, LoadArchiveError> {
+        MultiTextCollection::with_capacity(1).add_archive_reader(repository, bytes)
+    }
 }
@@ -0,0 +1,92 @@
+use super::{
+    LoadArchiveError, LoadUncompressedArchiveError,
+    peek::Peeked,
+    tagged::{self, Format, Tagged},
+};
+use crate::{MultiTextCollection, TextCollection, value::RepositoryName};
+use bzip2::read::MultiBzDecoder;
+use derive_more::{Display, Error};
+use pipe_trait::Pipe;
+use std::io::{self, Read};
+
+/// Error when trying to load data from a bzip2 archive.
+#[derive(Debug, Display, Error)]
+pub enum LoadBz2Error {
+    #[display("Failed to load the bzip2 archive: {_0}")]
+    Bzip2(io::Error),
+    #[display("Failed to extract data from the internal archive: {_0}")]
+    InternalArchive(LoadUncompressedArchiveError),
+}
+
+/// Create a streaming decoder of a bzip2 archive.
+pub(super) fn decoder<Bytes: Read>(bytes: Bytes) -> io::Result<MultiBzDecoder<Bytes>> {
+    Ok(MultiBzDecoder::new(bytes))
+}
+
+impl TextCollection {
+    /// Extract a bzip2 archive and add contents from its `desc` files to the text collection.
+    pub fn extend_from_bz2<Bytes: Read>(&mut self, bytes: Bytes) -> Result<(), LoadBz2Error> {
+        let tar = bytes
+            .pipe(decoder)
+            .map(|decoder| Tagged::new(Format::Bzip2, decoder))
+            .and_then(Peeked::new)
+            .map_err(tagged::strip)
+            .map_err(LoadBz2Error::Bzip2)?;
+        self.extend_from_uncompressed_archive(tar)
+            .map_err(|error| match error.untag() {
+                Ok((_, error)) => LoadBz2Error::Bzip2(error),
+                Err(error) => LoadBz2Error::InternalArchive(error),
+            })
+    }
+
+    /// Extract a bzip2 archive and add contents from its `desc` files to the text collection.
+    pub fn add_bz2<Bytes: Read>(mut self, bytes: Bytes) -> Result<Self, LoadBz2Error> {
+        self.extend_from_bz2(bytes)?;
+        Ok(self)
+    }
+
+    /// Extract a bzip2 archive and add contents from its `desc` files to the text collection.
+    pub fn from_bz2<Bytes: Read>(bytes: Bytes) -> Result<Self, LoadBz2Error> {
+        TextCollection::new().add_bz2(bytes)
+    }
+}
+
+impl<'a> MultiTextCollection<'a> {
+    /// Extract a bzip2 archive and add contents from its `desc` files to the multi-collection.
+    pub fn extend_from_bz2<Bytes: Read>(
+        &mut self,
+        repository: RepositoryName<'a>,
+        bytes: Bytes,
+    ) -> Result<(), LoadArchiveError> {
+        let collection = TextCollection::from_bz2(bytes)?;
+        self.insert(repository, collection);
+        Ok(())
+    }
+
+    /// Extract a bzip2 archive and add contents from its `desc` files to the multi-collection.
+    pub fn add_bz2<Bytes: Read>(
+        mut self,
+        repository: RepositoryName<'a>,
+        bytes: Bytes,
+    ) -> Result<Self, LoadArchiveError> {
+        self.extend_from_bz2(repository, bytes)?;
+        Ok(self)
+    }
+
+    /// Extract a bzip2 archive and add contents from its `desc` files to the multi-collection.
+    pub fn from_bz2<Bytes: Read>(
+        repository: RepositoryName<'a>,
+        bytes: Bytes,
+    ) -> Result<Self, LoadArchiveError> {
+        MultiTextCollection::with_capacity(1).add_bz2(repository, bytes)
+    }
+}
+
+impl From<LoadBz2Error> for LoadArchiveError {
+    fn from(value: LoadBz2Error) -> Self {
+        match value {
+            LoadBz2Error::Bzip2(error) => LoadArchiveError::Bzip2(error),
+            LoadBz2Error::InternalArchive(error) => LoadArchiveError::InternalArchive(error),
+        }
+    }
+}
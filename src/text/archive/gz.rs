@@ -1,4 +1,8 @@
-use super::{LoadArchiveError, LoadUncompressedArchiveError};
+use super::{
+    LoadArchiveError, LoadUncompressedArchiveError,
+    peek::Peeked,
+    tagged::{self, Format, Tagged},
+};
 use crate::{MultiTextCollection, TextCollection, value::RepositoryName};
 use derive_more::{Display, Error};
 use libflate::gzip::Decoder;
@@ -14,14 +18,25 @@ pub enum LoadGzError {
     InternalArchive(LoadUncompressedArchiveError),
 }
 
+/// Create a streaming decoder of a gzipped archive.
+pub(super) fn decoder<Bytes: Read>(bytes: Bytes) -> io::Result<Decoder<Bytes>> {
+    Decoder::new(bytes)
+}
+
 impl TextCollection {
     /// Extract a gzipped archive and add contents from its `desc` files to the text collection.
     pub fn extend_from_gz<Bytes: Read>(&mut self, bytes: Bytes) -> Result<(), LoadGzError> {
-        let mut decoder = bytes.pipe(Decoder::new).map_err(LoadGzError::Gzip)?;
-        let mut tar = Vec::new();
-        decoder.read_to_end(&mut tar).map_err(LoadGzError::Gzip)?;
-        self.extend_from_uncompressed_archive(&tar)
-            .map_err(LoadGzError::InternalArchive)
+        let tar = bytes
+            .pipe(decoder)
+            .map(|decoder| Tagged::new(Format::Gzip, decoder))
+            .and_then(Peeked::new)
+            .map_err(tagged::strip)
+            .map_err(LoadGzError::Gzip)?;
+        self.extend_from_uncompressed_archive(tar)
+            .map_err(|error| match error.untag() {
+                Ok((_, error)) => LoadGzError::Gzip(error),
+                Err(error) => LoadGzError::InternalArchive(error),
+            })
     }
 
     /// Extract a gzipped archive and add contents from its `desc` files to the text collection.
@@ -0,0 +1,92 @@
+use super::{
+    LoadArchiveError, LoadUncompressedArchiveError,
+    peek::Peeked,
+    tagged::{self, Format, Tagged},
+};
+use crate::{MultiTextCollection, TextCollection, value::RepositoryName};
+use derive_more::{Display, Error};
+use lz4_flex::frame::FrameDecoder;
+use pipe_trait::Pipe;
+use std::io::{self, Read};
+
+/// Error when trying to load data from an lz4 archive.
+#[derive(Debug, Display, Error)]
+pub enum LoadLz4Error {
+    #[display("Failed to load the lz4 archive: {_0}")]
+    Lz4(io::Error),
+    #[display("Failed to extract data from the internal archive: {_0}")]
+    InternalArchive(LoadUncompressedArchiveError),
+}
+
+/// Create a streaming decoder of an lz4 archive.
+pub(super) fn decoder<Bytes: Read>(bytes: Bytes) -> io::Result<FrameDecoder<Bytes>> {
+    Ok(FrameDecoder::new(bytes))
+}
+
+impl TextCollection {
+    /// Extract an lz4 archive and add contents from its `desc` files to the text collection.
+    pub fn extend_from_lz4<Bytes: Read>(&mut self, bytes: Bytes) -> Result<(), LoadLz4Error> {
+        let tar = bytes
+            .pipe(decoder)
+            .map(|decoder| Tagged::new(Format::Lz4, decoder))
+            .and_then(Peeked::new)
+            .map_err(tagged::strip)
+            .map_err(LoadLz4Error::Lz4)?;
+        self.extend_from_uncompressed_archive(tar)
+            .map_err(|error| match error.untag() {
+                Ok((_, error)) => LoadLz4Error::Lz4(error),
+                Err(error) => LoadLz4Error::InternalArchive(error),
+            })
+    }
+
+    /// Extract an lz4 archive and add contents from its `desc` files to the text collection.
+    pub fn add_lz4<Bytes: Read>(mut self, bytes: Bytes) -> Result<Self, LoadLz4Error> {
+        self.extend_from_lz4(bytes)?;
+        Ok(self)
+    }
+
+    /// Extract an lz4 archive and add contents from its `desc` files to the text collection.
+    pub fn from_lz4<Bytes: Read>(bytes: Bytes) -> Result<Self, LoadLz4Error> {
+        TextCollection::new().add_lz4(bytes)
+    }
+}
+
+impl<'a> MultiTextCollection<'a> {
+    /// Extract an lz4 archive and add contents from its `desc` files to the multi-collection.
+    pub fn extend_from_lz4<Bytes: Read>(
+        &mut self,
+        repository: RepositoryName<'a>,
+        bytes: Bytes,
+    ) -> Result<(), LoadArchiveError> {
+        let collection = TextCollection::from_lz4(bytes)?;
+        self.insert(repository, collection);
+        Ok(())
+    }
+
+    /// Extract an lz4 archive and add contents from its `desc` files to the multi-collection.
+    pub fn add_lz4<Bytes: Read>(
+        mut self,
+        repository: RepositoryName<'a>,
+        bytes: Bytes,
+    ) -> Result<Self, LoadArchiveError> {
+        self.extend_from_lz4(repository, bytes)?;
+        Ok(self)
+    }
+
+    /// Extract an lz4 archive and add contents from its `desc` files to the multi-collection.
+    pub fn from_lz4<Bytes: Read>(
+        repository: RepositoryName<'a>,
+        bytes: Bytes,
+    ) -> Result<Self, LoadArchiveError> {
+        MultiTextCollection::with_capacity(1).add_lz4(repository, bytes)
+    }
+}
+
+impl From<LoadLz4Error> for LoadArchiveError {
+    fn from(value: LoadLz4Error) -> Self {
+        match value {
+            LoadLz4Error::Lz4(error) => LoadArchiveError::Lz4(error),
+            LoadLz4Error::InternalArchive(error) => LoadArchiveError::InternalArchive(error),
+        }
+    }
+}
@@ -27,6 +27,12 @@ pub enum SupportedCompressedArchiveType {
     Gzip,
     #[strum(serialize = "application/x-xz")]
     Xz,
+    #[strum(serialize = "application/zstd")]
+    Zstd,
+    #[strum(serialize = "application/x-bzip2")]
+    Bzip2,
+    #[strum(serialize = "application/x-lz4")]
+    Lz4,
 }
 
 impl SupportedCompressedArchiveType {
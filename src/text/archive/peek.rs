@@ -0,0 +1,42 @@
+use std::io::{self, Read};
+
+/// Number of leading bytes to read ahead for mime type detection.
+const PEEK_LEN: usize = 8192;
+
+/// Reader whose leading bytes have been read ahead so that its mime type can be detected without consuming it.
+pub(crate) struct Peeked<Bytes> {
+    head: Vec<u8>,
+    position: usize,
+    rest: Bytes,
+}
+
+impl<Bytes: Read> Peeked<Bytes> {
+    /// Read ahead the leading bytes of a reader.
+    pub(crate) fn new(mut rest: Bytes) -> io::Result<Self> {
+        let mut head = Vec::with_capacity(PEEK_LEN);
+        (&mut rest).take(PEEK_LEN as u64).read_to_end(&mut head)?;
+        Ok(Peeked {
+            head,
+            position: 0,
+            rest,
+        })
+    }
+
+    /// The leading bytes which have been read ahead.
+    pub(crate) fn head(&self) -> &[u8] {
+        &self.head
+    }
+}
+
+impl<Bytes: Read> Read for Peeked<Bytes> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let remaining = &self.head[self.position..];
+        if remaining.is_empty() {
+            return self.rest.read(buf);
+        }
+        let len = remaining.len().min(buf.len());
+        buf[..len].copy_from_slice(&remaining[..len]);
+        self.position += len;
+        Ok(len)
+    }
+}
@@ -0,0 +1,69 @@
+use derive_more::{Display, Error};
+use std::io::{self, Read};
+
+/// Compression format of a [`Tagged`] decoder.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Format {
+    Gzip,
+    Xz,
+    Zstd,
+    Bzip2,
+    Lz4,
+}
+
+/// Error of a decoder, tagged with its compression format.
+#[derive(Debug, Display, Error)]
+#[display("{error}")]
+struct TaggedError {
+    #[error(not(source))]
+    format: Format,
+    error: io::Error,
+}
+
+/// Streaming decoder whose own errors are tagged with its compression format.
+///
+/// The tar reader passes the errors of the underlying reader through, so the errors of the decoder
+/// can be told apart from the errors of the tar archive with [`untag`], wherever the corruption is.
+pub(crate) struct Tagged<Decoder> {
+    format: Format,
+    decoder: Decoder,
+}
+
+impl<Decoder: Read> Tagged<Decoder> {
+    /// Tag the errors of a decoder with a compression format.
+    pub(crate) fn new(format: Format, decoder: Decoder) -> Self {
+        Tagged { format, decoder }
+    }
+}
+
+impl<Decoder: Read> Read for Tagged<Decoder> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let format = self.format;
+        self.decoder
+            .read(buf)
+            .map_err(|error| io::Error::new(error.kind(), TaggedError { format, error }))
+    }
+}
+
+/// Get the original error of a decoder from an error tagged by [`Tagged`], or the error itself if it wasn't tagged.
+pub(crate) fn strip(error: io::Error) -> io::Error {
+    untag(error).map_or_else(|error| error, |(_, error)| error)
+}
+
+/// Get the compression format and the original error of a decoder from an error tagged by [`Tagged`].
+///
+/// Errors which weren't tagged are returned as is.
+pub(crate) fn untag(error: io::Error) -> Result<(Format, io::Error), io::Error> {
+    if !error
+        .get_ref()
+        .is_some_and(|inner| inner.is::<TaggedError>())
+    {
+        return Err(error);
+    }
+    let TaggedError { format, error } = *error
+        .into_inner()
+        .expect("the error has an inner error")
+        .downcast::<TaggedError>()
+        .expect("the inner error is a tagged error");
+    Ok((format, error))
+}
@@ -1,10 +1,14 @@
-use super::{LoadArchiveError, LoadUncompressedArchiveError};
+use super::{
+    LoadArchiveError, LoadUncompressedArchiveError,
+    tagged::{self, Format},
+};
 use crate::{MultiTextCollection, TextCollection, value::RepositoryName};
 use derive_more::{Display, Error};
 use pipe_trait::Pipe;
 use std::{
     ffi::OsStr,
     io::{self, Read},
+    path::{Path, PathBuf},
 };
 
 /// Error when trying to load data from a tar archive.
@@ -12,31 +16,56 @@ use std::{
 #[display("Failed to read the tar archive: {_0}")]
 pub struct LoadTarError(io::Error);
 
-impl TextCollection {
-    /// Traverse a tar archive and add contents from `desc` files to the text collection.
-    pub fn extend_from_tar<Bytes: Read>(&mut self, bytes: Bytes) -> Result<(), LoadTarError> {
-        let mut tar = tar::Archive::new(bytes);
-        let entries = tar.entries().map_err(LoadTarError)?;
+impl LoadTarError {
+    /// Get the compression format and the original error if the error came from a decoder.
+    pub(super) fn untag(self) -> Result<(Format, io::Error), Self> {
+        tagged::untag(self.0).map_err(LoadTarError)
+    }
+}
 
-        for entry in entries {
-            let mut entry = entry.map_err(LoadTarError)?;
-            let path = entry.path().map_err(LoadTarError)?;
-            let file_name = path.file_name().and_then(OsStr::to_str);
-            if file_name != Some("desc") {
-                continue;
-            }
-            let mut text = entry
-                .header()
-                .size()
-                .unwrap_or(0)
-                .pipe(usize::try_from)
-                .unwrap_or(0)
-                .pipe(String::with_capacity);
-            entry.read_to_string(&mut text).map_err(LoadTarError)?;
-            self.insert(text.into());
+/// Traverse a tar archive and read the entries whose paths are accepted by `select` as texts.
+pub(crate) fn traverse_tar<Bytes, Select, Handle>(
+    bytes: Bytes,
+    mut select: Select,
+    mut handle: Handle,
+) -> Result<(), LoadTarError>
+where
+    Bytes: Read,
+    Select: FnMut(&Path) -> bool,
+    Handle: FnMut(PathBuf, String),
+{
+    let mut tar = tar::Archive::new(bytes);
+    let entries = tar.entries().map_err(LoadTarError)?;
+
+    for entry in entries {
+        let mut entry = entry.map_err(LoadTarError)?;
+        let path = entry.path().map_err(LoadTarError)?;
+        if !select(&path) {
+            continue;
         }
+        let path = path.into_owned();
+        let mut text = entry
+            .header()
+            .size()
+            .unwrap_or(0)
+            .pipe(usize::try_from)
+            .unwrap_or(0)
+            .pipe(String::with_capacity);
+        entry.read_to_string(&mut text).map_err(LoadTarError)?;
+        handle(path, text);
+    }
 
-        Ok(())
+    Ok(())
+}
+
+impl TextCollection {
+    /// Traverse a tar archive and add contents from `desc` files to the text collection.
+    pub fn extend_from_tar<Bytes: Read>(&mut self, bytes: Bytes) -> Result<(), LoadTarError> {
+        traverse_tar(
+            bytes,
+            |path| path.file_name() == Some(OsStr::new("desc")),
+            |_, text| self.insert(text.into()),
+        )
     }
 
     /// Traverse a tar archive and add contents from `desc` files to the text collection.
@@ -88,8 +117,12 @@ impl From<LoadTarError> for LoadUncompressedArchiveError {
     }
 }
 
+/// Errors of a decoder underneath the tar archive are converted into the variants of their formats.
 impl From<LoadTarError> for LoadArchiveError {
     fn from(value: LoadTarError) -> Self {
-        LoadArchiveError::Tar(value)
+        match value.untag() {
+            Ok((format, error)) => LoadArchiveError::decoder(format, error),
+            Err(value) => LoadArchiveError::Tar(value),
+        }
     }
 }
@@ -1,6 +1,9 @@
-use super::{mime::SupportedUncompressedArchiveType, tar::LoadTarError};
+use super::{
+    mime::SupportedUncompressedArchiveType, peek::Peeked, tagged::Format, tar::LoadTarError,
+};
 use crate::TextCollection;
 use derive_more::{Display, Error};
+use std::io::{self, Read};
 
 /// Error when trying to load data from an uncompressed archive.
 #[derive(Debug, Display, Error)]
@@ -12,14 +15,27 @@ pub enum LoadUncompressedArchiveError {
     Tar(LoadTarError),
 }
 
+impl LoadUncompressedArchiveError {
+    /// Get the compression format and the original error if the error came from a decoder
+    /// of the compressed archive around this one.
+    pub(super) fn untag(self) -> Result<(Format, io::Error), Self> {
+        match self {
+            LoadUncompressedArchiveError::Tar(error) => {
+                error.untag().map_err(LoadUncompressedArchiveError::Tar)
+            }
+            error => Err(error),
+        }
+    }
+}
+
 impl TextCollection {
     /// Detect the mime type of an uncompressed archive, traverse it, and add contents from
     /// its `desc` files to the text collection.
-    pub(super) fn extend_from_uncompressed_archive(
+    pub(super) fn extend_from_uncompressed_archive<Bytes: Read>(
         &mut self,
-        bytes: &[u8],
+        bytes: Peeked<Bytes>,
     ) -> Result<(), LoadUncompressedArchiveError> {
-        match SupportedUncompressedArchiveType::check(bytes) {
+        match SupportedUncompressedArchiveType::check(bytes.head()) {
             Ok(SupportedUncompressedArchiveType::Tar) => self
                 .extend_from_tar(bytes)
                 .map_err(LoadUncompressedArchiveError::Tar),
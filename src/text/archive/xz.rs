@@ -1,26 +1,42 @@
-use super::{LoadArchiveError, LoadUncompressedArchiveError, LzmaError};
+use super::{
+    LoadArchiveError, LoadUncompressedArchiveError,
+    peek::Peeked,
+    tagged::{self, Format, Tagged},
+};
 use crate::{MultiTextCollection, TextCollection, value::RepositoryName};
 use derive_more::{Display, Error};
-use lzma_rs::xz_decompress;
-use std::io::{BufReader, Read};
+use lzma_rust2::XzReader;
+use pipe_trait::Pipe;
+use std::io::{self, Read};
 
 /// Error when trying to load data from an xz archive.
 #[derive(Debug, Display, Error)]
 pub enum LoadXzError {
     #[display("Failed to load the xz archive: {_0}")]
-    Xz(LzmaError),
+    Xz(io::Error),
     #[display("Failed to extract data from the internal archive: {_0}")]
     InternalArchive(LoadUncompressedArchiveError),
 }
 
+/// Create a streaming decoder of an xz archive.
+pub(super) fn decoder<Bytes: Read>(bytes: Bytes) -> io::Result<XzReader<Bytes>> {
+    Ok(XzReader::new(bytes, true))
+}
+
 impl TextCollection {
     /// Extract an xz archive and add contents from its `desc` files to the text collection.
     pub fn extend_from_xz<Bytes: Read>(&mut self, bytes: Bytes) -> Result<(), LoadXzError> {
-        let mut buf_reader = BufReader::new(bytes);
-        let mut tar = Vec::new();
-        xz_decompress(&mut buf_reader, &mut tar).map_err(LoadXzError::Xz)?;
-        self.extend_from_uncompressed_archive(&tar)
-            .map_err(LoadXzError::InternalArchive)
+        let tar = bytes
+            .pipe(decoder)
+            .map(|decoder| Tagged::new(Format::Xz, decoder))
+            .and_then(Peeked::new)
+            .map_err(tagged::strip)
+            .map_err(LoadXzError::Xz)?;
+        self.extend_from_uncompressed_archive(tar)
+            .map_err(|error| match error.untag() {
+                Ok((_, error)) => LoadXzError::Xz(error),
+                Err(error) => LoadXzError::InternalArchive(error),
+            })
     }
 
     /// Extract an xz archive and add contents from its `desc` files to the text collection.
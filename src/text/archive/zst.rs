@@ -0,0 +1,94 @@
+use super::{
+    LoadArchiveError, LoadUncompressedArchiveError,
+    peek::Peeked,
+    tagged::{self, Format, Tagged},
+};
+use crate::{MultiTextCollection, TextCollection, value::RepositoryName};
+use derive_more::{Display, Error};
+use pipe_trait::Pipe;
+use ruzstd::decoding::{FrameDecoder, StreamingDecoder};
+use std::io::{self, Read};
+
+/// Error when trying to load data from a zstd archive.
+#[derive(Debug, Display, Error)]
+pub enum LoadZstError {
+    #[display("Failed to load the zstd archive: {_0}")]
+    Zstd(io::Error),
+    #[display("Failed to extract data from the internal archive: {_0}")]
+    InternalArchive(LoadUncompressedArchiveError),
+}
+
+/// Create a streaming decoder of a zstd archive.
+pub(super) fn decoder<Bytes: Read>(
+    bytes: Bytes,
+) -> io::Result<StreamingDecoder<Bytes, FrameDecoder>> {
+    StreamingDecoder::new(bytes).map_err(io::Error::other)
+}
+
+impl TextCollection {
+    /// Extract a zstd archive and add contents from its `desc` files to the text collection.
+    pub fn extend_from_zst<Bytes: Read>(&mut self, bytes: Bytes) -> Result<(), LoadZstError> {
+        let tar = bytes
+            .pipe(decoder)
+            .map(|decoder| Tagged::new(Format::Zstd, decoder))
+            .and_then(Peeked::new)
+            .map_err(tagged::strip)
+            .map_err(LoadZstError::Zstd)?;
+        self.extend_from_uncompressed_archive(tar)
+            .map_err(|error| match error.untag() {
+                Ok((_, error)) => LoadZstError::Zstd(error),
+                Err(error) => LoadZstError::InternalArchive(error),
+            })
+    }
+
+    /// Extract a zstd archive and add contents from its `desc` files to the text collection.
+    pub fn add_zst<Bytes: Read>(mut self, bytes: Bytes) -> Result<Self, LoadZstError> {
+        self.extend_from_zst(bytes)?;
+        Ok(self)
+    }
+
+    /// Extract a zstd archive and add contents from its `desc` files to the text collection.
+    pub fn from_zst<Bytes: Read>(bytes: Bytes) -> Result<Self, LoadZstError> {
+        TextCollection::new().add_zst(bytes)
+    }
+}
+
+impl<'a> MultiTextCollection<'a> {
+    /// Extract a zstd archive and add contents from its `desc` files to the multi-collection.
+    pub fn extend_from_zst<Bytes: Read>(
+        &mut self,
+        repository: RepositoryName<'a>,
+        bytes: Bytes,
+    ) -> Result<(), LoadArchiveError> {
+        let collection = TextCollection::from_zst(bytes)?;
+        self.insert(repository, collection);
+        Ok(())
+    }
+
+    /// Extract a zstd archive and add contents from its `desc` files to the multi-collection.
+    pub fn add_zst<Bytes: Read>(
+        mut self,
+        repository: RepositoryName<'a>,
+        bytes: Bytes,
+    ) -> Result<Self, LoadArchiveError> {
+        self.extend_from_zst(repository, bytes)?;
+        Ok(self)
+    }
+
+    /// Extract a zstd archive and add contents from its `desc` files to the multi-collection.
+    pub fn from_zst<Bytes: Read>(
+        repository: RepositoryName<'a>,
+        bytes: Bytes,
+    ) -> Result<Self, LoadArchiveError> {
+        MultiTextCollection::with_capacity(1).add_zst(repository, bytes)
+    }
+}
+
+impl From<LoadZstError> for LoadArchiveError {
+    fn from(value: LoadZstError) -> Self {
+        match value {
+            LoadZstError::Zstd(error) => LoadArchiveError::Zstd(error),
+            LoadZstError::InternalArchive(error) => LoadArchiveError::InternalArchive(error),
+        }
+    }
+}
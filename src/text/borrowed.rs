@@ -0,0 +1,86 @@
+mod parse;
+mod tar;
+
+pub use tar::LoadBorrowedTarError;
+
+/// Collection of `desc` texts borrowed from a buffer owned by the caller.
+///
+/// Unlike [`TextCollection`](super::TextCollection), it doesn't copy the texts, so it can be used
+/// to parse a memory-mapped uncompressed archive without allocating per package.
+#[derive(Debug, Default, Clone)]
+pub struct BorrowedTextCollection<'a> {
+    internal: Vec<&'a str>,
+}
+
+impl<'a> BorrowedTextCollection<'a> {
+    /// Create an empty text collection.
+    pub fn new() -> Self {
+        BorrowedTextCollection::default()
+    }
+
+    /// Create an empty text collection with specified capacity.
+    pub fn with_capacity(capacity: usize) -> Self {
+        BorrowedTextCollection {
+            internal: Vec::with_capacity(capacity),
+        }
+    }
+
+    /// Add data into the text collection.
+    pub fn insert(&mut self, text: &'a str) {
+        self.internal.push(text);
+    }
+
+    /// Add data into the text collection.
+    pub fn add_item(mut self, text: &'a str) -> Self {
+        self.insert(text);
+        self
+    }
+
+    /// Shrink the capacity of the internal data.
+    pub fn shrink_to_fit(&mut self) {
+        self.internal.shrink_to_fit()
+    }
+
+    /// The number of texts within the collection.
+    pub fn len(&self) -> usize {
+        self.internal.len()
+    }
+
+    /// Whether the collection is empty.
+    pub fn is_empty(&self) -> bool {
+        self.internal.is_empty()
+    }
+
+    /// Only keep the texts for which `keep` returns `true`.
+    pub fn retain<Keep: FnMut(&'a str) -> bool>(&mut self, mut keep: Keep) {
+        self.internal.retain(|text| keep(text))
+    }
+
+    /// Iterate over the texts within the collection.
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = &'a str> + DoubleEndedIterator + '_ {
+        self.internal.iter().copied()
+    }
+}
+
+impl<'a> FromIterator<&'a str> for BorrowedTextCollection<'a> {
+    fn from_iter<Iter: IntoIterator<Item = &'a str>>(iter: Iter) -> Self {
+        BorrowedTextCollection {
+            internal: Vec::from_iter(iter),
+        }
+    }
+}
+
+impl<'a> Extend<&'a str> for BorrowedTextCollection<'a> {
+    fn extend<Iter: IntoIterator<Item = &'a str>>(&mut self, iter: Iter) {
+        self.internal.extend(iter);
+    }
+}
+
+impl<'a> IntoIterator for BorrowedTextCollection<'a> {
+    type Item = &'a str;
+    type IntoIter = std::vec::IntoIter<&'a str>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.internal.into_iter()
+    }
+}
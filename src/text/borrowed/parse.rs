@@ -0,0 +1,93 @@
+use super::BorrowedTextCollection;
+use crate::{QueryDatabase, single::InsertError, text::parse::TextCollectionParseError};
+use arch_pkg_text::desc::{Query, QueryMut, misc::ShouldReuse};
+use pipe_trait::Pipe;
+use rayon::prelude::*;
+
+/// Return type of [`BorrowedTextCollection::parse`] and [`BorrowedTextCollection::parse_mut`].
+type ParseResult<'a, Querier> = Result<
+    QueryDatabase<'a, Querier>,
+    TextCollectionParseError<<&'a str as TryInto<Querier>>::Error>,
+>;
+
+impl<'a> BorrowedTextCollection<'a> {
+    /// Parse a database of queriers.
+    fn parse_with<Querier, Insert, InsertSuccess>(
+        &self,
+        mut insert: Insert,
+    ) -> ParseResult<'a, Querier>
+    where
+        &'a str: TryInto<Querier>,
+        Insert:
+            FnMut(&mut QueryDatabase<'a, Querier>, Querier) -> Result<InsertSuccess, InsertError>,
+    {
+        let mut db = QueryDatabase::with_capacity(self.internal.len());
+
+        for text in self.iter() {
+            let querier = text.try_into().map_err(TextCollectionParseError::Parse)?;
+            insert(&mut db, querier).map_err(TextCollectionParseError::Insert)?;
+        }
+
+        Ok(db)
+    }
+
+    /// Parse a database of [immutable queriers](Query).
+    ///
+    /// The database borrows from the underlying buffer rather than from the collection.
+    pub fn parse<Querier>(&self) -> ParseResult<'a, Querier>
+    where
+        &'a str: TryInto<Querier>,
+        Querier: Query<'a> + ShouldReuse,
+    {
+        self.parse_with(QueryDatabase::insert)
+    }
+
+    /// Parse a database of [mutable queriers](QueryMut).
+    ///
+    /// The database borrows from the underlying buffer rather than from the collection.
+    pub fn parse_mut<Querier>(&self) -> ParseResult<'a, Querier>
+    where
+        &'a str: TryInto<Querier>,
+        Querier: QueryMut<'a> + ShouldReuse,
+    {
+        self.parse_with(QueryDatabase::insert_mut)
+    }
+
+    /// Parse a database of queriers in parallel.
+    fn par_parse_with<Querier, QueriersIntoDb>(
+        &self,
+        queriers_into_db: QueriersIntoDb,
+    ) -> ParseResult<'a, Querier>
+    where
+        &'a str: TryInto<Querier, Error: Send>,
+        Querier: Send,
+        QueriersIntoDb: FnOnce(Vec<Querier>) -> Result<QueryDatabase<'a, Querier>, InsertError>,
+    {
+        self.internal
+            .par_iter()
+            .copied()
+            .map(TryInto::<Querier>::try_into)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(TextCollectionParseError::Parse)?
+            .pipe(queriers_into_db)
+            .map_err(TextCollectionParseError::Insert)
+    }
+
+    /// Parse a database of [immutable queriers](Query) in parallel.
+    pub fn par_parse<Querier>(&self) -> ParseResult<'a, Querier>
+    where
+        &'a str: TryInto<Querier, Error: Send>,
+        Querier: Query<'a> + ShouldReuse + Send,
+    {
+        self.par_parse_with(QueryDatabase::from_queriers)
+    }
+
+    /// Parse a database of [mutable queriers](QueryMut) in parallel.
+    pub fn par_parse_mut<Querier>(&self) -> ParseResult<'a, Querier>
+    where
+        &'a str: TryInto<Querier, Error: Send>,
+        Querier: QueryMut<'a> + ShouldReuse + Send,
+    {
+        self.par_parse_with(QueryDatabase::from_queriers_mut)
+    }
+}
@@ -0,0 +1,69 @@
+use super::BorrowedTextCollection;
+use derive_more::{Display, Error};
+use std::{ffi::OsStr, io, path::PathBuf, str::Utf8Error};
+
+/// Error when trying to borrow data from an uncompressed tar archive.
+#[derive(Debug, Display, Error)]
+pub enum LoadBorrowedTarError {
+    #[display("Failed to read the tar archive: {_0}")]
+    Tar(io::Error),
+    #[display("Entry {path:?} lies outside of the buffer")]
+    OutOfBounds {
+        #[error(not(source))]
+        path: PathBuf,
+    },
+    #[display("Entry {path:?} is not valid UTF-8: {error}")]
+    InvalidUtf8 {
+        #[error(not(source))]
+        path: PathBuf,
+        #[error(source)]
+        error: Utf8Error,
+    },
+}
+
+impl<'a> BorrowedTextCollection<'a> {
+    /// Traverse an uncompressed tar archive and add slices of its `desc` files to the text collection.
+    ///
+    /// The contents of the entries are borrowed from `bytes` instead of being copied.
+    pub fn extend_from_tar(&mut self, bytes: &'a [u8]) -> Result<(), LoadBorrowedTarError> {
+        let mut tar = tar::Archive::new(bytes);
+        let entries = tar.entries().map_err(LoadBorrowedTarError::Tar)?;
+
+        for entry in entries {
+            let entry = entry.map_err(LoadBorrowedTarError::Tar)?;
+            if !entry.header().entry_type().is_file() {
+                continue;
+            }
+            let path = entry.path().map_err(LoadBorrowedTarError::Tar)?;
+            if path.file_name() != Some(OsStr::new("desc")) {
+                continue;
+            }
+            let slice = usize::try_from(entry.raw_file_position())
+                .ok()
+                .zip(usize::try_from(entry.size()).ok())
+                .and_then(|(start, size)| bytes.get(start..start.checked_add(size)?));
+            let Some(slice) = slice else {
+                let path = path.into_owned();
+                return Err(LoadBorrowedTarError::OutOfBounds { path });
+            };
+            let text = str::from_utf8(slice).map_err(|error| {
+                let path = path.into_owned();
+                LoadBorrowedTarError::InvalidUtf8 { path, error }
+            })?;
+            self.insert(text);
+        }
+
+        Ok(())
+    }
+
+    /// Traverse an uncompressed tar archive and add slices of its `desc` files to the text collection.
+    pub fn add_tar(mut self, bytes: &'a [u8]) -> Result<Self, LoadBorrowedTarError> {
+        self.extend_from_tar(bytes)?;
+        Ok(self)
+    }
+
+    /// Traverse an uncompressed tar archive and add slices of its `desc` files to the text collection.
+    pub fn from_tar(bytes: &'a [u8]) -> Result<Self, LoadBorrowedTarError> {
+        BorrowedTextCollection::new().add_tar(bytes)
+    }
+}
@@ -0,0 +1,83 @@
+mod archive;
+mod local;
+mod parse;
+
+pub use parse::FilesCollectionParseError;
+
+use crate::Text;
+
+/// Pair of `desc` and `files` texts of a single package.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FilesText {
+    /// Content of the `desc` file.
+    pub desc: Text,
+    /// Content of the `files` file.
+    pub files: Text,
+    /// Decompressed content of the `mtree` file, only available in the local pacman database.
+    pub mtree: Option<Text>,
+}
+
+impl FilesText {
+    /// Pair a `desc` text with a `files` text.
+    pub fn new(desc: Text, files: Text) -> Self {
+        FilesText {
+            desc,
+            files,
+            mtree: None,
+        }
+    }
+
+    /// Attach the decompressed content of an `mtree` file.
+    pub fn with_mtree(self, mtree: Text) -> Self {
+        FilesText {
+            mtree: Some(mtree),
+            ..self
+        }
+    }
+}
+
+/// Collection of all `desc` and `files` texts from which a [`FileOwnerDatabase`](crate::FileOwnerDatabase) may be built.
+#[derive(Debug, Default, Clone)]
+pub struct FilesCollection {
+    internal: Vec<FilesText>,
+}
+
+impl FilesCollection {
+    /// Create an empty collection.
+    pub fn new() -> Self {
+        FilesCollection::default()
+    }
+
+    /// Create an empty collection with specified capacity.
+    pub fn with_capacity(capacity: usize) -> Self {
+        FilesCollection {
+            internal: Vec::with_capacity(capacity),
+        }
+    }
+
+    /// Add data into the collection.
+    pub fn insert(&mut self, text: FilesText) {
+        self.internal.push(text);
+    }
+
+    /// Add data into the collection.
+    pub fn add_item(mut self, text: FilesText) -> Self {
+        self.insert(text);
+        self
+    }
+
+    /// Get an iterator over all the pairs of texts.
+    pub fn iter(&self) -> core::slice::Iter<'_, FilesText> {
+        self.internal.iter()
+    }
+
+    /// The number of [`FilesText`]s within the collection.
+    pub fn len(&self) -> usize {
+        self.internal.len()
+    }
+
+    /// Whether the collection is empty.
+    pub fn is_empty(&self) -> bool {
+        self.internal.is_empty()
+    }
+}
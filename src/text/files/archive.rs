@@ -0,0 +1,96 @@
+use super::{FilesCollection, FilesText};
+use crate::text::archive::{LoadArchiveError, LoadTarError, decompress, traverse_tar};
+use arch_pkg_text::misc::indexmap::IndexMap;
+use pipe_trait::Pipe;
+use std::{ffi::OsStr, io::Read, path::PathBuf};
+
+impl FilesCollection {
+    /// Traverse a tar archive and add contents from its `desc` and `files` files to the collection.
+    ///
+    /// Packages without a `desc` file are ignored. Packages without a `files` file are treated as owning no files.
+    pub fn extend_from_tar<Bytes: Read>(&mut self, bytes: Bytes) -> Result<(), LoadTarError> {
+        let mut packages = IndexMap::<PathBuf, (Option<String>, Option<String>)>::new();
+
+        traverse_tar(
+            bytes,
+            |path| {
+                matches!(
+                    path.file_name().and_then(OsStr::to_str),
+                    Some("desc" | "files")
+                )
+            },
+            |path, text| {
+                let is_desc = path.file_name() == Some(OsStr::new("desc"));
+                let directory = path.parent().map(PathBuf::from).unwrap_or_default();
+                let (desc, files) = packages.entry(directory).or_default();
+                if is_desc {
+                    *desc = Some(text);
+                } else {
+                    *files = Some(text);
+                }
+            },
+        )?;
+
+        let texts = packages.into_values().filter_map(|(desc, files)| {
+            FilesText::new(desc?.into(), files.unwrap_or_default().into()).pipe(Some)
+        });
+        self.internal.extend(texts);
+        Ok(())
+    }
+
+    /// Traverse a tar archive and add contents from its `desc` and `files` files to the collection.
+    pub fn add_tar<Bytes: Read>(mut self, bytes: Bytes) -> Result<Self, LoadTarError> {
+        self.extend_from_tar(bytes)?;
+        Ok(self)
+    }
+
+    /// Traverse a tar archive and add contents from its `desc` and `files` files to the collection.
+    pub fn from_tar<Bytes: Read>(bytes: Bytes) -> Result<Self, LoadTarError> {
+        FilesCollection::new().add_tar(bytes)
+    }
+
+    /// Detect mime type of an archive, extract it, and add contents from its `desc` and `files` files to the collection.
+    ///
+    /// This is how `.files` archives of sync repositories (such as `core.files`) are loaded.
+    pub fn extend_from_archive(&mut self, bytes: &[u8]) -> Result<(), LoadArchiveError> {
+        self.extend_from_archive_reader(bytes)
+    }
+
+    /// Detect mime type of an archive, extract it, and add contents from its `desc` and `files` files to the collection.
+    pub fn add_archive(mut self, bytes: &[u8]) -> Result<Self, LoadArchiveError> {
+        self.extend_from_archive(bytes)?;
+        Ok(self)
+    }
+
+    /// Detect mime type of an archive, extract it, and add contents from its `desc` and `files` files to the collection.
+    pub fn from_archive(bytes: &[u8]) -> Result<Self, LoadArchiveError> {
+        FilesCollection::new().add_archive(bytes)
+    }
+
+    /// Detect mime type of an archive from its leading bytes, extract it as a stream,
+    /// and add contents from its `desc` and `files` files to the collection.
+    pub fn extend_from_archive_reader<Bytes: Read>(
+        &mut self,
+        bytes: Bytes,
+    ) -> Result<(), LoadArchiveError> {
+        let tar = decompress(bytes)?;
+        self.extend_from_tar(tar)?;
+        Ok(())
+    }
+
+    /// Detect mime type of an archive from its leading bytes, extract it as a stream,
+    /// and add contents from its `desc` and `files` files to the collection.
+    pub fn add_archive_reader<Bytes: Read>(
+        mut self,
+        bytes: Bytes,
+    ) -> Result<Self, LoadArchiveError> {
+        self.extend_from_archive_reader(bytes)?;
+        Ok(self)
+    }
+
+    /// Detect mime type of an archive from its leading bytes, extract it as a stream,
+    /// and add contents from its `desc` and `files` files to the collection.
+    pub fn from_archive_reader<Bytes: Read>(bytes: Bytes) -> Result<Self, LoadArchiveError> {
+        FilesCollection::new().add_archive_reader(bytes)
+    }
+}
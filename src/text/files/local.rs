@@ -0,0 +1,212 @@
+use super::{FilesCollection, FilesText};
+use crate::text::local::{LoadLocalDbError, ReadLocalDbDirError, ReadLocalDbFileError};
+use libflate::gzip::Decoder;
+use pipe_trait::Pipe;
+use rayon::prelude::*;
+use std::{
+    fs::{DirEntry, File, read_dir, read_to_string},
+    io::{self, ErrorKind, Read},
+    path::Path,
+};
+
+/// Read a text file, treating a missing file as `None`.
+fn read_optional(path: &Path) -> Result<Option<String>, ReadLocalDbFileError> {
+    match read_to_string(path) {
+        Ok(text) => Ok(Some(text)),
+        Err(error) if error.kind() == ErrorKind::NotFound => Ok(None),
+        Err(error) => Err(ReadLocalDbFileError::new(error, path.to_path_buf())),
+    }
+}
+
+/// Read and decompress a gzipped `mtree` file, treating a missing file as `None`.
+fn read_mtree(path: &Path) -> Result<Option<String>, ReadLocalDbFileError> {
+    let decompress = |file: File| -> io::Result<String> {
+        let mut text = String::new();
+        Decoder::new(file)?.read_to_string(&mut text)?;
+        Ok(text)
+    };
+    match File::open(path).and_then(decompress) {
+        Ok(text) => Ok(Some(text)),
+        Err(error) if error.kind() == ErrorKind::NotFound => Ok(None),
+        Err(error) => Err(ReadLocalDbFileError::new(error, path.to_path_buf())),
+    }
+}
+
+/// Read the `desc`, `files`, and optionally `mtree` files of a package directory in the local pacman database.
+///
+/// Returns `None` if the directory has no `desc` file.
+fn read_package(
+    entry: &DirEntry,
+    mtree: bool,
+) -> Result<Option<FilesText>, LoadLocalDbError<'static>> {
+    if !entry.file_type().is_ok_and(|file_type| file_type.is_dir()) {
+        return Ok(None);
+    }
+    let package_path = entry.path();
+    let read = || -> Result<Option<FilesText>, ReadLocalDbFileError> {
+        let Some(desc) = read_optional(&package_path.join("desc"))? else {
+            return Ok(None);
+        };
+        let files = read_optional(&package_path.join("files"))?.unwrap_or_default();
+        let mut text = FilesText::new(desc.into(), files.into());
+        if mtree && let Some(mtree) = read_mtree(&package_path.join("mtree"))? {
+            text = text.with_mtree(mtree.into());
+        }
+        Ok(Some(text))
+    };
+    read().map_err(LoadLocalDbError::ReadFile)
+}
+
+impl FilesCollection {
+    /// Load data from a local pacman database.
+    fn extend_from_local_db_with<'path>(
+        &mut self,
+        local_db_path: &'path Path,
+        mtree: bool,
+    ) -> Result<(), LoadLocalDbError<'path>> {
+        let entries = local_db_path
+            .pipe(read_dir)
+            .map_err(|error| ReadLocalDbDirError::new(error, local_db_path))
+            .map_err(LoadLocalDbError::ReadDir)?;
+
+        for entry in entries {
+            let Ok(entry) = entry else { continue };
+            if let Some(text) = read_package(&entry, mtree)? {
+                self.insert(text);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Load data from a local pacman database in parallel.
+    fn par_extend_from_local_db_with<'path>(
+        &mut self,
+        local_db_path: &'path Path,
+        mtree: bool,
+    ) -> Result<(), LoadLocalDbError<'path>> {
+        let texts = local_db_path
+            .pipe(read_dir)
+            .map_err(|error| ReadLocalDbDirError::new(error, local_db_path))
+            .map_err(LoadLocalDbError::ReadDir)?
+            .par_bridge()
+            .flatten()
+            .map(|entry| read_package(&entry, mtree))
+            .collect::<Result<Vec<Option<FilesText>>, LoadLocalDbError>>()?
+            .into_iter()
+            .flatten();
+        self.internal.extend(texts);
+        Ok(())
+    }
+
+    /// Load the `desc` and `files` files of all packages from a local pacman database.
+    ///
+    /// A local pacman database is a directory usually located at `$ARCH_ROOT/var/lib/pacman/local/`.
+    pub fn extend_from_local_db<'path>(
+        &mut self,
+        local_db_path: &'path Path,
+    ) -> Result<(), LoadLocalDbError<'path>> {
+        self.extend_from_local_db_with(local_db_path, false)
+    }
+
+    /// Load the `desc` and `files` files of all packages from a local pacman database.
+    ///
+    /// A local pacman database is a directory usually located at `$ARCH_ROOT/var/lib/pacman/local/`.
+    pub fn add_local_db(mut self, local_db_path: &'_ Path) -> Result<Self, LoadLocalDbError<'_>> {
+        self.extend_from_local_db(local_db_path)?;
+        Ok(self)
+    }
+
+    /// Load the `desc` and `files` files of all packages from a local pacman database.
+    ///
+    /// A local pacman database is a directory usually located at `$ARCH_ROOT/var/lib/pacman/local/`.
+    pub fn from_local_db(local_db_path: &'_ Path) -> Result<Self, LoadLocalDbError<'_>> {
+        FilesCollection::new().add_local_db(local_db_path)
+    }
+
+    /// Load the `desc`, `files`, and gzipped `mtree` files of all packages from a local pacman database.
+    ///
+    /// A local pacman database is a directory usually located at `$ARCH_ROOT/var/lib/pacman/local/`.
+    pub fn extend_from_local_db_with_mtree<'path>(
+        &mut self,
+        local_db_path: &'path Path,
+    ) -> Result<(), LoadLocalDbError<'path>> {
+        self.extend_from_local_db_with(local_db_path, true)
+    }
+
+    /// Load the `desc`, `files`, and gzipped `mtree` files of all packages from a local pacman database.
+    ///
+    /// A local pacman database is a directory usually located at `$ARCH_ROOT/var/lib/pacman/local/`.
+    pub fn add_local_db_with_mtree(
+        mut self,
+        local_db_path: &'_ Path,
+    ) -> Result<Self, LoadLocalDbError<'_>> {
+        self.extend_from_local_db_with_mtree(local_db_path)?;
+        Ok(self)
+    }
+
+    /// Load the `desc`, `files`, and gzipped `mtree` files of all packages from a local pacman database.
+    ///
+    /// A local pacman database is a directory usually located at `$ARCH_ROOT/var/lib/pacman/local/`.
+    pub fn from_local_db_with_mtree(local_db_path: &'_ Path) -> Result<Self, LoadLocalDbError<'_>> {
+        FilesCollection::new().add_local_db_with_mtree(local_db_path)
+    }
+
+    /// Load the `desc` and `files` files of all packages from a local pacman database in parallel.
+    ///
+    /// A local pacman database is a directory usually located at `$ARCH_ROOT/var/lib/pacman/local/`.
+    pub fn par_extend_from_local_db<'path>(
+        &mut self,
+        local_db_path: &'path Path,
+    ) -> Result<(), LoadLocalDbError<'path>> {
+        self.par_extend_from_local_db_with(local_db_path, false)
+    }
+
+    /// Load the `desc` and `files` files of all packages from a local pacman database in parallel.
+    ///
+    /// A local pacman database is a directory usually located at `$ARCH_ROOT/var/lib/pacman/local/`.
+    pub fn par_add_local_db(
+        mut self,
+        local_db_path: &'_ Path,
+    ) -> Result<Self, LoadLocalDbError<'_>> {
+        self.par_extend_from_local_db(local_db_path)?;
+        Ok(self)
+    }
+
+    /// Load the `desc` and `files` files of all packages from a local pacman database in parallel.
+    ///
+    /// A local pacman database is a directory usually located at `$ARCH_ROOT/var/lib/pacman/local/`.
+    pub fn par_from_local_db(local_db_path: &Path) -> Result<Self, LoadLocalDbError<'_>> {
+        FilesCollection::new().par_add_local_db(local_db_path)
+    }
+
+    /// Load the `desc`, `files`, and gzipped `mtree` files of all packages from a local pacman database in parallel.
+    ///
+    /// A local pacman database is a directory usually located at `$ARCH_ROOT/var/lib/pacman/local/`.
+    pub fn par_extend_from_local_db_with_mtree<'path>(
+        &mut self,
+        local_db_path: &'path Path,
+    ) -> Result<(), LoadLocalDbError<'path>> {
+        self.par_extend_from_local_db_with(local_db_path, true)
+    }
+
+    /// Load the `desc`, `files`, and gzipped `mtree` files of all packages from a local pacman database in parallel.
+    ///
+    /// A local pacman database is a directory usually located at `$ARCH_ROOT/var/lib/pacman/local/`.
+    pub fn par_add_local_db_with_mtree(
+        mut self,
+        local_db_path: &'_ Path,
+    ) -> Result<Self, LoadLocalDbError<'_>> {
+        self.par_extend_from_local_db_with_mtree(local_db_path)?;
+        Ok(self)
+    }
+
+    /// Load the `desc`, `files`, and gzipped `mtree` files of all packages from a local pacman database in parallel.
+    ///
+    /// A local pacman database is a directory usually located at `$ARCH_ROOT/var/lib/pacman/local/`.
+    pub fn par_from_local_db_with_mtree(
+        local_db_path: &Path,
+    ) -> Result<Self, LoadLocalDbError<'_>> {
+        FilesCollection::new().par_add_local_db_with_mtree(local_db_path)
+    }
+}
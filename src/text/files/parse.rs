@@ -0,0 +1,74 @@
+use super::FilesCollection;
+use crate::{
+    FileOwnerDatabase,
+    files::WithFileList,
+    single::InsertError,
+    value::{BackupList, FileList, Mtree},
+};
+use arch_pkg_text::desc::{Query, QueryMut, misc::ShouldReuse};
+use derive_more::{Display, Error};
+
+/// Error type when trying to create a [`FileOwnerDatabase`] from a [`FilesCollection`].
+#[derive(Debug, Display, Clone, Copy, Error)]
+#[display(bound(ParseError: Display))]
+pub enum FilesCollectionParseError<ParseError> {
+    Parse(ParseError),
+    Insert(InsertError),
+}
+
+/// Return type of [`FilesCollection::parse`] and [`FilesCollection::parse_mut`].
+type ParseResult<'a, Querier> = Result<
+    FileOwnerDatabase<'a, Querier>,
+    FilesCollectionParseError<<&'a str as TryInto<Querier>>::Error>,
+>;
+
+impl FilesCollection {
+    /// Parse a database of queriers and their files.
+    fn parse_with<'a, Querier, Insert, InsertSuccess>(
+        &'a self,
+        mut insert: Insert,
+    ) -> ParseResult<'a, Querier>
+    where
+        &'a str: TryInto<Querier>,
+        Insert: FnMut(
+            &mut FileOwnerDatabase<'a, Querier>,
+            WithFileList<'a, Querier>,
+        ) -> Result<InsertSuccess, InsertError>,
+    {
+        let mut db = FileOwnerDatabase::with_capacity(self.internal.len());
+
+        for text in &self.internal {
+            let querier = text
+                .desc
+                .as_str()
+                .try_into()
+                .map_err(FilesCollectionParseError::Parse)?;
+            let mut attached = WithFileList::new(querier, FileList::from_files_text(&text.files))
+                .with_backup(BackupList::from_files_text(&text.files));
+            if let Some(mtree) = &text.mtree {
+                attached = attached.with_mtree(Mtree(mtree));
+            }
+            insert(&mut db, attached).map_err(FilesCollectionParseError::Insert)?;
+        }
+
+        Ok(db)
+    }
+
+    /// Parse a database of [immutable queriers](Query) and their files.
+    pub fn parse<'a, Querier>(&'a self) -> ParseResult<'a, Querier>
+    where
+        &'a str: TryInto<Querier>,
+        Querier: Query<'a> + ShouldReuse,
+    {
+        self.parse_with(FileOwnerDatabase::insert)
+    }
+
+    /// Parse a database of [mutable queriers](QueryMut) and their files.
+    pub fn parse_mut<'a, Querier>(&'a self) -> ParseResult<'a, Querier>
+    where
+        &'a str: TryInto<Querier>,
+        Querier: QueryMut<'a> + ShouldReuse,
+    {
+        self.parse_with(FileOwnerDatabase::insert_mut)
+    }
+}
@@ -20,7 +20,7 @@ pub struct ReadLocalDbDirError<'a> {
 
 impl<'a> ReadLocalDbDirError<'a> {
     /// Create an error.
-    fn new(error: io::Error, path: &'a Path) -> Self {
+    pub(super) fn new(error: io::Error, path: &'a Path) -> Self {
         Self { error, path }
     }
 
@@ -46,7 +46,7 @@ pub struct ReadLocalDbFileError {
 
 impl ReadLocalDbFileError {
     /// Create an error.
-    fn new(error: io::Error, path: PathBuf) -> Self {
+    pub(super) fn new(error: io::Error, path: PathBuf) -> Self {
         Self { error, path }
     }
 
@@ -1,6 +1,7 @@
 use super::MultiTextCollection;
+use crate::{Text, value::RepositoryName};
 
-impl MultiTextCollection<'_> {
+impl<'a> MultiTextCollection<'a> {
     /// Shrink the capacity of the internal data.
     pub fn shrink_to_fit(&mut self) {
         self.internal.shrink_to_fit()
@@ -10,4 +11,14 @@ impl MultiTextCollection<'_> {
     pub fn is_empty(&self) -> bool {
         self.iter().next().is_none()
     }
+
+    /// Only keep the [`Text`]s for which `keep` returns `true`.
+    pub fn retain<Keep>(&mut self, mut keep: Keep)
+    where
+        Keep: FnMut(RepositoryName<'a>, &Text) -> bool,
+    {
+        for (repository, collection) in &mut self.internal {
+            collection.retain(|text| keep(*repository, text));
+        }
+    }
 }
@@ -1,4 +1,5 @@
 use super::TextCollection;
+use crate::Text;
 
 impl TextCollection {
     /// Shrink the capacity of the internal data.
@@ -15,4 +16,9 @@ impl TextCollection {
     pub fn is_empty(&self) -> bool {
         self.internal.is_empty()
     }
+
+    /// Only keep the [`Text`]s for which `keep` returns `true`.
+    pub fn retain<Keep: FnMut(&Text) -> bool>(&mut self, keep: Keep) {
+        self.internal.retain(keep)
+    }
 }
@@ -0,0 +1,266 @@
+use super::{MultiTextCollection, TextCollection, archive::LoadArchiveError};
+use crate::value::RepositoryName;
+use core::{cmp::Ordering, slice};
+use derive_more::{Display, Error};
+use pipe_trait::Pipe;
+use rayon::prelude::*;
+use std::{
+    ffi::OsStr,
+    fs::{File, read_dir},
+    io::{self, BufReader},
+    path::{Path, PathBuf},
+};
+
+/// Error when trying to read a pacman sync directory.
+#[derive(Debug, Display, Error)]
+#[display("Failed to read {path:?} as a directory: {error}")]
+pub struct ReadSyncDirError<'a> {
+    #[error(source)]
+    error: io::Error,
+    path: &'a Path,
+}
+
+impl<'a> ReadSyncDirError<'a> {
+    /// Create an error.
+    fn new(error: io::Error, path: &'a Path) -> Self {
+        Self { error, path }
+    }
+
+    /// The source of this error.
+    pub fn source(&self) -> &io::Error {
+        &self.error
+    }
+
+    /// Path to the sync directory.
+    pub fn path(&self) -> &Path {
+        self.path
+    }
+}
+
+/// Error when trying to load a sync database archive.
+#[derive(Debug, Display, Error)]
+#[display("Failed to load {path:?} as a sync database: {error}")]
+pub struct LoadSyncDbError {
+    #[error(source)]
+    error: LoadArchiveError,
+    path: PathBuf,
+}
+
+impl LoadSyncDbError {
+    /// Create an error.
+    fn new(error: LoadArchiveError, path: PathBuf) -> Self {
+        Self { error, path }
+    }
+
+    /// The source of this error.
+    pub fn source(&self) -> &LoadArchiveError {
+        &self.error
+    }
+
+    /// Path to the sync database archive.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+/// Sync database archive of a repository, such as `/var/lib/pacman/sync/core.db`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyncDb {
+    repository: Box<str>,
+    path: PathBuf,
+}
+
+impl SyncDb {
+    /// Pair a repository name with the path to its sync database archive.
+    pub fn new(repository: RepositoryName<'_>, path: PathBuf) -> Self {
+        SyncDb {
+            repository: repository.as_str().into(),
+            path,
+        }
+    }
+
+    /// Name of the repository.
+    pub fn repository(&self) -> RepositoryName<'_> {
+        RepositoryName(&self.repository)
+    }
+
+    /// Path to the sync database archive.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Load the `desc` texts of the sync database archive.
+    fn load(&self) -> Result<TextCollection, LoadSyncDbError> {
+        load_sync_db(&self.path)
+    }
+}
+
+/// Load the `desc` texts of a sync database archive.
+pub(crate) fn load_sync_db(path: &Path) -> Result<TextCollection, LoadSyncDbError> {
+    path.pipe(File::open)
+        .map_err(LoadArchiveError::Read)
+        .map(BufReader::new)
+        .and_then(TextCollection::from_archive_reader)
+        .map_err(|error| LoadSyncDbError::new(error, path.to_path_buf()))
+}
+
+/// List of sync database archives in a pacman sync directory.
+///
+/// The list can be filtered and reordered before being loaded by [`MultiTextCollection::from_sync_dir`].
+///
+/// A [`MultiTextCollection`] borrows the names of its repositories, and the names of the repositories
+/// in a sync directory are only known after it has been listed, so the list must outlive the collections
+/// which are loaded from it. That's why there is no constructor which takes the path of a sync directory.
+#[derive(Debug, Default, Clone)]
+pub struct SyncDir {
+    internal: Vec<SyncDb>,
+}
+
+impl SyncDir {
+    /// List every `*.db` file in a pacman sync directory, sorted by repository name.
+    ///
+    /// A pacman sync directory is usually located at `$ARCH_ROOT/var/lib/pacman/sync/`.
+    /// Files whose stems aren't [valid repository names](RepositoryName::is_valid) are ignored.
+    pub fn read(sync_dir_path: &Path) -> Result<Self, ReadSyncDirError<'_>> {
+        let entries = sync_dir_path
+            .pipe(read_dir)
+            .map_err(|error| ReadSyncDirError::new(error, sync_dir_path))?;
+
+        let mut internal = Vec::new();
+        for entry in entries {
+            let Ok(entry) = entry else { continue };
+            let path = entry.path();
+            if path.extension() != Some(OsStr::new("db")) || !path.is_file() {
+                continue;
+            }
+            let Some(repository) = path.file_stem().and_then(OsStr::to_str) else {
+                continue;
+            };
+            let repository = RepositoryName(repository);
+            if !repository.is_valid() {
+                continue;
+            }
+            internal.push(SyncDb::new(repository, path.clone()));
+        }
+        internal.sort_by(|a, b| a.repository.cmp(&b.repository));
+
+        Ok(SyncDir { internal })
+    }
+
+    /// Keep only the sync databases whose repository names satisfy `keep`.
+    pub fn retain<Keep>(&mut self, mut keep: Keep)
+    where
+        Keep: FnMut(RepositoryName<'_>) -> bool,
+    {
+        self.internal.retain(|db| keep(db.repository()));
+    }
+
+    /// Sort the sync databases with a comparator.
+    pub fn sort_by<Compare>(&mut self, mut compare: Compare)
+    where
+        Compare: FnMut(&SyncDb, &SyncDb) -> Ordering,
+    {
+        self.internal.sort_by(|a, b| compare(a, b));
+    }
+
+    /// Keep only the sync databases of the listed repositories, in the order they were listed.
+    ///
+    /// This is useful for following the repository order declared in `pacman.conf`.
+    pub fn select(&mut self, repositories: &[RepositoryName<'_>]) {
+        self.internal
+            .retain(|db| repositories.contains(&db.repository()));
+        self.internal.sort_by_key(|db| {
+            repositories
+                .iter()
+                .position(|repository| *repository == db.repository())
+        });
+    }
+
+    /// Get an iterator over all the sync databases.
+    pub fn iter(&self) -> slice::Iter<'_, SyncDb> {
+        self.internal.iter()
+    }
+
+    /// The number of sync databases.
+    pub fn len(&self) -> usize {
+        self.internal.len()
+    }
+
+    /// Whether there are no sync databases.
+    pub fn is_empty(&self) -> bool {
+        self.internal.is_empty()
+    }
+}
+
+impl<'a> IntoIterator for &'a SyncDir {
+    type Item = &'a SyncDb;
+    type IntoIter = slice::Iter<'a, SyncDb>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl FromIterator<SyncDb> for SyncDir {
+    fn from_iter<Iter: IntoIterator<Item = SyncDb>>(iter: Iter) -> Self {
+        SyncDir {
+            internal: Vec::from_iter(iter),
+        }
+    }
+}
+
+impl<'a> MultiTextCollection<'a> {
+    /// Load every sync database archive in a [`SyncDir`] in order.
+    pub fn extend_from_sync_dir(&mut self, sync_dir: &'a SyncDir) -> Result<(), LoadSyncDbError> {
+        for db in sync_dir {
+            let collection = db.load()?;
+            self.insert(db.repository(), collection);
+        }
+        Ok(())
+    }
+
+    /// Load every sync database archive in a [`SyncDir`] in order.
+    pub fn add_sync_dir(mut self, sync_dir: &'a SyncDir) -> Result<Self, LoadSyncDbError> {
+        self.extend_from_sync_dir(sync_dir)?;
+        Ok(self)
+    }
+
+    /// Load every sync database archive in a [`SyncDir`] in order.
+    ///
+    /// The repository names are borrowed from `sync_dir`, see [`SyncDir`] for why it must be read beforehand.
+    pub fn from_sync_dir(sync_dir: &'a SyncDir) -> Result<Self, LoadSyncDbError> {
+        MultiTextCollection::with_capacity(sync_dir.len()).add_sync_dir(sync_dir)
+    }
+
+    /// Load every sync database archive in a [`SyncDir`] in parallel.
+    ///
+    /// The repositories are inserted in the same order as they are in the [`SyncDir`].
+    pub fn par_extend_from_sync_dir(
+        &mut self,
+        sync_dir: &'a SyncDir,
+    ) -> Result<(), LoadSyncDbError> {
+        let collections = sync_dir
+            .internal
+            .par_iter()
+            .map(|db| db.load().map(|collection| (db.repository(), collection)))
+            .collect::<Result<Vec<_>, _>>()?;
+        self.extend(collections);
+        Ok(())
+    }
+
+    /// Load every sync database archive in a [`SyncDir`] in parallel.
+    ///
+    /// The repositories are inserted in the same order as they are in the [`SyncDir`].
+    pub fn par_add_sync_dir(mut self, sync_dir: &'a SyncDir) -> Result<Self, LoadSyncDbError> {
+        self.par_extend_from_sync_dir(sync_dir)?;
+        Ok(self)
+    }
+
+    /// Load every sync database archive in a [`SyncDir`] in parallel.
+    ///
+    /// The repositories are inserted in the same order as they are in the [`SyncDir`].
+    ///
+    /// The repository names are borrowed from `sync_dir`, see [`SyncDir`] for why it must be read beforehand.
+    pub fn par_from_sync_dir(sync_dir: &'a SyncDir) -> Result<Self, LoadSyncDbError> {
+        MultiTextCollection::with_capacity(sync_dir.len()).par_add_sync_dir(sync_dir)
+    }
+}
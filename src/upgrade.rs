@@ -0,0 +1,212 @@
+//! Plan system upgrades by comparing a local database against sync databases.
+
+use crate::{
+    MultiQueryDatabase, QueryDatabase,
+    multi::{WithParsedVersionUtils, WithRepositoryNameUtils},
+    satisfy::{package_satisfies, vercmp},
+    value::RepositoryName,
+};
+use arch_pkg_text::{
+    desc::Query,
+    value::{Name, Version},
+};
+use core::cmp::Ordering;
+
+/// Installed package whose version differs from the version in the sync databases.
+#[derive(Debug, Clone, Copy)]
+pub struct VersionChange<'a> {
+    name: Name<'a>,
+    installed: Version<'a>,
+    candidate: Version<'a>,
+    repository: RepositoryName<'a>,
+}
+
+impl<'a> VersionChange<'a> {
+    /// Name of the package.
+    pub fn name(&self) -> Name<'a> {
+        self.name
+    }
+
+    /// Version of the installed package.
+    pub fn installed(&self) -> Version<'a> {
+        self.installed
+    }
+
+    /// Version of the package in the sync databases.
+    pub fn candidate(&self) -> Version<'a> {
+        self.candidate
+    }
+
+    /// Repository of the candidate.
+    pub fn repository(&self) -> RepositoryName<'a> {
+        self.repository
+    }
+}
+
+/// Installed package which doesn't exist in any sync database.
+#[derive(Debug, Clone, Copy)]
+pub struct ForeignPackage<'a> {
+    name: Name<'a>,
+    version: Option<Version<'a>>,
+}
+
+impl<'a> ForeignPackage<'a> {
+    /// Name of the package.
+    pub fn name(&self) -> Name<'a> {
+        self.name
+    }
+
+    /// Version of the installed package.
+    pub fn version(&self) -> Option<Version<'a>> {
+        self.version
+    }
+}
+
+/// Installed package which is superseded by a sync package via its `%REPLACES%` array.
+#[derive(Debug, Clone, Copy)]
+pub struct Replacement<'a> {
+    replaced: Name<'a>,
+    replacer: Name<'a>,
+    version: Version<'a>,
+    repository: RepositoryName<'a>,
+}
+
+impl<'a> Replacement<'a> {
+    /// Name of the installed package which would be replaced.
+    pub fn replaced(&self) -> Name<'a> {
+        self.replaced
+    }
+
+    /// Name of the sync package which replaces it.
+    pub fn replacer(&self) -> Name<'a> {
+        self.replacer
+    }
+
+    /// Version of the replacer.
+    pub fn version(&self) -> Version<'a> {
+        self.version
+    }
+
+    /// Repository of the replacer.
+    pub fn repository(&self) -> RepositoryName<'a> {
+        self.repository
+    }
+}
+
+/// Result of comparing a local database against sync databases, the equivalent of `pacman -Qu`.
+///
+/// The candidate of each package is its [latest](crate::multi::MultiQuerier::latest) version among
+/// all repositories. All lists are sorted by package names.
+#[derive(Debug, Default, Clone)]
+pub struct UpgradePlan<'a> {
+    upgrades: Vec<VersionChange<'a>>,
+    downgrades: Vec<VersionChange<'a>>,
+    foreign: Vec<ForeignPackage<'a>>,
+    replacements: Vec<Replacement<'a>>,
+}
+
+impl<'a> UpgradePlan<'a> {
+    /// Compare the installed packages in `local` against the packages in `sync`.
+    ///
+    /// Installed packages without versions are ignored. Versions which couldn't be parsed are compared
+    /// with [`vercmp`].
+    pub fn new<LocalQuerier, SyncQuerier>(
+        local: &'a QueryDatabase<'a, LocalQuerier>,
+        sync: &'a MultiQueryDatabase<'a, SyncQuerier>,
+    ) -> Self
+    where
+        LocalQuerier: Query<'a>,
+        SyncQuerier: Query<'a>,
+    {
+        let mut plan = UpgradePlan::default();
+
+        for (name, installed) in local.entries().map(|entry| entry.into_tuple()) {
+            let installed_version = installed.version();
+            let Some(candidate) = sync.get(name).and_then(|queriers| queriers.latest()) else {
+                plan.foreign.push(ForeignPackage {
+                    name,
+                    version: installed_version,
+                });
+                continue;
+            };
+            let (Some(installed_version), Some(candidate_version)) =
+                (installed_version, candidate.version())
+            else {
+                continue;
+            };
+            let ordering = match installed_version.parse() {
+                Ok(parsed) => candidate.parsed_version().cmp(&parsed),
+                Err(_) => vercmp(candidate_version, installed_version),
+            };
+            let change = VersionChange {
+                name,
+                installed: installed_version,
+                candidate: candidate_version,
+                repository: candidate.repository_name(),
+            };
+            match ordering {
+                Ordering::Greater => plan.upgrades.push(change),
+                Ordering::Less => plan.downgrades.push(change),
+                Ordering::Equal => {}
+            }
+        }
+
+        for (replacer, candidate) in sync.latest_entries() {
+            if local.get(replacer).is_some() {
+                continue;
+            }
+            let Some(version) = candidate.version() else {
+                continue;
+            };
+            for replaces in candidate.replaces().into_iter().flatten() {
+                let (replaced, _) = replaces.components();
+                let Some(installed) = local.get(Name(replaced.as_str())) else {
+                    continue;
+                };
+                let Some(replaced) = installed.name() else {
+                    continue;
+                };
+                if package_satisfies(replaces, Some(replaced), installed.version(), None) {
+                    plan.replacements.push(Replacement {
+                        replaced,
+                        replacer,
+                        version,
+                        repository: candidate.repository_name(),
+                    });
+                }
+            }
+        }
+
+        plan.upgrades.sort_by_key(|change| change.name);
+        plan.downgrades.sort_by_key(|change| change.name);
+        plan.foreign.sort_by_key(|package| package.name);
+        plan.replacements
+            .sort_by_key(|replacement| (replacement.replaced, replacement.replacer));
+        plan
+    }
+
+    /// Installed packages which have newer versions in the sync databases.
+    pub fn upgrades(&self) -> &[VersionChange<'a>] {
+        &self.upgrades
+    }
+
+    /// Installed packages which are newer than their versions in the sync databases.
+    pub fn downgrades(&self) -> &[VersionChange<'a>] {
+        &self.downgrades
+    }
+
+    /// Installed packages which don't exist in any sync database.
+    pub fn foreign(&self) -> &[ForeignPackage<'a>] {
+        &self.foreign
+    }
+
+    /// Installed packages which are superseded by sync packages that aren't installed.
+    pub fn replacements(&self) -> &[Replacement<'a>] {
+        &self.replacements
+    }
+
+    /// Check whether there is nothing to upgrade, downgrade, or replace.
+    pub fn is_up_to_date(&self) -> bool {
+        self.upgrades.is_empty() && self.downgrades.is_empty() && self.replacements.is_empty()
+    }
+}
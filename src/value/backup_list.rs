@@ -0,0 +1,88 @@
+use super::file_list::section;
+use arch_pkg_text::value::FilePath;
+use core::{iter::FusedIterator, str::Lines};
+use pipe_trait::Pipe;
+
+/// List of backup files and their md5 checksums under the `%BACKUP%` section of a `files` file.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct BackupList<'a>(pub &'a str);
+
+impl<'a> BackupList<'a> {
+    /// Extract the `%BACKUP%` section from the content of a `files` file.
+    ///
+    /// Returns an empty list if the section doesn't exist.
+    pub fn from_files_text(text: &'a str) -> Self {
+        section(text, "%BACKUP%")
+            .unwrap_or_default()
+            .pipe(BackupList)
+    }
+
+    /// Get an immutable reference to the string underneath.
+    pub fn as_str(&self) -> &'a str {
+        self.0
+    }
+
+    /// Get an iterator over all the entries in the list.
+    pub fn iter(&self) -> BackupListIter<'a> {
+        BackupListIter {
+            lines: self.0.lines(),
+        }
+    }
+}
+
+impl<'a> From<&'a str> for BackupList<'a> {
+    fn from(value: &'a str) -> Self {
+        BackupList(value)
+    }
+}
+
+impl<'a> IntoIterator for BackupList<'a> {
+    type Item = BackupEntry<'a>;
+    type IntoIter = BackupListIter<'a>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Entry of a [`BackupList`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BackupEntry<'a> {
+    path: FilePath<'a>,
+    md5: &'a str,
+}
+
+impl<'a> BackupEntry<'a> {
+    /// Path of the backup file relative to the root of the system.
+    pub fn path(&self) -> FilePath<'a> {
+        self.path
+    }
+
+    /// Md5 checksum of the backup file as it was installed, in lowercase hexadecimal.
+    pub fn md5(&self) -> &'a str {
+        self.md5
+    }
+}
+
+/// [Iterator] over all the [entries](BackupEntry) in a [`BackupList`].
+///
+/// Lines without a checksum are skipped.
+#[derive(Debug, Clone)]
+pub struct BackupListIter<'a> {
+    lines: Lines<'a>,
+}
+
+impl<'a> Iterator for BackupListIter<'a> {
+    type Item = BackupEntry<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.lines.by_ref().find_map(|line| {
+            let (path, md5) = line.trim().split_once('\t')?;
+            Some(BackupEntry {
+                path: FilePath(path),
+                md5,
+            })
+        })
+    }
+}
+
+impl FusedIterator for BackupListIter<'_> {}
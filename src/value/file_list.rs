@@ -0,0 +1,79 @@
+use arch_pkg_text::value::FilePath;
+use core::{iter::FusedIterator, str::Lines};
+use pipe_trait::Pipe;
+
+/// List of file paths under the `%FILES%` section of a `files` file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FileList<'a>(pub &'a str);
+
+impl<'a> FileList<'a> {
+    /// Extract the `%FILES%` section from the content of a `files` file.
+    ///
+    /// Returns an empty list if the section doesn't exist.
+    pub fn from_files_text(text: &'a str) -> Self {
+        section(text, "%FILES%").unwrap_or_default().pipe(FileList)
+    }
+
+    /// Get an immutable reference to the string underneath.
+    pub fn as_str(&self) -> &'a str {
+        self.0
+    }
+
+    /// Get an iterator over all the file paths in the list.
+    pub fn iter(&self) -> FileListIter<'a> {
+        FileListIter {
+            lines: self.0.lines(),
+        }
+    }
+}
+
+impl<'a> From<&'a str> for FileList<'a> {
+    fn from(value: &'a str) -> Self {
+        FileList(value)
+    }
+}
+
+impl<'a> IntoIterator for FileList<'a> {
+    type Item = FilePath<'a>;
+    type IntoIter = FileListIter<'a>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// [Iterator] over all the [file paths](FilePath) in a [`FileList`].
+#[derive(Debug, Clone)]
+pub struct FileListIter<'a> {
+    lines: Lines<'a>,
+}
+
+impl<'a> Iterator for FileListIter<'a> {
+    type Item = FilePath<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.lines
+            .by_ref()
+            .map(str::trim)
+            .find(|line| !line.is_empty())
+            .map(FilePath)
+    }
+}
+
+impl FusedIterator for FileListIter<'_> {}
+
+/// Find the text of a `%SECTION%` in a `desc` or `files` file.
+pub(super) fn section<'a>(text: &'a str, header: &str) -> Option<&'a str> {
+    let is_header = |line: &str| line.len() > 1 && line.starts_with('%') && line.ends_with('%');
+    let mut offset = 0;
+    let mut start = None;
+    for line in text.split_inclusive('\n') {
+        let trimmed = line.trim();
+        match start {
+            None if trimmed == header => start = Some(offset + line.len()),
+            Some(start) if is_header(trimmed) => return Some(&text[start..offset]),
+            _ => {}
+        }
+        offset += line.len();
+    }
+    start.map(|start| &text[start..])
+}
@@ -0,0 +1,23 @@
+use super::file_list::section;
+
+/// Reason of installation of a package under the `%REASON%` section of a local `desc` file.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InstallReason {
+    /// The package was explicitly installed (`%REASON%` is `0` or absent).
+    #[default]
+    Explicit,
+    /// The package was installed as a dependency of another package (`%REASON%` is `1`).
+    Dependency,
+}
+
+impl InstallReason {
+    /// Extract the `%REASON%` section from the content of a local `desc` file.
+    ///
+    /// Returns [`InstallReason::Explicit`] if the section doesn't exist or isn't recognized, like pacman does.
+    pub fn from_desc_text(text: &str) -> Self {
+        match section(text, "%REASON%").map(str::trim) {
+            Some("1") => InstallReason::Dependency,
+            _ => InstallReason::Explicit,
+        }
+    }
+}
@@ -0,0 +1,174 @@
+use core::{iter::FusedIterator, str::Lines};
+use std::borrow::Cow;
+
+/// Decompressed content of an `mtree` file which describes the files installed by a package.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Mtree<'a>(pub &'a str);
+
+impl<'a> Mtree<'a> {
+    /// Get an immutable reference to the string underneath.
+    pub fn as_str(&self) -> &'a str {
+        self.0
+    }
+
+    /// Get an iterator over all the entries in the mtree.
+    ///
+    /// Defaults declared by `/set` are applied to the entries and retracted by `/unset`.
+    pub fn entries(&self) -> MtreeEntries<'a> {
+        MtreeEntries {
+            lines: self.0.lines(),
+            defaults: Vec::new(),
+        }
+    }
+}
+
+impl<'a> From<&'a str> for Mtree<'a> {
+    fn from(value: &'a str) -> Self {
+        Mtree(value)
+    }
+}
+
+/// Entry of an [`Mtree`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MtreeEntry<'a> {
+    path: &'a str,
+    keywords: Vec<(&'a str, &'a str)>,
+}
+
+impl<'a> MtreeEntry<'a> {
+    /// Path of the entry as written in the mtree, i.e. escaped and starting with `./`.
+    pub fn raw_path(&self) -> &'a str {
+        self.path
+    }
+
+    /// Path of the entry relative to the root of the system, with escape sequences decoded.
+    pub fn path(&self) -> Cow<'a, str> {
+        let path = self.path.strip_prefix("./").unwrap_or(self.path);
+        if !path.contains('\\') {
+            return Cow::Borrowed(path);
+        }
+
+        let mut bytes = Vec::with_capacity(path.len());
+        let mut rest = path.as_bytes();
+        while let Some((&byte, tail)) = rest.split_first() {
+            let octal = tail
+                .get(..3)
+                .filter(|digits| digits.iter().all(|digit| matches!(digit, b'0'..=b'7')))
+                .map(|digits| {
+                    digits
+                        .iter()
+                        .fold(0u16, |acc, digit| acc * 8 + u16::from(digit - b'0'))
+                })
+                .and_then(|code| u8::try_from(code).ok());
+            match (byte, octal) {
+                (b'\\', Some(decoded)) => {
+                    bytes.push(decoded);
+                    rest = &tail[3..];
+                }
+                _ => {
+                    bytes.push(byte);
+                    rest = tail;
+                }
+            }
+        }
+        String::from_utf8_lossy(&bytes).into_owned().into()
+    }
+
+    /// Whether the entry describes package metadata (such as `.PKGINFO` or `.BUILDINFO`) rather than an installed file.
+    pub fn is_metadata(&self) -> bool {
+        self.path.starts_with("./.") && !self.path[3..].contains('/')
+    }
+
+    /// Get the value of a keyword.
+    pub fn get(&self, keyword: &str) -> Option<&'a str> {
+        self.keywords
+            .iter()
+            .find(|(key, _)| *key == keyword)
+            .map(|(_, value)| *value)
+    }
+
+    /// Value of the `type` keyword, such as `file`, `dir`, or `link`.
+    pub fn entry_type(&self) -> Option<&'a str> {
+        self.get("type")
+    }
+
+    /// Value of the `mode` keyword.
+    pub fn mode(&self) -> Option<&'a str> {
+        self.get("mode")
+    }
+
+    /// Value of the `size` keyword.
+    pub fn size(&self) -> Option<u64> {
+        self.get("size")?.parse().ok()
+    }
+
+    /// Value of the `link` keyword.
+    pub fn link(&self) -> Option<&'a str> {
+        self.get("link")
+    }
+
+    /// Value of the `md5digest` keyword.
+    pub fn md5_digest(&self) -> Option<&'a str> {
+        self.get("md5digest")
+    }
+
+    /// Value of the `sha256digest` keyword.
+    pub fn sha256_digest(&self) -> Option<&'a str> {
+        self.get("sha256digest")
+    }
+}
+
+/// [Iterator] over all the [entries](MtreeEntry) in an [`Mtree`].
+#[derive(Debug, Clone)]
+pub struct MtreeEntries<'a> {
+    lines: Lines<'a>,
+    defaults: Vec<(&'a str, &'a str)>,
+}
+
+impl<'a> Iterator for MtreeEntries<'a> {
+    type Item = MtreeEntry<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let line = self.lines.next()?.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let mut words = line.split_whitespace();
+            let Some(head) = words.next() else { continue };
+            let keywords = words.filter_map(|word| word.split_once('='));
+
+            match head {
+                "/set" => {
+                    for (key, value) in keywords {
+                        self.defaults.retain(|(existing, _)| *existing != key);
+                        self.defaults.push((key, value));
+                    }
+                }
+                "/unset" => {
+                    for key in line.split_whitespace().skip(1) {
+                        if key == "all" {
+                            self.defaults.clear();
+                        } else {
+                            self.defaults.retain(|(existing, _)| *existing != key);
+                        }
+                    }
+                }
+                path => {
+                    let mut entry = MtreeEntry {
+                        path,
+                        keywords: self.defaults.clone(),
+                    };
+                    for (key, value) in keywords {
+                        entry.keywords.retain(|(existing, _)| *existing != key);
+                        entry.keywords.push((key, value));
+                    }
+                    return Some(entry);
+                }
+            }
+        }
+    }
+}
+
+impl FusedIterator for MtreeEntries<'_> {}
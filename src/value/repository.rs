@@ -2,6 +2,7 @@ use derive_more::{AsRef, Deref, Display, From, Into};
 
 /// Name of a repository.
 #[derive(Debug, Display, Clone, Copy, PartialEq, Eq, AsRef, Deref, From, Into)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(transparent))]
 pub struct RepositoryName<'a>(pub &'a str);
 
 impl<'a> RepositoryName<'a> {
@@ -0,0 +1,10 @@
+//! Write sync database archives, the equivalent of `repo-add`.
+
+mod compression;
+mod desc;
+mod sync_db;
+
+pub use crate::raw::DESC_FIELDS;
+pub use compression::{Compression, WriteArchiveError};
+pub use desc::{desc_text, files_text};
+pub use sync_db::{InsertEntryError, SyncDbWriter};
@@ -0,0 +1,74 @@
+use derive_more::{Display, Error};
+use libflate::gzip::Encoder;
+use lzma_rust2::{XzOptions, XzWriter};
+use ruzstd::encoding::{CompressionLevel, compress_to_vec};
+use std::io::{self, Write};
+
+/// Compression format of a written archive.
+#[derive(Debug, Display, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Compression {
+    /// Gzip, the default of `repo-add`.
+    #[display("gz")]
+    Gzip,
+    /// Xz.
+    #[display("xz")]
+    Xz,
+    /// Zstandard.
+    #[display("zst")]
+    Zstd,
+}
+
+impl Compression {
+    /// Suffix which `repo-add` appends to the archive name, e.g. `.db.tar.gz`.
+    pub fn extension(self) -> &'static str {
+        match self {
+            Compression::Gzip => "tar.gz",
+            Compression::Xz => "tar.xz",
+            Compression::Zstd => "tar.zst",
+        }
+    }
+}
+
+/// Error when trying to write an archive.
+#[derive(Debug, Display, Error)]
+pub enum WriteArchiveError {
+    #[display("Failed to create the tar archive: {_0}")]
+    Tar(io::Error),
+    #[display("Failed to create the gzip archive: {_0}")]
+    Gzip(io::Error),
+    #[display("Failed to create the xz archive: {_0}")]
+    Xz(io::Error),
+    #[display("Failed to write the archive: {_0}")]
+    Write(io::Error),
+}
+
+/// Compress an uncompressed tar archive into `writer`.
+pub(super) fn compress<Writer: Write>(
+    tar: &[u8],
+    mut writer: Writer,
+    compression: Compression,
+) -> Result<Writer, WriteArchiveError> {
+    match compression {
+        Compression::Gzip => {
+            let mut encoder = Encoder::new(writer).map_err(WriteArchiveError::Gzip)?;
+            encoder.write_all(tar).map_err(WriteArchiveError::Gzip)?;
+            encoder
+                .finish()
+                .into_result()
+                .map_err(WriteArchiveError::Gzip)
+        }
+        Compression::Xz => {
+            let mut encoder =
+                XzWriter::new(writer, XzOptions::with_preset(6)).map_err(WriteArchiveError::Xz)?;
+            encoder.write_all(tar).map_err(WriteArchiveError::Xz)?;
+            encoder.finish().map_err(WriteArchiveError::Xz)
+        }
+        Compression::Zstd => {
+            let compressed = compress_to_vec(tar, CompressionLevel::Fastest);
+            writer
+                .write_all(&compressed)
+                .map_err(WriteArchiveError::Write)?;
+            Ok(writer)
+        }
+    }
+}
@@ -0,0 +1,36 @@
+use crate::{
+    raw::{DESC_FIELDS, RawFields},
+    value::FileList,
+};
+use arch_pkg_text::desc::Query;
+
+/// Render the content of a `desc` file from a querier.
+///
+/// Fields are written in the order of [`DESC_FIELDS`]. Absent and empty fields are omitted.
+pub fn desc_text<'a>(querier: &impl Query<'a>) -> String {
+    let mut text = String::new();
+    for (field, value) in DESC_FIELDS
+        .into_iter()
+        .zip(RawFields::new(querier).values())
+    {
+        let Some(value) = value.filter(|value| !value.is_empty()) else {
+            continue;
+        };
+        text.push('%');
+        text.push_str(field.into());
+        text.push_str("%\n");
+        text.push_str(value);
+        text.push_str("\n\n");
+    }
+    text
+}
+
+/// Render the content of a `files` file from a list of file paths.
+pub fn files_text(files: FileList) -> String {
+    let mut text = String::from("%FILES%\n");
+    for path in files {
+        text.push_str(path.as_str());
+        text.push('\n');
+    }
+    text
+}
@@ -0,0 +1,197 @@
+use super::{
+    Compression, WriteArchiveError,
+    compression::compress,
+    desc::{desc_text, files_text},
+};
+use crate::{PackageDesc, value::FileList};
+use arch_pkg_text::desc::Query;
+use derive_more::{Display, Error};
+use std::{
+    collections::BTreeMap,
+    io::{self, Write},
+};
+use tar::{Builder, EntryType, Header};
+
+/// Error when trying to add a package to a [`SyncDbWriter`].
+#[derive(Debug, Display, Clone, Copy, PartialEq, Eq, Error)]
+pub enum InsertEntryError {
+    #[display("The package has no name")]
+    MissingName,
+    #[display("The package has no version")]
+    MissingVersion,
+}
+
+/// Entry of a single package in a sync database.
+#[derive(Debug, Clone)]
+struct SyncDbEntry {
+    directory: String,
+    desc: String,
+    files: Option<String>,
+}
+
+/// Builder of sync database archives, the equivalent of `repo-add`.
+///
+/// Each package becomes a `<name>-<version>/desc` entry of the `.db` archive. The `.files` archive
+/// additionally contains `<name>-<version>/files` entries. Adding a package replaces the previous
+/// version of the package of the same name. Entries are written sorted by names with zeroed timestamps
+/// so that the same packages always produce the same archives.
+#[derive(Debug, Default, Clone)]
+pub struct SyncDbWriter {
+    entries: BTreeMap<String, SyncDbEntry>,
+}
+
+impl SyncDbWriter {
+    /// Create an empty writer.
+    pub fn new() -> Self {
+        SyncDbWriter::default()
+    }
+
+    /// Add a package without a file list.
+    pub fn insert<'a>(&mut self, querier: &impl Query<'a>) -> Result<(), InsertEntryError> {
+        self.insert_querier(querier, None)
+    }
+
+    /// Add a package with the list of files it owns.
+    pub fn insert_with_files<'a>(
+        &mut self,
+        querier: &impl Query<'a>,
+        files: FileList,
+    ) -> Result<(), InsertEntryError> {
+        self.insert_querier(querier, Some(files_text(files)))
+    }
+
+    /// Add an owned package description without a file list.
+    pub fn insert_desc(&mut self, desc: &PackageDesc) -> Result<(), InsertEntryError> {
+        self.insert_package_desc(desc, None)
+    }
+
+    /// Add an owned package description with the list of files it owns.
+    pub fn insert_desc_with_files(
+        &mut self,
+        desc: &PackageDesc,
+        files: FileList,
+    ) -> Result<(), InsertEntryError> {
+        self.insert_package_desc(desc, Some(files_text(files)))
+    }
+
+    /// Add a package from a querier.
+    fn insert_querier<'a>(
+        &mut self,
+        querier: &impl Query<'a>,
+        files: Option<String>,
+    ) -> Result<(), InsertEntryError> {
+        let name = querier.name().map(|name| name.as_str());
+        let version = querier.version().map(|version| version.as_str());
+        self.insert_entry(name, version, desc_text(querier), files)
+    }
+
+    /// Add a package from an owned package description.
+    fn insert_package_desc(
+        &mut self,
+        desc: &PackageDesc,
+        files: Option<String>,
+    ) -> Result<(), InsertEntryError> {
+        let name = desc.name.as_deref();
+        let version = desc.version.as_deref();
+        self.insert_entry(name, version, desc.to_string(), files)
+    }
+
+    /// Add a package.
+    fn insert_entry(
+        &mut self,
+        name: Option<&str>,
+        version: Option<&str>,
+        desc: String,
+        files: Option<String>,
+    ) -> Result<(), InsertEntryError> {
+        let name = name.ok_or(InsertEntryError::MissingName)?;
+        let version = version.ok_or(InsertEntryError::MissingVersion)?;
+        let entry = SyncDbEntry {
+            directory: format!("{name}-{version}"),
+            desc,
+            files,
+        };
+        self.entries.insert(name.to_string(), entry);
+        Ok(())
+    }
+
+    /// Remove a package by name, the equivalent of `repo-remove`.
+    ///
+    /// Returns `false` if the package doesn't exist.
+    pub fn remove(&mut self, name: &str) -> bool {
+        self.entries.remove(name).is_some()
+    }
+
+    /// Number of packages.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether there are no packages.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Write the `.db` archive, which only contains `desc` files.
+    pub fn write_db<Writer: Write>(
+        &self,
+        writer: Writer,
+        compression: Compression,
+    ) -> Result<Writer, WriteArchiveError> {
+        let tar = self.tar(false).map_err(WriteArchiveError::Tar)?;
+        compress(&tar, writer, compression)
+    }
+
+    /// Write the `.files` archive, which contains both `desc` and `files` files.
+    ///
+    /// Packages added without file lists get empty `%FILES%` sections.
+    pub fn write_files_db<Writer: Write>(
+        &self,
+        writer: Writer,
+        compression: Compression,
+    ) -> Result<Writer, WriteArchiveError> {
+        let tar = self.tar(true).map_err(WriteArchiveError::Tar)?;
+        compress(&tar, writer, compression)
+    }
+
+    /// Create an uncompressed tar archive.
+    fn tar(&self, with_files: bool) -> io::Result<Vec<u8>> {
+        let mut builder = Builder::new(Vec::new());
+
+        for entry in self.entries.values() {
+            let mut header = Header::new_gnu();
+            header.set_entry_type(EntryType::Directory);
+            header.set_mode(0o755);
+            header.set_size(0);
+            header.set_mtime(0);
+            builder.append_data(&mut header, format!("{}/", entry.directory), io::empty())?;
+
+            append_file(&mut builder, &entry.directory, "desc", &entry.desc)?;
+            if with_files {
+                let files = entry.files.as_deref().unwrap_or("%FILES%\n");
+                append_file(&mut builder, &entry.directory, "files", files)?;
+            }
+        }
+
+        builder.into_inner()
+    }
+}
+
+/// Append a regular file to a tar archive.
+fn append_file(
+    builder: &mut Builder<Vec<u8>>,
+    directory: &str,
+    name: &str,
+    content: &str,
+) -> io::Result<()> {
+    let mut header = Header::new_gnu();
+    header.set_entry_type(EntryType::Regular);
+    header.set_mode(0o644);
+    header.set_size(content.len() as u64);
+    header.set_mtime(0);
+    builder.append_data(
+        &mut header,
+        format!("{directory}/{name}"),
+        content.as_bytes(),
+    )
+}
@@ -0,0 +1,255 @@
+pub mod _utils;
+
+use _utils::{
+    MULTI_TEXTS,
+    desc::desc,
+    fixtures::archives::official::{TAR, TZST},
+};
+use arch_pkg_db::{
+    BorrowedTextCollection, EagerMultiQueryDatabase, EagerQueryDatabase, MemoQueryDatabase,
+    MultiTextCollection, Text, TextCollection,
+    architecture::ArchitectureFilter,
+    desc::EagerQuerier,
+    value::{Architecture, Name, RepositoryName},
+};
+use itertools::Itertools;
+use pretty_assertions::assert_eq;
+
+fn alarm_texts() -> TextCollection {
+    [
+        desc("bash", "5.2.026-3")
+            .architectures(&["aarch64"])
+            .description("bash"),
+        desc("bash-completion", "2.14.0-2")
+            .architectures(&["any"])
+            .description("bash-completion"),
+        desc("fat-binary", "1.0-1")
+            .architectures(&["aarch64", "x86_64"])
+            .description("fat-binary"),
+        // an empty `%ARCH%` section
+        desc("no-arch", "1.0-1")
+            .field("ARCH", &[""])
+            .description("no-arch"),
+        desc("unknown", "1.0-1"),
+    ]
+    .into_iter()
+    .map(|desc| Text::from(desc.build()))
+    .collect()
+}
+
+fn multi_texts() -> MultiTextCollection<'static> {
+    MULTI_TEXTS
+        .clone()
+        .add_collection(RepositoryName("alarm"), alarm_texts())
+}
+
+#[test]
+fn filter() {
+    let filter = ArchitectureFilter::new(Architecture("aarch64"));
+    assert_eq!(filter.target().as_str(), "aarch64");
+    assert!(filter.accepts("aarch64"));
+    assert!(filter.accepts("any"));
+    assert!(!filter.accepts("x86_64"));
+
+    let texts = alarm_texts();
+    let accepted: Vec<_> = texts
+        .iter()
+        .map(Text::as_str)
+        .map(|text| filter.matches_text(text))
+        .collect();
+    assert_eq!(accepted, [true, true, true, true, true]);
+
+    let filter = ArchitectureFilter::new(Architecture("x86_64"));
+    let accepted: Vec<_> = texts
+        .iter()
+        .map(|text| {
+            let querier: EagerQuerier = text.as_str().try_into().unwrap();
+            (filter.matches_text(text.as_str()), filter.matches(&querier))
+        })
+        .collect();
+    assert_eq!(
+        accepted,
+        [
+            (false, false),
+            (true, true),
+            (true, true),
+            (true, true),
+            (true, true),
+        ],
+    );
+}
+
+#[test]
+fn single_view() {
+    let texts = alarm_texts();
+    let db: EagerQueryDatabase = texts.parse().unwrap();
+    let view = db.for_architecture(Architecture("x86_64"));
+    assert!(view.get(Name("bash")).is_none());
+    assert!(view.get(Name("fat-binary")).is_some());
+    assert_eq!(
+        view.names().sorted().collect::<Vec<_>>(),
+        [
+            Name("bash-completion"),
+            Name("fat-binary"),
+            Name("no-arch"),
+            Name("unknown"),
+        ],
+    );
+    assert_eq!(view.queriers().count(), 4);
+    assert_eq!(db.len(), 5);
+}
+
+#[test]
+fn single_retain() {
+    let texts = alarm_texts();
+    let mut db: EagerQueryDatabase = texts.parse().unwrap();
+    db.retain_architecture(Architecture("x86_64"));
+    assert_eq!(db.len(), 4);
+    assert!(db.get(Name("bash")).is_none());
+
+    let mut db: MemoQueryDatabase = texts.parse_mut().unwrap();
+    db.retain_architecture_mut(Architecture("i686"));
+    assert_eq!(
+        db.names().sorted().collect::<Vec<_>>(),
+        [Name("bash-completion"), Name("no-arch"), Name("unknown")],
+    );
+}
+
+#[test]
+fn multi_view() {
+    let texts = multi_texts();
+    let db: EagerMultiQueryDatabase = texts.parse().unwrap();
+
+    let view = db.for_architecture(Architecture("aarch64"));
+    let bash: Vec<_> = view
+        .get(Name("bash"))
+        .map(|(repository, _)| repository.as_str())
+        .collect();
+    assert_eq!(bash, ["alarm"]);
+    let (repository, _) = view.latest(Name("bash-completion")).unwrap();
+    assert_eq!(repository, RepositoryName("alarm"));
+    assert!(view.get(Name("glibc")).next().is_none());
+
+    let view = db.for_architecture(Architecture("x86_64"));
+    let bash: Vec<_> = view
+        .get(Name("bash"))
+        .map(|(repository, _)| repository.as_str())
+        .collect();
+    assert_eq!(bash, ["core"]);
+    let alarm: Vec<_> = view
+        .entries()
+        .filter(|(_, repository, _)| repository.as_str() == "alarm")
+        .map(|(name, _, _)| name.as_str())
+        .sorted()
+        .collect();
+    assert_eq!(
+        alarm,
+        ["bash-completion", "fat-binary", "no-arch", "unknown"]
+    );
+}
+
+#[test]
+fn multi_retain() {
+    let texts = multi_texts();
+    let mut db: EagerMultiQueryDatabase = texts.parse().unwrap();
+    db.retain_architecture(Architecture("aarch64"));
+    let mut entries: Vec<_> = db
+        .entries()
+        .flat_map(|(name, querier)| {
+            querier
+                .entries()
+                .map(move |(repository, _)| (name.as_str(), repository.as_str()))
+        })
+        .collect();
+    entries.sort();
+    assert_eq!(
+        entries,
+        [
+            ("bash", "alarm"),
+            ("bash-completion", "alarm"),
+            ("bash-completion", "extra"),
+            ("fat-binary", "alarm"),
+            ("no-arch", "alarm"),
+            ("unknown", "alarm"),
+        ],
+    );
+}
+
+#[test]
+fn skip_at_parse_time() {
+    let mut texts = multi_texts();
+    texts.retain_architecture(Architecture("aarch64"));
+    let db: EagerMultiQueryDatabase = texts.parse().unwrap();
+    assert_eq!(db.len(), 5);
+    assert!(db.get(Name("glibc")).is_none());
+
+    let mut texts = TextCollection::from_tar(TAR.as_slice()).unwrap();
+    texts.retain_architecture(Architecture("aarch64"));
+    let db: EagerQueryDatabase = texts.parse().unwrap();
+    assert_eq!(db.names().collect::<Vec<_>>(), [Name("bash-completion")],);
+
+    let mut texts = BorrowedTextCollection::from_tar(&TAR).unwrap();
+    texts.retain_architecture(Architecture("x86_64"));
+    assert_eq!(texts.len(), 9);
+    texts.retain_architecture(Architecture("i686"));
+    assert_eq!(texts.len(), 1);
+}
+
+#[test]
+fn skip_at_extraction_time() {
+    let texts =
+        TextCollection::from_archive_for_architecture(TZST.as_slice(), Architecture("aarch64"))
+            .unwrap();
+    let db: EagerQueryDatabase = texts.parse().unwrap();
+    assert_eq!(db.names().collect::<Vec<_>>(), [Name("bash-completion")]);
+
+    let mut expected = TextCollection::from_archive(&TZST).unwrap();
+    expected.retain_architecture(Architecture("x86_64"));
+    let texts =
+        TextCollection::from_archive_for_architecture(TZST.as_slice(), Architecture("x86_64"))
+            .unwrap();
+    assert_eq!(
+        texts.iter().map(|text| text.as_str()).collect::<Vec<_>>(),
+        expected
+            .iter()
+            .map(|text| text.as_str())
+            .collect::<Vec<_>>(),
+    );
+
+    let texts = MultiTextCollection::new()
+        .add_archive_for_architecture(
+            RepositoryName("core"),
+            TZST.as_slice(),
+            Architecture("i686"),
+        )
+        .unwrap();
+    let db: EagerMultiQueryDatabase = texts.parse().unwrap();
+    assert_eq!(db.len(), 1);
+
+    let texts = MultiTextCollection::from_archive_for_architecture(
+        RepositoryName("core"),
+        TZST.as_slice(),
+        Architecture("aarch64"),
+    )
+    .unwrap();
+    let db: EagerMultiQueryDatabase = texts.parse().unwrap();
+    assert_eq!(db.names().collect::<Vec<_>>(), [Name("bash-completion")]);
+}
+
+#[test]
+fn multi_view_latest_tie() {
+    let texts: TextCollection = [desc("tie", "1.0-1").architectures(&["any"])]
+        .into_iter()
+        .map(|desc| Text::from(desc.build()))
+        .collect();
+    let multi_texts = MultiTextCollection::new()
+        .add_collection(RepositoryName("zeta"), texts.clone())
+        .add_collection(RepositoryName("beta"), texts.clone())
+        .add_collection(RepositoryName("alpha"), texts);
+    for _ in 0..16 {
+        let db: EagerMultiQueryDatabase = multi_texts.parse().unwrap();
+        let view = db.for_architecture(Architecture("x86_64"));
+        let (repository, _) = view.latest(Name("tie")).unwrap();
+        assert_eq!(repository, RepositoryName("alpha"));
+    }
+}
@@ -1,13 +1,16 @@
-use _utils::fixtures::archives::official::{TAR, TGZ, TXZ};
+use _utils::fixtures::archives::official::{TAR, TBZ2, TGZ, TLZ4, TXZ, TZST};
 use arch_pkg_db::{
     EagerQueryDatabase, TextCollection,
     desc::Query,
     single::Entry,
+    text::archive::{LoadArchiveError, LoadGzError, LoadXzError},
     value::{Description, Name},
 };
 use itertools::Itertools;
+use lzma_rust2::{XzOptions, XzWriter};
 use pipe_trait::Pipe;
 use pretty_assertions::assert_eq;
+use std::io::{self, Read};
 
 fn assert_official_db(queriers: &EagerQueryDatabase<'_>) {
     dbg!(&queriers);
@@ -84,6 +87,27 @@ fn txz() {
     assert_official_db(&queriers);
 }
 
+#[test]
+fn tzst() {
+    let texts = TZST.as_slice().pipe(TextCollection::from_zst).unwrap();
+    let queriers: EagerQueryDatabase<'_> = texts.parse().unwrap();
+    assert_official_db(&queriers);
+}
+
+#[test]
+fn tbz2() {
+    let texts = TBZ2.as_slice().pipe(TextCollection::from_bz2).unwrap();
+    let queriers: EagerQueryDatabase<'_> = texts.parse().unwrap();
+    assert_official_db(&queriers);
+}
+
+#[test]
+fn tlz4() {
+    let texts = TLZ4.as_slice().pipe(TextCollection::from_lz4).unwrap();
+    let queriers: EagerQueryDatabase<'_> = texts.parse().unwrap();
+    assert_official_db(&queriers);
+}
+
 #[test]
 fn detect() {
     eprintln!("CASE: tar");
@@ -100,4 +124,145 @@ fn detect() {
     let texts = TXZ.as_slice().pipe(TextCollection::from_archive).unwrap();
     let queriers: EagerQueryDatabase<'_> = texts.parse().unwrap();
     assert_official_db(&queriers);
+
+    eprintln!("CASE: tzst");
+    let texts = TZST.as_slice().pipe(TextCollection::from_archive).unwrap();
+    let queriers: EagerQueryDatabase<'_> = texts.parse().unwrap();
+    assert_official_db(&queriers);
+
+    eprintln!("CASE: tbz2");
+    let texts = TBZ2.as_slice().pipe(TextCollection::from_archive).unwrap();
+    let queriers: EagerQueryDatabase<'_> = texts.parse().unwrap();
+    assert_official_db(&queriers);
+
+    eprintln!("CASE: tlz4");
+    let texts = TLZ4.as_slice().pipe(TextCollection::from_archive).unwrap();
+    let queriers: EagerQueryDatabase<'_> = texts.parse().unwrap();
+    assert_official_db(&queriers);
+}
+
+/// Reader which yields at most a few bytes at a time, like a slow network stream.
+struct Trickle<'a>(&'a [u8]);
+
+impl Read for Trickle<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let len = self.0.len().min(buf.len()).min(3);
+        let (head, tail) = self.0.split_at(len);
+        buf[..len].copy_from_slice(head);
+        self.0 = tail;
+        Ok(len)
+    }
+}
+
+#[test]
+fn detect_reader() {
+    let cases = [
+        ("tar", &TAR),
+        ("tgz", &TGZ),
+        ("txz", &TXZ),
+        ("tzst", &TZST),
+        ("tbz2", &TBZ2),
+        ("tlz4", &TLZ4),
+    ];
+    for (name, archive) in cases {
+        eprintln!("CASE: {name}");
+        let texts = archive
+            .as_slice()
+            .pipe(Trickle)
+            .pipe(TextCollection::from_archive_reader)
+            .unwrap();
+        let queriers: EagerQueryDatabase<'_> = texts.parse().unwrap();
+        assert_official_db(&queriers);
+    }
+}
+
+#[test]
+fn detect_reader_unsupported() {
+    let error = b"not an archive at all"
+        .as_slice()
+        .pipe(TextCollection::from_archive_reader);
+    assert!(matches!(
+        error,
+        Err(LoadArchiveError::GetMime | LoadArchiveError::UnsupportedMimeType(_)),
+    ));
+}
+
+#[test]
+fn detect_internal_archive_unsupported() {
+    let mut encoder = libflate::gzip::Encoder::new(Vec::new()).unwrap();
+    io::Write::write_all(&mut encoder, b"not a tar archive").unwrap();
+    let gzip = encoder.finish().into_result().unwrap();
+    let error = gzip.as_slice().pipe(TextCollection::from_archive_reader);
+    assert!(matches!(error, Err(LoadArchiveError::InternalArchive(_))));
+}
+
+#[test]
+fn txz_corrupted() {
+    let mut archive = TXZ.to_vec();
+    archive[6..12].fill(0xFF);
+    let error = archive.as_slice().pipe(TextCollection::from_archive_reader);
+    let Err(LoadArchiveError::Xz(error)) = error else {
+        panic!("expecting an xz error, found {error:?}");
+    };
+    assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+}
+
+/// Create a tar archive of `desc` files whose contents barely compress, so that the compressed
+/// archive is much larger than the leading bytes read ahead for mime type detection.
+fn large_tar() -> Vec<u8> {
+    let mut state = 0x2545_F491_4F6C_DD1D_u64;
+    let mut builder = tar::Builder::new(Vec::new());
+    for index in 0..64 {
+        let mut text = format!("%NAME%\npackage-{index}\n\n%VERSION%\n1.0-1\n\n%DESC%\n");
+        for _ in 0..4096 {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            text += &format!("{:02x}", state as u8);
+        }
+        text.push('\n');
+        let mut header = tar::Header::new_gnu();
+        header.set_size(text.len() as u64);
+        header.set_mode(0o644);
+        builder
+            .append_data(
+                &mut header,
+                format!("package-{index}/desc"),
+                text.as_bytes(),
+            )
+            .unwrap();
+    }
+    builder.into_inner().unwrap()
+}
+
+#[test]
+fn tgz_truncated_after_read_ahead() {
+    // stored blocks let the decoder yield data before it reaches the truncation
+    let options = libflate::gzip::EncodeOptions::new().no_compression();
+    let mut encoder = libflate::gzip::Encoder::with_options(Vec::new(), options).unwrap();
+    io::Write::write_all(&mut encoder, &large_tar()).unwrap();
+    let mut archive = encoder.finish().into_result().unwrap();
+    archive.truncate(archive.len() / 2);
+    assert!(archive.len() > 128 * 1024);
+
+    let error = archive.as_slice().pipe(TextCollection::from_archive_reader);
+    assert!(matches!(error, Err(LoadArchiveError::Gzip(_))), "{error:?}");
+
+    let error = archive.as_slice().pipe(TextCollection::from_gz);
+    assert!(matches!(error, Err(LoadGzError::Gzip(_))), "{error:?}");
+}
+
+#[test]
+fn txz_truncated_after_read_ahead() {
+    let mut writer = XzWriter::new(Vec::new(), XzOptions::with_preset(1)).unwrap();
+    io::Write::write_all(&mut writer, &large_tar()).unwrap();
+    let mut archive = writer.finish().unwrap();
+    archive.truncate(archive.len() / 2);
+    assert!(archive.len() > 128 * 1024);
+
+    let error = archive.as_slice().pipe(TextCollection::from_archive_reader);
+    assert!(matches!(error, Err(LoadArchiveError::Xz(_))), "{error:?}");
+
+    let error = archive.as_slice().pipe(TextCollection::from_xz);
+    assert!(matches!(error, Err(LoadXzError::Xz(_))), "{error:?}");
 }
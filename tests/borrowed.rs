@@ -0,0 +1,66 @@
+pub mod _utils;
+
+use _utils::fixtures::archives::official::{DB_TEXTS, TAR, TGZ};
+use arch_pkg_db::{
+    BorrowedTextCollection, EagerQueryDatabase, MemoQueryDatabase, TextCollection,
+    desc::{Query, QueryMut},
+    text::LoadBorrowedTarError,
+    value::{Description, Name},
+};
+use itertools::Itertools;
+use pretty_assertions::assert_eq;
+
+#[test]
+fn borrow_from_tar() {
+    let texts = BorrowedTextCollection::from_tar(&TAR).unwrap();
+    assert_eq!(texts.len(), 9);
+
+    let range = TAR.as_ptr_range();
+    for text in texts.iter() {
+        assert!(range.contains(&text.as_ptr()), "{text:?} is not borrowed");
+    }
+
+    let expected: Vec<_> = DB_TEXTS.iter().copied().sorted().collect();
+    let received: Vec<_> = texts.iter().sorted().collect();
+    assert_eq!(received, expected);
+}
+
+#[test]
+fn parse_same_as_owned() {
+    let borrowed = BorrowedTextCollection::from_tar(&TAR).unwrap();
+    let owned = TextCollection::from_tar(TAR.as_slice()).unwrap();
+
+    let borrowed_db: EagerQueryDatabase = borrowed.par_parse().unwrap();
+    let owned_db: EagerQueryDatabase = owned.parse().unwrap();
+    assert_eq!(
+        borrowed_db.names().sorted().collect::<Vec<_>>(),
+        owned_db.names().sorted().collect::<Vec<_>>(),
+    );
+    for (name, querier) in owned_db.entries().map(|entry| entry.into_tuple()) {
+        let received = borrowed_db.get(name).unwrap();
+        assert_eq!(received.description(), querier.description());
+        assert_eq!(
+            received.version().map(|version| version.as_str()),
+            querier.version().map(|version| version.as_str()),
+        );
+    }
+}
+
+#[test]
+fn database_outlives_collection() {
+    let mut db: MemoQueryDatabase = BorrowedTextCollection::from_tar(&TAR)
+        .unwrap()
+        .parse_mut()
+        .unwrap();
+    let querier = db.get_mut(Name("bash")).unwrap();
+    assert_eq!(
+        querier.description_mut(),
+        Some(Description("The GNU Bourne Again shell")),
+    );
+}
+
+#[test]
+fn compressed_archive() {
+    let error = BorrowedTextCollection::from_tar(&TGZ).unwrap_err();
+    assert!(matches!(error, LoadBorrowedTarError::Tar(_)), "{error:?}");
+}
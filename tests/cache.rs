@@ -0,0 +1,196 @@
+pub mod _utils;
+
+use _utils::{
+    fixtures::archives::official::{TGZ, TXZ, TZST},
+    temp::Temp,
+};
+use arch_pkg_db::{
+    EagerMultiQueryDatabase,
+    cache::{DecodeCacheError, SnapshotCache},
+    value::{Name, RepositoryName},
+};
+use pretty_assertions::assert_eq;
+use std::{
+    fs::{self, File},
+    path::Path,
+    time::{Duration, SystemTime},
+};
+
+fn package_count(cache: &SnapshotCache) -> usize {
+    let texts = cache.to_multi_text_collection();
+    let db: EagerMultiQueryDatabase = texts.parse().unwrap();
+    db.names().count()
+}
+
+fn set_modified(path: &Path, time: SystemTime) {
+    File::options()
+        .write(true)
+        .open(path)
+        .unwrap()
+        .set_modified(time)
+        .unwrap();
+}
+
+#[test]
+fn build_load_and_refresh() {
+    let root = Temp::new("arch-pkg-db-cache-");
+    let core = root.join("core.db");
+    let extra = root.join("extra.db");
+    let cache_path = root.join("sync.cache");
+    fs::write(&core, TGZ.as_slice()).unwrap();
+    fs::write(&extra, TZST.as_slice()).unwrap();
+    let sources = [
+        (RepositoryName("core"), core.as_path()),
+        (RepositoryName("extra"), extra.as_path()),
+    ];
+
+    let built = SnapshotCache::load_or_build(&cache_path, &sources).unwrap();
+    assert!(cache_path.exists());
+    assert!(built.is_fresh());
+    assert_eq!(built.repositories().count(), 2);
+    assert_eq!(package_count(&built), 9);
+    let core_cache = built.get(RepositoryName("core")).unwrap();
+    assert_eq!(core_cache.stamp().size(), TGZ.len() as u64);
+    assert_eq!(core_cache.source(), core);
+
+    let loaded = SnapshotCache::load(&cache_path).unwrap();
+    assert_eq!(loaded.to_bytes(), built.to_bytes());
+    let db_texts = loaded.to_multi_text_collection();
+    let db: EagerMultiQueryDatabase = db_texts.parse().unwrap();
+    let bash = db.get(Name("bash")).unwrap();
+    assert!(bash.get(RepositoryName("core")).is_some());
+    assert!(bash.get(RepositoryName("extra")).is_some());
+
+    // a new modification time with the same content keeps the cache fresh
+    set_modified(&core, SystemTime::now() + Duration::from_secs(60));
+    assert!(loaded.is_fresh());
+
+    // a different content invalidates the cache
+    fs::write(&core, TXZ.as_slice()).unwrap();
+    assert!(!loaded.is_fresh());
+    let mut refreshed = loaded;
+    assert_eq!(refreshed.refresh().unwrap(), 1);
+    assert!(refreshed.is_fresh());
+    assert_eq!(
+        refreshed
+            .get(RepositoryName("core"))
+            .unwrap()
+            .stamp()
+            .size(),
+        TXZ.len() as u64,
+    );
+
+    let reloaded = SnapshotCache::load_or_build(&cache_path, &sources).unwrap();
+    assert_eq!(reloaded.to_bytes(), refreshed.to_bytes());
+    assert_eq!(
+        SnapshotCache::load(&cache_path).unwrap().to_bytes(),
+        refreshed.to_bytes(),
+    );
+}
+
+#[test]
+fn take_texts() {
+    let root = Temp::new("arch-pkg-db-cache-");
+    let core = root.join("core.db");
+    fs::write(&core, TGZ.as_slice()).unwrap();
+    let mut cache = SnapshotCache::new()
+        .add_archive(RepositoryName("core"), &core)
+        .unwrap();
+    let expected = package_count(&cache);
+
+    let texts = cache.take_multi_text_collection();
+    let db: EagerMultiQueryDatabase = texts.parse().unwrap();
+    assert_eq!(db.names().count(), expected);
+    assert!(db.get(Name("bash")).is_some());
+
+    let core_cache = cache.get(RepositoryName("core")).unwrap();
+    assert!(core_cache.texts().is_empty());
+    assert!(!core_cache.is_fresh());
+    assert!(!cache.is_fresh());
+
+    // the emptied repository isn't trusted after being saved and loaded either
+    let cache_path = root.join("sync.cache");
+    cache.save(&cache_path).unwrap();
+    let sources = [(RepositoryName("core"), core.as_path())];
+    let reloaded = SnapshotCache::load_or_build(&cache_path, &sources).unwrap();
+    assert!(reloaded.is_fresh());
+    assert_eq!(package_count(&reloaded), expected);
+
+    assert_eq!(cache.refresh().unwrap(), 1);
+    assert!(cache.is_fresh());
+    assert_eq!(package_count(&cache), expected);
+}
+
+#[test]
+fn touch_updates_modified() {
+    let root = Temp::new("arch-pkg-db-cache-");
+    let core = root.join("core.db");
+    let cache_path = root.join("sync.cache");
+    fs::write(&core, TGZ.as_slice()).unwrap();
+    let sources = [(RepositoryName("core"), core.as_path())];
+    let built = SnapshotCache::load_or_build(&cache_path, &sources).unwrap();
+
+    let touched = SystemTime::now() + Duration::from_secs(60);
+    set_modified(&core, touched);
+    let modified = touched.duration_since(SystemTime::UNIX_EPOCH).unwrap();
+    let stamp = |cache: &SnapshotCache| *cache.get(RepositoryName("core")).unwrap().stamp();
+    assert_ne!(stamp(&built).modified(), Some(modified));
+
+    let reloaded = SnapshotCache::load_or_build(&cache_path, &sources).unwrap();
+    assert_eq!(stamp(&reloaded).modified(), Some(modified));
+    assert_eq!(stamp(&reloaded).sha256(), stamp(&built).sha256());
+    assert_eq!(package_count(&reloaded), package_count(&built));
+    assert_eq!(
+        stamp(&SnapshotCache::load(&cache_path).unwrap()),
+        stamp(&reloaded),
+    );
+}
+
+#[test]
+fn decode_errors() {
+    assert_eq!(
+        SnapshotCache::from_bytes(b"not a cache at all").unwrap_err(),
+        DecodeCacheError::InvalidMagic,
+    );
+
+    let bytes = SnapshotCache::new().to_bytes();
+    assert_eq!(
+        SnapshotCache::from_bytes(&bytes[..bytes.len() - 1]).unwrap_err(),
+        DecodeCacheError::UnexpectedEnd,
+    );
+
+    let mut versioned = bytes.clone();
+    versioned[16..20].copy_from_slice(&999u32.to_le_bytes());
+    assert_eq!(
+        SnapshotCache::from_bytes(&versioned).unwrap_err(),
+        DecodeCacheError::UnsupportedVersion(999),
+    );
+
+    let mut trailing = bytes;
+    trailing.push(0);
+    assert_eq!(
+        SnapshotCache::from_bytes(&trailing).unwrap_err(),
+        DecodeCacheError::TrailingBytes,
+    );
+}
+
+#[test]
+fn decode_invalid_nanoseconds() {
+    let root = Temp::new("arch-pkg-db-cache-");
+    let core = root.join("core.db");
+    fs::write(&core, TGZ.as_slice()).unwrap();
+    let mut bytes = SnapshotCache::new()
+        .add_archive(RepositoryName("core"), &core)
+        .unwrap()
+        .to_bytes();
+
+    // magic, version, repository count, name, source path, size, and the modification time flag
+    let source = core.to_string_lossy();
+    let offset = 16 + 4 + 8 + (8 + "core".len()) + (8 + source.len()) + 8 + 1;
+    bytes[offset..offset + 8].copy_from_slice(&u64::MAX.to_le_bytes());
+    bytes[offset + 8..offset + 12].copy_from_slice(&u32::MAX.to_le_bytes());
+    assert_eq!(
+        SnapshotCache::from_bytes(&bytes).unwrap_err(),
+        DecodeCacheError::InvalidNanoseconds(u32::MAX),
+    );
+}
@@ -0,0 +1,191 @@
+use _utils::temp::Temp;
+use arch_pkg_db::{
+    MultiTextCollection,
+    config::{PacmanConf, ParsePacmanConfErrorKind},
+    desc::EagerQuerier,
+    value::{Name, RepositoryName},
+};
+use itertools::Itertools;
+use pretty_assertions::assert_eq;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+fn repositories<'a>(multi_collection: &'a MultiTextCollection<'a>) -> Vec<&'a str> {
+    multi_collection
+        .iter()
+        .map(|(repository, _)| repository.as_str())
+        .dedup()
+        .collect()
+}
+
+#[test]
+fn read() {
+    let root = Temp::pacman_root();
+    let conf = PacmanConf::read(Path::new("/etc/pacman.conf"), &root).unwrap();
+    dbg!(&conf);
+
+    assert_eq!(conf.options().db_path(), Some(Path::new("/custom/db/")));
+    assert_eq!(conf.options().root_dir(), None);
+    assert_eq!(
+        conf.options().architectures().collect::<Vec<_>>(),
+        ["x86_64", "x86_64_v3"],
+    );
+    assert_eq!(conf.db_path(), PathBuf::from("/custom/db/"));
+    assert_eq!(conf.sync_dir(&root), root.join("custom/db/sync"));
+    assert_eq!(conf.local_db(&root), root.join("custom/db/local"));
+
+    assert_eq!(
+        conf.repository_names().collect::<Vec<_>>(),
+        ["personal", "core", "extra", "derivative"].map(RepositoryName),
+    );
+
+    let servers = |name| -> Vec<_> {
+        conf.repository(RepositoryName(name))
+            .unwrap()
+            .servers()
+            .collect()
+    };
+    assert_eq!(servers("personal"), ["file:///srv/repo/personal"]);
+    assert_eq!(
+        servers("core"),
+        [
+            "https://geo.mirror.pkgbuild.com/$repo/os/$arch",
+            "https://mirror.rackspace.com/archlinux/$repo/os/$arch",
+        ],
+    );
+    assert_eq!(servers("derivative"), ["https://example.com/$repo/$arch"]);
+    assert!(conf.repository(RepositoryName("multilib")).is_none());
+}
+
+#[test]
+fn db_path_defaults() {
+    let parse = |text: &str| PacmanConf::parse(text, Path::new("pacman.conf"), Path::new("/"));
+
+    let conf = parse("[options]\n[core]\n").unwrap();
+    assert_eq!(conf.db_path(), PathBuf::from("/var/lib/pacman/"));
+    assert_eq!(
+        conf.sync_dir(Path::new("/mnt")),
+        PathBuf::from("/mnt/var/lib/pacman/sync"),
+    );
+
+    let conf = parse("[options]\nRootDir = /chroot\n").unwrap();
+    assert_eq!(conf.db_path(), PathBuf::from("/chroot/var/lib/pacman/"));
+}
+
+#[test]
+fn syntax_errors() {
+    let parse = |text: &str| PacmanConf::parse(text, Path::new("pacman.conf"), Path::new("/"));
+
+    let error = parse("# comment\nDBPath = /db\n").unwrap_err();
+    assert!(matches!(error.kind(), ParsePacmanConfErrorKind::NoSection));
+    assert_eq!(error.line(), 2);
+    assert_eq!(
+        error.to_string(),
+        "pacman.conf:2: Directive doesn't belong to any section"
+    );
+
+    let error = parse("[options\n").unwrap_err();
+    assert!(matches!(
+        error.kind(),
+        ParsePacmanConfErrorKind::UnterminatedSection,
+    ));
+
+    let error = parse("[options]\nDBPath\n").unwrap_err();
+    assert!(matches!(
+        error.kind(),
+        ParsePacmanConfErrorKind::MissingValue(key) if key == "DBPath",
+    ));
+}
+
+#[test]
+fn missing_include() {
+    let root = Temp::pacman_root();
+    let text = "[core]\nInclude = /etc/pacman.d/not-exist\n";
+    let conf = PacmanConf::parse(text, Path::new("pacman.conf"), &root).unwrap();
+    assert_eq!(
+        conf.repository(RepositoryName("core"))
+            .unwrap()
+            .servers()
+            .count(),
+        0
+    );
+}
+
+#[test]
+fn include_under_root_with_wildcards() {
+    let root = Temp::new("arch-pkg-db-config-[root]*?-");
+    let mirrorlist_dir = root.join("etc/pacman.d");
+    fs::create_dir_all(&mirrorlist_dir).unwrap();
+    fs::write(
+        mirrorlist_dir.join("mirrorlist"),
+        "Server = https://example.com/$repo/$arch\n",
+    )
+    .unwrap();
+    let text = "[core]\nInclude = /etc/pacman.d/*\n";
+    let conf = PacmanConf::parse(text, Path::new("pacman.conf"), &root).unwrap();
+    assert_eq!(
+        conf.repository(RepositoryName("core"))
+            .unwrap()
+            .servers()
+            .collect::<Vec<_>>(),
+        ["https://example.com/$repo/$arch"],
+    );
+}
+
+#[test]
+fn from_pacman_conf() {
+    let root = Temp::pacman_root();
+    let conf = PacmanConf::read(Path::new("/etc/pacman.conf"), &root).unwrap();
+
+    let multi_collection = MultiTextCollection::from_pacman_conf(&conf, &root).unwrap();
+    assert_eq!(
+        repositories(&multi_collection),
+        ["personal", "core", "extra", "derivative"],
+    );
+    let db = multi_collection.parse::<EagerQuerier>().unwrap();
+    assert!(db.get(Name("bash")).is_some());
+
+    let multi_collection = MultiTextCollection::par_from_pacman_conf(&conf, &root).unwrap();
+    assert_eq!(
+        repositories(&multi_collection),
+        ["personal", "core", "extra", "derivative"],
+    );
+}
+
+#[test]
+fn from_pacman_conf_missing_db() {
+    let root = Temp::pacman_root();
+    let text = "[options]\nDBPath = /custom/db/\n[core]\n[multilib]\n";
+    let conf = PacmanConf::parse(text, Path::new("pacman.conf"), &root).unwrap();
+    let error = MultiTextCollection::from_pacman_conf(&conf, &root).unwrap_err();
+    assert_eq!(error.path(), root.join("custom/db/sync/multilib.db"));
+}
+
+#[test]
+fn from_pacman_conf_skip_missing() {
+    let root = Temp::pacman_root();
+    let text = "[options]\nDBPath = /custom/db/\n[core]\n[multilib]\n[extra]\n[testing]\n";
+    let conf = PacmanConf::parse(text, Path::new("pacman.conf"), &root).unwrap();
+
+    let mut multi_collection = MultiTextCollection::new();
+    let missing = multi_collection
+        .extend_from_pacman_conf_skip_missing(&conf, &root)
+        .unwrap();
+    assert_eq!(
+        missing,
+        [RepositoryName("multilib"), RepositoryName("testing")]
+    );
+    assert_eq!(repositories(&multi_collection), ["core", "extra"]);
+
+    let mut multi_collection = MultiTextCollection::new();
+    let missing = multi_collection
+        .par_extend_from_pacman_conf_skip_missing(&conf, &root)
+        .unwrap();
+    assert_eq!(
+        missing,
+        [RepositoryName("multilib"), RepositoryName("testing")]
+    );
+    assert_eq!(repositories(&multi_collection), ["core", "extra"]);
+}
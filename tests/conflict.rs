@@ -0,0 +1,189 @@
+pub mod _utils;
+
+use _utils::{desc::desc, fixtures};
+use arch_pkg_db::{
+    EagerQueryDatabase, Text, TextCollection,
+    conflict::{Conflict, ConflictMatch, PackageOrigin, find_conflicts},
+};
+use pretty_assertions::assert_eq;
+
+fn target_texts() -> TextCollection {
+    [
+        Text::from(fixtures::core::BASH),
+        Text::from(fixtures::extra::RUSTUP),
+        Text::from(desc("foo", "1.0-1").conflicts(&["bar>=2", "foo"]).build()),
+    ]
+    .into_iter()
+    .collect()
+}
+
+fn local_texts() -> TextCollection {
+    [
+        desc("rust", "1:1.80.0-1").provides(&["cargo"]),
+        desc("bar", "2.1-1"),
+        desc("baz", "1.0-1").provides(&["bar=2.5"]),
+        desc("old-bar", "1.0-1").provides(&["bar=1.0"]),
+        desc("any-bar", "1.0-1").provides(&["bar"]),
+        desc("qux", "1.0-1").conflicts(&["sh"]),
+        desc("foo", "0.9-1").conflicts(&["rustup"]),
+    ]
+    .into_iter()
+    .map(|desc| Text::from(desc.build()))
+    .collect()
+}
+
+fn summarize<'a>(conflicts: &[Conflict<'a>]) -> Vec<(&'a str, &'a str, String, Option<String>)> {
+    conflicts
+        .iter()
+        .map(|conflict| {
+            let matched = match conflict.matched() {
+                ConflictMatch::Name => None,
+                ConflictMatch::Provision(provision) => Some(provision.to_string()),
+            };
+            (
+                conflict.package().as_str(),
+                conflict.target().as_str(),
+                conflict.constraint().to_string(),
+                matched,
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn targets_against_installed() {
+    let target_texts = target_texts();
+    let targets: EagerQueryDatabase = target_texts.parse().unwrap();
+    let local_texts = local_texts();
+    let local: EagerQueryDatabase = local_texts.parse().unwrap();
+    let conflicts = find_conflicts(targets.queriers(), &local);
+
+    assert_eq!(
+        summarize(&conflicts),
+        [
+            ("foo", "bar", "bar>=2".to_string(), None),
+            (
+                "foo",
+                "baz",
+                "bar>=2".to_string(),
+                Some("bar=2.5".to_string())
+            ),
+            ("qux", "bash", "sh".to_string(), Some("sh".to_string())),
+            ("rustup", "rust", "rust".to_string(), None),
+            (
+                "rustup",
+                "rust",
+                "cargo".to_string(),
+                Some("cargo".to_string())
+            ),
+        ],
+    );
+
+    let origins: Vec<_> = conflicts
+        .iter()
+        .map(|conflict| (conflict.package_origin(), conflict.target_origin()))
+        .collect();
+    assert_eq!(
+        origins,
+        [
+            (PackageOrigin::Target, PackageOrigin::Installed),
+            (PackageOrigin::Target, PackageOrigin::Installed),
+            (PackageOrigin::Installed, PackageOrigin::Target),
+            (PackageOrigin::Target, PackageOrigin::Installed),
+            (PackageOrigin::Target, PackageOrigin::Installed),
+        ],
+    );
+}
+
+#[test]
+fn between_targets() {
+    let target_texts: TextCollection = [
+        Text::from(fixtures::extra::RUST),
+        Text::from(fixtures::extra::RUSTUP),
+    ]
+    .into_iter()
+    .collect();
+    let targets: EagerQueryDatabase = target_texts.parse().unwrap();
+    let local = EagerQueryDatabase::new();
+    let conflicts = find_conflicts(targets.queriers(), &local);
+
+    assert_eq!(
+        summarize(&conflicts),
+        [
+            (
+                "rust",
+                "rustup",
+                "cargo".to_string(),
+                Some("cargo".to_string())
+            ),
+            (
+                "rust",
+                "rustup",
+                "rustfmt".to_string(),
+                Some("rustfmt".to_string())
+            ),
+            ("rustup", "rust", "rust".to_string(), None),
+            (
+                "rustup",
+                "rust",
+                "cargo".to_string(),
+                Some("cargo".to_string())
+            ),
+            (
+                "rustup",
+                "rust",
+                "rustfmt".to_string(),
+                Some("rustfmt".to_string())
+            ),
+        ],
+    );
+    assert!(conflicts.iter().all(|conflict| {
+        conflict.package_origin() == PackageOrigin::Target
+            && conflict.target_origin() == PackageOrigin::Target
+    }));
+}
+
+#[test]
+fn display() {
+    let target_texts = target_texts();
+    let targets: EagerQueryDatabase = target_texts.parse().unwrap();
+    let local_texts = local_texts();
+    let local: EagerQueryDatabase = local_texts.parse().unwrap();
+    let messages: Vec<_> = find_conflicts(targets.queriers(), &local)
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+
+    assert_eq!(
+        messages,
+        [
+            "foo-1.0-1 and bar-2.1-1 are in conflict (bar>=2)",
+            "foo-1.0-1 and baz-1.0-1 are in conflict (bar>=2)",
+            "qux-1.0-1 and bash-5.2.026-2 are in conflict (sh)",
+            "rustup-1.28.2-3 and rust-1:1.80.0-1 are in conflict",
+            "rustup-1.28.2-3 and rust-1:1.80.0-1 are in conflict (cargo)",
+        ],
+    );
+}
+
+#[test]
+fn replaced_installed() {
+    let target_texts: TextCollection = [desc("new-bar", "3.0-1")
+        .conflicts(&["bar", "baz"])
+        .replaces(&["bar", "baz<1"])]
+    .into_iter()
+    .map(|desc| Text::from(desc.build()))
+    .collect();
+    let targets: EagerQueryDatabase = target_texts.parse().unwrap();
+    let local_texts: TextCollection = [desc("bar", "2.1-1"), desc("baz", "1.0-1")]
+        .into_iter()
+        .map(|desc| Text::from(desc.build()))
+        .collect();
+    let local: EagerQueryDatabase = local_texts.parse().unwrap();
+    let conflicts = find_conflicts(targets.queriers(), &local);
+
+    assert_eq!(
+        summarize(&conflicts),
+        [("new-bar", "baz", "baz".to_string(), None)],
+    );
+}
@@ -0,0 +1,349 @@
+pub mod _utils;
+
+use _utils::{MULTI_TEXTS, desc::desc, fixtures};
+use arch_pkg_db::{
+    EagerMultiQueryDatabase, EagerQueryDatabase, MultiTextCollection, Text, TextCollection,
+    desc::FieldName,
+    diff::{DatabaseDiff, MultiDatabaseDiff, PackageEntry, VersionDiff},
+    value::RepositoryName,
+};
+use pretty_assertions::assert_eq;
+
+fn old_texts() -> TextCollection {
+    [
+        desc("foo", "1.0-1")
+            .description("Old foo")
+            .depends(&["bar", "baz>=1"]),
+        desc("bar", "2.0-1"),
+        desc("baz", "1.0-1").provides(&["libbaz.so=1-64"]),
+        desc("gone", "1.0-1"),
+        desc("same", "1.0-1").description("Unchanged"),
+    ]
+    .into_iter()
+    .map(|desc| Text::from(desc.build()))
+    .collect()
+}
+
+fn new_texts() -> TextCollection {
+    [
+        desc("foo", "1.0-2")
+            .description("New foo")
+            .depends(&["baz>=2", "bar"]),
+        desc("bar", "1:1.0-1").field("URL", &["https://example.com"]),
+        desc("baz", "0.9-1").provides(&["libbaz.so=1-64"]),
+        desc("fresh", "0.1-1"),
+        desc("same", "1.0-1").description("Unchanged"),
+    ]
+    .into_iter()
+    .map(|desc| Text::from(desc.build()))
+    .collect()
+}
+
+#[test]
+fn single() {
+    let old_texts = old_texts();
+    let old: EagerQueryDatabase = old_texts.parse().unwrap();
+    let new_texts = new_texts();
+    let new: EagerQueryDatabase = new_texts.parse().unwrap();
+    let diff = DatabaseDiff::new(&old, &new);
+
+    let entries = |packages: &[_]| -> Vec<(String, Option<String>)> {
+        packages
+            .iter()
+            .map(|package: &PackageEntry| {
+                (
+                    package.name().to_string(),
+                    package.version().map(|version| version.to_string()),
+                )
+            })
+            .collect()
+    };
+    assert_eq!(
+        entries(diff.added()),
+        [("fresh".to_string(), Some("0.1-1".to_string()))],
+    );
+    assert_eq!(
+        entries(diff.removed()),
+        [("gone".to_string(), Some("1.0-1".to_string()))],
+    );
+
+    let versions = |changes: &[_]| -> Vec<(String, String, String)> {
+        changes
+            .iter()
+            .map(|change: &VersionDiff| {
+                (
+                    change.name().to_string(),
+                    change.old_version().to_string(),
+                    change.new_version().to_string(),
+                )
+            })
+            .collect()
+    };
+    assert_eq!(
+        versions(diff.upgraded()),
+        [
+            (
+                "bar".to_string(),
+                "2.0-1".to_string(),
+                "1:1.0-1".to_string()
+            ),
+            ("foo".to_string(), "1.0-1".to_string(), "1.0-2".to_string()),
+        ],
+    );
+    assert_eq!(
+        versions(diff.downgraded()),
+        [("baz".to_string(), "1.0-1".to_string(), "0.9-1".to_string())],
+    );
+
+    let changed: Vec<_> = diff
+        .changed()
+        .iter()
+        .map(|package| package.name().as_str())
+        .collect();
+    assert_eq!(changed, ["bar", "foo"]);
+
+    let bar = &diff.changed()[0];
+    assert!(bar.dependencies().is_empty());
+    let fields: Vec<_> = bar
+        .fields()
+        .iter()
+        .map(|change| (change.field(), change.old_value(), change.new_value()))
+        .collect();
+    assert_eq!(
+        fields,
+        [(FieldName::Url, None, Some("https://example.com"))],
+    );
+
+    let foo = &diff.changed()[1];
+    let dependencies: Vec<_> = foo
+        .dependencies()
+        .iter()
+        .map(|change| (change.field(), change.added(), change.removed()))
+        .collect();
+    assert_eq!(
+        dependencies,
+        [(
+            FieldName::Dependencies,
+            ["baz>=2"].as_slice(),
+            ["baz>=1"].as_slice(),
+        )],
+    );
+    let fields: Vec<_> = foo
+        .fields()
+        .iter()
+        .map(|change| (change.field(), change.old_value(), change.new_value()))
+        .collect();
+    assert_eq!(
+        fields,
+        [(FieldName::Description, Some("Old foo"), Some("New foo"))],
+    );
+
+    assert!(!diff.is_empty());
+    assert!(DatabaseDiff::new(&old, &old).is_empty());
+}
+
+#[test]
+fn single_checksums() {
+    let checksums = |md5: &str, sha256: &str| {
+        desc("foo", "1.0-1")
+            .field("ISIZE", &["100"])
+            .field("MD5SUM", &[md5])
+            .field("SHA256SUM", &[sha256])
+            .field("URL", &["https://example.com"])
+            .build()
+    };
+    let old_texts: TextCollection = [Text::from(checksums("0123", "4567"))]
+        .into_iter()
+        .collect();
+    let old: EagerQueryDatabase = old_texts.parse().unwrap();
+    let new_texts: TextCollection = [Text::from(checksums("89ab", "cdef"))]
+        .into_iter()
+        .collect();
+    let new: EagerQueryDatabase = new_texts.parse().unwrap();
+    let diff = DatabaseDiff::new(&old, &new);
+
+    let [foo] = diff.changed() else {
+        panic!("expecting one changed package, found {:?}", diff.changed());
+    };
+    let fields: Vec<_> = foo
+        .fields()
+        .iter()
+        .map(|change| (change.field(), change.old_value(), change.new_value()))
+        .collect();
+    assert_eq!(
+        fields,
+        [
+            (FieldName::Md5Checksum, Some("0123"), Some("89ab")),
+            (FieldName::Sha256Checksum, Some("4567"), Some("cdef")),
+        ],
+    );
+}
+
+#[test]
+fn multi() {
+    let mut new_texts = MultiTextCollection::new();
+    new_texts.insert(
+        "core".into(),
+        [fixtures::core::BASH, fixtures::core::GLIBC]
+            .into_iter()
+            .map(Text::from)
+            .collect(),
+    );
+    new_texts.insert(
+        "extra".into(),
+        [fixtures::extra::RUST, fixtures::extra::RUSTUP]
+            .into_iter()
+            .map(Text::from)
+            .collect(),
+    );
+    new_texts.insert(
+        "testing".into(),
+        [Text::from(desc("paru", "2.2.0-1").build())]
+            .into_iter()
+            .collect(),
+    );
+
+    let old: EagerMultiQueryDatabase = MULTI_TEXTS.parse().unwrap();
+    let new: EagerMultiQueryDatabase = new_texts.parse().unwrap();
+    let diff = MultiDatabaseDiff::new(&old, &new);
+
+    let summary: Vec<_> = diff
+        .iter()
+        .map(|(repository, diff)| {
+            let names = |packages: &[PackageEntry]| -> Vec<String> {
+                packages
+                    .iter()
+                    .map(|package| package.name().to_string())
+                    .collect()
+            };
+            (
+                repository.to_string(),
+                names(diff.added()),
+                names(diff.removed()),
+                diff.is_empty(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        summary,
+        [
+            (
+                "core".to_string(),
+                vec![],
+                vec![
+                    "ncurses".to_string(),
+                    "pacman".to_string(),
+                    "readline".to_string(),
+                ],
+                false,
+            ),
+            (
+                "derivative".to_string(),
+                vec![],
+                vec!["paru".to_string()],
+                false
+            ),
+            (
+                "extra".to_string(),
+                vec![],
+                vec![
+                    "bash-completion".to_string(),
+                    "parallel-disk-usage".to_string(),
+                ],
+                false,
+            ),
+            (
+                "personal".to_string(),
+                vec![],
+                vec!["parallel-disk-usage".to_string(), "paru".to_string()],
+                false,
+            ),
+            (
+                "testing".to_string(),
+                vec!["paru".to_string()],
+                vec![],
+                false
+            ),
+        ],
+    );
+
+    let extra = diff.get(RepositoryName("extra")).unwrap();
+    assert!(extra.upgraded().is_empty());
+    assert!(extra.downgraded().is_empty());
+    assert!(extra.changed().is_empty());
+    assert!(diff.get(RepositoryName("multilib")).is_none());
+    assert!(MultiDatabaseDiff::new(&old, &old).is_empty());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn single_yaml() {
+    let old_texts = old_texts();
+    let old: EagerQueryDatabase = old_texts.parse().unwrap();
+    let new_texts = new_texts();
+    let new: EagerQueryDatabase = new_texts.parse().unwrap();
+    let diff = DatabaseDiff::new(&old, &new);
+    let yaml = serde_yaml::to_string(&diff).unwrap();
+    assert_eq!(
+        yaml,
+        text_block_macros::text_block_fnl! {
+            "added:"
+            "- name: fresh"
+            "  version: 0.1-1"
+            "removed:"
+            "- name: gone"
+            "  version: 1.0-1"
+            "upgraded:"
+            "- name: bar"
+            "  old: 2.0-1"
+            "  new: 1:1.0-1"
+            "- name: foo"
+            "  old: 1.0-1"
+            "  new: 1.0-2"
+            "downgraded:"
+            "- name: baz"
+            "  old: 1.0-1"
+            "  new: 0.9-1"
+            "changed:"
+            "- name: bar"
+            "  dependencies: []"
+            "  fields:"
+            "  - field: URL"
+            "    old: null"
+            "    new: https://example.com"
+            "- name: foo"
+            "  dependencies:"
+            "  - field: DEPENDS"
+            "    added:"
+            "    - baz>=2"
+            "    removed:"
+            "    - baz>=1"
+            "  fields:"
+            "  - field: DESC"
+            "    old: Old foo"
+            "    new: New foo"
+        },
+    );
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn multi_yaml() {
+    let old: EagerMultiQueryDatabase = MULTI_TEXTS.parse().unwrap();
+    let new_texts = MultiTextCollection::new().add_collection(
+        RepositoryName("core"),
+        [fixtures::core::BASH].into_iter().map(Text::from).collect(),
+    );
+    let new: EagerMultiQueryDatabase = new_texts.parse().unwrap();
+    let diff = MultiDatabaseDiff::new(&old, &new);
+    let value = serde_yaml::to_value(&diff).unwrap();
+    let repositories: Vec<_> = value
+        .as_mapping()
+        .unwrap()
+        .keys()
+        .map(|key| key.as_str().unwrap())
+        .collect();
+    assert_eq!(repositories, ["core", "derivative", "extra", "personal"]);
+    assert_eq!(value["core"]["added"], serde_yaml::Value::Sequence(vec![]));
+    assert_eq!(value["core"]["removed"][0]["name"], "glibc");
+}
@@ -0,0 +1,199 @@
+use _utils::{
+    fixtures::archives::official_files::{FILES_TAR, FILES_TGZ, LOCAL_DB},
+    temp::Temp,
+};
+use arch_pkg_db::{
+    EagerFileOwnerDatabase, FilesCollection,
+    desc::{EagerQuerier, Query},
+    files::{FileStatus, WithFileList},
+    value::{FileList, FilePath, Mtree, Name},
+};
+use itertools::Itertools;
+use pipe_trait::Pipe;
+use pretty_assertions::assert_eq;
+
+fn owner_names<'a>(db: &EagerFileOwnerDatabase<'a>, path: &str) -> Vec<&'a str> {
+    db.owners(path)
+        .map(|entry| entry.name().as_str())
+        .sorted()
+        .collect()
+}
+
+fn assert_files_db(db: &EagerFileOwnerDatabase<'_>) {
+    dbg!(&db);
+
+    assert_eq!(
+        db.names().sorted().collect::<Vec<_>>(),
+        [
+            "bash",
+            "bash-completion",
+            "glibc",
+            "ncurses",
+            "pacman",
+            "parallel-disk-usage",
+            "readline",
+            "rust",
+            "rustup",
+        ]
+        .map(Name),
+    );
+
+    assert_eq!(owner_names(db, "/usr/bin/bash"), ["bash"]);
+    assert_eq!(owner_names(db, "usr/bin/bash"), ["bash"]);
+    assert_eq!(owner_names(db, "/usr/bin/pacman"), ["pacman"]);
+    assert_eq!(owner_names(db, "/usr/bin/cargo"), ["rust", "rustup"]);
+    assert_eq!(owner_names(db, "/usr/bin/not-exist"), [] as [&str; 0]);
+    assert!(owner_names(db, "/usr/bin/").contains(&"bash"));
+    assert!(owner_names(db, "/usr/bin").contains(&"pacman"));
+    assert_eq!(owner_names(db, "/"), [] as [&str; 0]);
+
+    let owner = db.owners("/usr/bin/pacman").next().unwrap();
+    assert_eq!(owner.name(), Name("pacman"));
+    assert_eq!(owner.querier().name(), Some(Name("pacman")));
+
+    let files = db.files(Name("pacman")).unwrap().iter().collect::<Vec<_>>();
+    assert!(files.contains(&FilePath("etc/pacman.conf")));
+    assert!(files.contains(&FilePath("usr/bin/makepkg")));
+    assert!(!files.contains(&FilePath("usr/bin/bash")));
+    assert!(db.files(Name("not-exist")).is_none());
+}
+
+#[test]
+fn tar() {
+    let collection = FILES_TAR
+        .as_slice()
+        .pipe(FilesCollection::from_tar)
+        .unwrap();
+    assert_eq!(collection.len(), 9);
+    let db = collection.parse().unwrap();
+    assert_files_db(&db);
+}
+
+#[test]
+fn tgz() {
+    let collection = FILES_TGZ
+        .as_slice()
+        .pipe(FilesCollection::from_archive)
+        .unwrap();
+    assert_eq!(collection.len(), 9);
+    let db = collection.parse_mut().unwrap();
+    assert_files_db(&db);
+}
+
+#[test]
+fn reinsert() {
+    let collection = FILES_TAR
+        .as_slice()
+        .pipe(FilesCollection::from_tar)
+        .unwrap();
+    let mut db = collection.parse::<EagerQuerier>().unwrap();
+    let rust = collection
+        .iter()
+        .find(|text| text.desc.contains("\n%NAME%\nrust\n"))
+        .unwrap();
+    let querier = rust.desc.as_str().try_into().unwrap();
+    let files = FileList::from_files_text("%FILES%\nusr/bin/rustc\n");
+    let old = db.insert(WithFileList::new(querier, files)).unwrap();
+    assert!(old.is_some());
+    assert_eq!(owner_names(&db, "/usr/bin/cargo"), ["rustup"]);
+    assert_eq!(owner_names(&db, "/usr/bin/rustc"), ["rust", "rustup"]);
+    assert_eq!(
+        db.files(Name("rust")).unwrap().iter().collect::<Vec<_>>(),
+        [FilePath("usr/bin/rustc")],
+    );
+}
+
+fn assert_local_db(collection: &FilesCollection, root: &Temp, mtree: bool) {
+    assert_eq!(collection.len(), 3);
+    let db: EagerFileOwnerDatabase = collection.parse().unwrap();
+
+    assert_eq!(owner_names(&db, "/usr/bin/bash"), ["bash"]);
+    assert_eq!(owner_names(&db, "/etc/pacman.conf"), ["pacman"]);
+    assert_eq!(db.files(Name("rust")).unwrap().iter().count(), 0);
+
+    let backup = db
+        .backup(Name("pacman"))
+        .unwrap()
+        .iter()
+        .map(|entry| (entry.path().as_str(), entry.md5()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        backup,
+        [
+            ("etc/makepkg.conf", "02a5438d646039d54b62a978631e4085"),
+            ("etc/pacman.conf", "c7678b8b7b20b5e344dc33c722e212cf"),
+        ],
+    );
+    assert_eq!(db.backup(Name("bash")).unwrap().iter().count(), 0);
+
+    let modified = db
+        .modified_backups(root)
+        .unwrap()
+        .into_iter()
+        .map(|(name, entry, status)| (name, entry.path().as_str(), status))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        modified,
+        [(Name("pacman"), "etc/makepkg.conf", FileStatus::Modified)],
+    );
+
+    if !mtree {
+        assert!(db.mtree(Name("bash")).is_none());
+        assert!(db.modified_files(Name("bash"), root).is_none());
+        return;
+    }
+
+    assert!(db.mtree(Name("pacman")).is_none());
+    let modified = db
+        .modified_files(Name("bash"), root)
+        .unwrap()
+        .unwrap()
+        .into_iter()
+        .map(|(entry, status)| (entry.path().into_owned(), status))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        modified,
+        [
+            ("etc/bash.bashrc".to_string(), FileStatus::Modified),
+            ("usr/share/doc/bash/README".to_string(), FileStatus::Missing),
+        ],
+    );
+}
+
+#[test]
+fn from_local_db() {
+    let root = Temp::official_root();
+    let collection = FilesCollection::from_local_db(&root.join(LOCAL_DB)).unwrap();
+    assert_local_db(&collection, &root, false);
+}
+
+#[test]
+fn par_from_local_db() {
+    let root = Temp::official_root();
+    let collection = FilesCollection::par_from_local_db(&root.join(LOCAL_DB)).unwrap();
+    assert_local_db(&collection, &root, false);
+}
+
+#[test]
+fn from_local_db_with_mtree() {
+    let root = Temp::official_root();
+    let collection = FilesCollection::from_local_db_with_mtree(&root.join(LOCAL_DB)).unwrap();
+    assert_local_db(&collection, &root, true);
+}
+
+#[test]
+fn par_from_local_db_with_mtree() {
+    let root = Temp::official_root();
+    let collection = FilesCollection::par_from_local_db_with_mtree(&root.join(LOCAL_DB)).unwrap();
+    assert_local_db(&collection, &root, true);
+}
+
+#[test]
+fn mtree_path_escape() {
+    let mtree = Mtree("./usr/share/a\\040b type=file\n./usr/share/a\\777b type=file\n");
+    let paths: Vec<_> = mtree
+        .entries()
+        .map(|entry| entry.path().into_owned())
+        .collect();
+    assert_eq!(paths, ["usr/share/a b", "usr/share/a\\777b"]);
+}
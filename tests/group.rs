@@ -0,0 +1,105 @@
+pub mod _utils;
+
+use _utils::desc::desc;
+use arch_pkg_db::{
+    EagerMultiQueryDatabase, EagerQueryDatabase, MultiTextCollection, Text, TextCollection,
+    value::{Group, Name, RepositoryName},
+};
+use pretty_assertions::assert_eq;
+
+fn core_texts() -> TextCollection {
+    [
+        desc("gcc", "1.0-1").groups(&["base-devel"]),
+        desc("make", "1.0-1").groups(&["base-devel"]),
+        desc("autoconf", "1.0-1").groups(&["base-devel", "autotools"]),
+        // an empty `%GROUPS%` section
+        desc("bash", "1.0-1").field("GROUPS", &[""]),
+    ]
+    .into_iter()
+    .map(|desc| Text::from(desc.build()))
+    .collect()
+}
+
+fn extra_texts() -> TextCollection {
+    [
+        desc("plasma-desktop", "1.0-1").groups(&["plasma"]),
+        desc("kwin", "1.0-1").groups(&["plasma", "kde-applications"]),
+        desc("make", "1.0-1").groups(&["base-devel"]),
+    ]
+    .into_iter()
+    .map(|desc| Text::from(desc.build()))
+    .collect()
+}
+
+#[test]
+fn single_group_index() {
+    let texts = core_texts();
+    let db: EagerQueryDatabase = texts.parse().unwrap();
+    let index = db.group_index();
+
+    assert_eq!(index.groups(), [Group("autotools"), Group("base-devel")]);
+    assert_eq!(
+        index.group_members(Group("base-devel")),
+        ["autoconf", "gcc", "make"].map(Name),
+    );
+    assert_eq!(index.group_members(Group("autotools")), [Name("autoconf")]);
+    assert_eq!(index.group_members(Group("not-exist")), []);
+}
+
+#[test]
+fn single_group_index_repeated_groups() {
+    let texts: TextCollection = [
+        desc("autoconf", "1.0-1").groups(&["autotools", "base-devel", "autotools"]),
+        desc("automake", "1.0-1").groups(&["autotools", "autotools"]),
+    ]
+    .into_iter()
+    .map(|desc| Text::from(desc.build()))
+    .collect();
+    let db: EagerQueryDatabase = texts.parse().unwrap();
+    let index = db.group_index();
+
+    assert_eq!(
+        index.group_members(Group("autotools")),
+        ["autoconf", "automake"].map(Name),
+    );
+    assert_eq!(index.group_members(Group("base-devel")), [Name("autoconf")]);
+}
+
+#[test]
+fn multi_group_index() {
+    let texts = MultiTextCollection::new()
+        .add_collection(RepositoryName("core"), core_texts())
+        .add_collection(RepositoryName("extra"), extra_texts());
+    let db: EagerMultiQueryDatabase = texts.parse().unwrap();
+    let index = db.group_index();
+
+    assert_eq!(
+        index.groups(),
+        ["autotools", "base-devel", "kde-applications", "plasma"].map(Group),
+    );
+    assert_eq!(
+        index.group_members(Group("base-devel")),
+        [
+            (RepositoryName("core"), Name("autoconf")),
+            (RepositoryName("core"), Name("gcc")),
+            (RepositoryName("core"), Name("make")),
+            (RepositoryName("extra"), Name("make")),
+        ],
+    );
+    assert_eq!(
+        index.repository_groups(RepositoryName("extra")),
+        ["base-devel", "kde-applications", "plasma"].map(Group),
+    );
+    assert_eq!(
+        index.repository_groups(RepositoryName("core")),
+        ["autotools", "base-devel"].map(Group),
+    );
+    assert_eq!(
+        index.repository_group_members(RepositoryName("extra"), Group("plasma")),
+        ["kwin", "plasma-desktop"].map(Name),
+    );
+    assert_eq!(
+        index.repository_group_members(RepositoryName("core"), Group("plasma")),
+        [],
+    );
+}
@@ -0,0 +1,191 @@
+pub mod _utils;
+
+use _utils::MULTI_TEXTS;
+use arch_pkg_db::{
+    MultiTextCollection, Text, TextCollection,
+    desc::{EagerQuerier, Query},
+    multi::{
+        EagerMultiQueryDatabasePrioritized, WithParsedVersion, WithParsedVersionUtils,
+        WithRepositoryName, WithRepositoryNameUtils,
+    },
+    value::{DependencyName, Name, RepositoryName},
+};
+use itertools::Itertools;
+use pipe_trait::Pipe;
+use pretty_assertions::assert_eq;
+
+type QuerierVersionRepository<'a> =
+    WithRepositoryName<'a, WithParsedVersion<'a, &'a EagerQuerier<'a>>>;
+
+const ORDER: &[RepositoryName] = &[RepositoryName("personal"), RepositoryName("extra")];
+
+fn assert_db_get(
+    db: &EagerMultiQueryDatabasePrioritized,
+    name: Name<'static>,
+    assertion: fn(QuerierVersionRepository),
+) {
+    eprintln!();
+    eprintln!("============");
+    eprintln!("PACKAGE: {name}");
+    db.get(name).unwrap().pipe(assertion);
+}
+
+fn assert_package(
+    querier: QuerierVersionRepository,
+    name: &'static str,
+    version: &'static str,
+    repository: &'static str,
+) {
+    assert_eq!(querier.name(), Some(Name(name)));
+    let actual_version = querier.version().unwrap();
+    assert_eq!(actual_version.as_str(), version);
+    assert_eq!(querier.repository_name(), RepositoryName(repository));
+    assert_eq!(querier.parsed_version(), actual_version.parse().unwrap());
+}
+
+fn assert_bash(querier: QuerierVersionRepository) {
+    assert_package(querier, "bash", "5.2.026-2", "core");
+}
+
+fn assert_bash_completion(querier: QuerierVersionRepository) {
+    assert_package(querier, "bash-completion", "2.14.0-2", "extra");
+}
+
+fn assert_parallel_disk_usage(querier: QuerierVersionRepository) {
+    assert_package(querier, "parallel-disk-usage", "0.9.2-1", "personal");
+}
+
+fn assert_paru(querier: QuerierVersionRepository) {
+    assert_package(querier, "paru", "2.0.3-1", "personal");
+}
+
+#[test]
+fn db_parse_prioritized_get() {
+    let db = MULTI_TEXTS.parse().unwrap();
+    let db: EagerMultiQueryDatabasePrioritized = db.prioritized(ORDER);
+    assert_eq!(db.order(), ORDER);
+    assert_db_get(&db, Name("bash"), assert_bash);
+    assert_db_get(&db, Name("bash-completion"), assert_bash_completion);
+    assert_db_get(&db, Name("parallel-disk-usage"), assert_parallel_disk_usage);
+    assert_db_get(&db, Name("paru"), assert_paru);
+    assert!(db.get(Name("not-exist")).is_none());
+}
+
+#[test]
+fn db_par_parse_prioritized_get() {
+    let db = MULTI_TEXTS.par_parse().unwrap();
+    let db: EagerMultiQueryDatabasePrioritized = db.prioritized(ORDER);
+    assert_db_get(&db, Name("bash"), assert_bash);
+    assert_db_get(&db, Name("bash-completion"), assert_bash_completion);
+    assert_db_get(&db, Name("parallel-disk-usage"), assert_parallel_disk_usage);
+    assert_db_get(&db, Name("paru"), assert_paru);
+}
+
+#[test]
+fn db_parse_prioritized_fallback_to_latest() {
+    let db = MULTI_TEXTS.parse().unwrap();
+    let order = [RepositoryName("not-exist")];
+    let db: EagerMultiQueryDatabasePrioritized = db.prioritized(&order);
+    let paru = db.get(Name("paru")).unwrap();
+    assert_package(paru, "paru", "2.1.0-1", "derivative");
+    let parallel_disk_usage = db.get(Name("parallel-disk-usage")).unwrap();
+    assert_package(
+        parallel_disk_usage,
+        "parallel-disk-usage",
+        "0.21.1-1",
+        "extra",
+    );
+}
+
+#[test]
+fn db_parse_prioritized_entries() {
+    let db = MULTI_TEXTS.parse().unwrap();
+    let db: EagerMultiQueryDatabasePrioritized = db.prioritized(ORDER);
+
+    let entries: Vec<_> = db
+        .entries()
+        .map(|(name, querier)| {
+            (
+                name.as_str(),
+                querier.repository_name().as_str(),
+                querier.version().unwrap().as_str(),
+            )
+        })
+        .sorted()
+        .collect();
+    dbg!(&entries);
+    assert_eq!(
+        entries,
+        [
+            ("bash", "core", "5.2.026-2"),
+            ("bash-completion", "extra", "2.14.0-2"),
+            ("glibc", "core", "2.42+r17+gd7274d718e6f-1"),
+            ("ncurses", "core", "6.5-4"),
+            ("pacman", "core", "7.0.0.r6.gc685ae6-6"),
+            ("parallel-disk-usage", "personal", "0.9.2-1"),
+            ("paru", "personal", "2.0.3-1"),
+            ("readline", "core", "8.3.001-1"),
+            ("rust", "extra", "1:1.90.0-3"),
+            ("rustup", "extra", "1.28.2-3"),
+        ],
+    );
+
+    let queriers: Vec<_> = db
+        .queriers()
+        .map(|querier| (querier.name().unwrap(), querier.repository_name()))
+        .sorted_by_key(|(name, _)| *name)
+        .collect();
+    let names: Vec<_> = db.names().sorted().collect();
+    assert_eq!(
+        queriers.iter().map(|(name, _)| *name).collect::<Vec<_>>(),
+        names,
+    );
+    assert_eq!(
+        entries
+            .iter()
+            .map(|(name, repository, _)| (Name(name), RepositoryName(repository)))
+            .collect::<Vec<_>>(),
+        queriers,
+    );
+}
+
+#[test]
+fn db_parse_prioritized_alternative_providers() {
+    let db = MULTI_TEXTS.parse().unwrap();
+    let db: EagerMultiQueryDatabasePrioritized = db.prioritized(ORDER);
+
+    let providers: Vec<_> = db
+        .alternative_providers(DependencyName("cargo"))
+        .map(|querier| {
+            (
+                querier.name().unwrap().as_str(),
+                querier.repository_name().as_str(),
+            )
+        })
+        .sorted()
+        .collect();
+    dbg!(&providers);
+    assert_eq!(providers, [("rust", "extra"), ("rustup", "extra")]);
+
+    let providers: Vec<_> = db
+        .alternative_providers(DependencyName("not-exist"))
+        .collect();
+    assert!(providers.is_empty());
+}
+
+#[test]
+fn db_parse_prioritized_fallback_tie() {
+    let texts: TextCollection = [Text::from(_utils::fixtures::core::BASH)]
+        .into_iter()
+        .collect();
+    let multi_texts = MultiTextCollection::new()
+        .add_collection(RepositoryName("zeta"), texts.clone())
+        .add_collection(RepositoryName("beta"), texts.clone())
+        .add_collection(RepositoryName("alpha"), texts);
+    for _ in 0..16 {
+        let db = multi_texts.parse().unwrap();
+        let db: EagerMultiQueryDatabasePrioritized = db.prioritized(ORDER);
+        let bash = db.get(Name("bash")).unwrap();
+        assert_package(bash, "bash", "5.2.026-2", "alpha");
+    }
+}
@@ -0,0 +1,116 @@
+pub mod _utils;
+
+use _utils::MULTI_TEXTS;
+use arch_pkg_db::{
+    desc::{MemoQuerier, QueryMut},
+    multi::{
+        MemoMultiQueryDatabasePrioritized, WithParsedVersion, WithParsedVersionUtils,
+        WithRepositoryName, WithRepositoryNameUtils,
+    },
+    value::{DependencyName, Name, RepositoryName},
+};
+use itertools::Itertools;
+use pipe_trait::Pipe;
+use pretty_assertions::assert_eq;
+
+type QuerierVersionRepository<'r, 'a> =
+    WithRepositoryName<'a, WithParsedVersion<'a, &'r mut MemoQuerier<'a>>>;
+
+const ORDER: &[RepositoryName] = &[RepositoryName("personal"), RepositoryName("extra")];
+
+fn assert_db_get_mut(
+    db: &mut MemoMultiQueryDatabasePrioritized,
+    name: Name<'static>,
+    assertion: fn(QuerierVersionRepository),
+) {
+    eprintln!();
+    eprintln!("============");
+    eprintln!("PACKAGE: {name}");
+    db.get_mut(name).unwrap().pipe(assertion);
+}
+
+fn assert_package(
+    mut querier: QuerierVersionRepository,
+    name: &'static str,
+    version: &'static str,
+    repository: &'static str,
+) {
+    assert_eq!(querier.name_mut(), Some(Name(name)));
+    let actual_version = querier.version_mut().unwrap();
+    assert_eq!(actual_version.as_str(), version);
+    assert_eq!(querier.repository_name(), RepositoryName(repository));
+    assert_eq!(querier.parsed_version(), actual_version.parse().unwrap());
+}
+
+fn assert_bash(querier: QuerierVersionRepository) {
+    assert_package(querier, "bash", "5.2.026-2", "core");
+}
+
+fn assert_bash_completion(querier: QuerierVersionRepository) {
+    assert_package(querier, "bash-completion", "2.14.0-2", "extra");
+}
+
+fn assert_parallel_disk_usage(querier: QuerierVersionRepository) {
+    assert_package(querier, "parallel-disk-usage", "0.9.2-1", "personal");
+}
+
+fn assert_paru(querier: QuerierVersionRepository) {
+    assert_package(querier, "paru", "2.0.3-1", "personal");
+}
+
+#[test]
+fn db_parse_mut_prioritized_get_mut() {
+    let mut db = MULTI_TEXTS.parse_mut().unwrap();
+    let mut db: MemoMultiQueryDatabasePrioritized = db.prioritized_mut(ORDER);
+    assert_db_get_mut(&mut db, Name("bash"), assert_bash);
+    assert_db_get_mut(&mut db, Name("bash-completion"), assert_bash_completion);
+    assert_db_get_mut(
+        &mut db,
+        Name("parallel-disk-usage"),
+        assert_parallel_disk_usage,
+    );
+    assert_db_get_mut(&mut db, Name("paru"), assert_paru);
+}
+
+#[test]
+fn db_parse_mut_prioritized_entries_mut() {
+    let mut db = MULTI_TEXTS.parse_mut().unwrap();
+    let mut db: MemoMultiQueryDatabasePrioritized = db.prioritized_mut(ORDER);
+
+    let entries: Vec<_> = db
+        .entries_mut()
+        .map(|(name, querier)| (name.as_str(), querier.repository_name().as_str()))
+        .filter(|(name, _)| matches!(*name, "parallel-disk-usage" | "paru" | "bash"))
+        .sorted()
+        .collect();
+    assert_eq!(
+        entries,
+        [
+            ("bash", "core"),
+            ("parallel-disk-usage", "personal"),
+            ("paru", "personal"),
+        ],
+    );
+
+    let queriers = db.queriers_mut().count();
+    assert_eq!(queriers, db.names().count());
+}
+
+#[test]
+fn db_parse_mut_prioritized_alternative_providers_mut() {
+    let mut db = MULTI_TEXTS.parse_mut().unwrap();
+    let mut db: MemoMultiQueryDatabasePrioritized = db.prioritized_mut(ORDER);
+
+    let providers: Vec<_> = db
+        .alternative_providers_mut(DependencyName("cargo"))
+        .map(|mut querier| {
+            (
+                querier.name_mut().unwrap().as_str(),
+                querier.repository_name().as_str(),
+            )
+        })
+        .sorted()
+        .collect();
+    dbg!(&providers);
+    assert_eq!(providers, [("rust", "extra"), ("rustup", "extra")]);
+}
@@ -0,0 +1,89 @@
+pub mod _utils;
+
+use _utils::desc::desc;
+use arch_pkg_db::{
+    EagerQueryDatabase, Text, TextCollection,
+    orphan::InstallReasons,
+    value::{InstallReason, Name},
+};
+use pretty_assertions::assert_eq;
+
+fn local_texts() -> TextCollection {
+    [
+        desc("app", "1.0-1")
+            .depends(&["lib-a"])
+            .optdepends(&["opt-tool: for extra features"]),
+        desc("lib-a", "1.0-1").depends(&["lib-b"]).reason(1),
+        desc("lib-b", "1.0-1").reason(1),
+        desc("opt-tool", "1.0-1").reason(1),
+        desc("stale", "1.0-1").depends(&["stale-lib>=1"]).reason(1),
+        desc("stale-lib", "1.0-1").reason(1),
+        desc("cycle-a", "1.0-1").depends(&["cycle-b"]).reason(1),
+        desc("cycle-b", "1.0-1").depends(&["cycle-a"]).reason(1),
+        desc("virtual-user", "1.0-1")
+            .depends(&["virtual"])
+            .reason(1),
+        desc("virtual-impl", "1.0-1")
+            .provides(&["virtual=1.0"])
+            .reason(1),
+        desc("lonely", "1.0-1"),
+    ]
+    .into_iter()
+    .map(|desc| Text::from(desc.build()))
+    .collect()
+}
+
+#[test]
+fn install_reasons() {
+    let texts = local_texts();
+    let reasons = InstallReasons::from_texts(&texts);
+    assert_eq!(reasons.len(), texts.len());
+    assert_eq!(reasons.get(Name("app")), Some(InstallReason::Explicit));
+    assert_eq!(reasons.get(Name("lib-a")), Some(InstallReason::Dependency));
+    assert_eq!(reasons.get(Name("not-exist")), None);
+    assert_eq!(
+        InstallReason::from_desc_text("%NAME%\nfoo\n\n%REASON%\n0\n"),
+        InstallReason::Explicit,
+    );
+}
+
+#[test]
+fn orphans() {
+    let texts = local_texts();
+    let db: EagerQueryDatabase = texts.parse().unwrap();
+    let audit = db.orphan_audit(InstallReasons::from_texts(&texts));
+
+    assert_eq!(
+        audit.orphans(),
+        ["opt-tool", "stale", "virtual-user"].map(Name),
+    );
+    assert_eq!(
+        audit.orphans_honoring_optional(),
+        ["stale", "virtual-user"].map(Name),
+    );
+}
+
+#[test]
+fn orphaned_subgraphs() {
+    let texts = local_texts();
+    let db: EagerQueryDatabase = texts.parse().unwrap();
+    let audit = db.orphan_audit(InstallReasons::from_texts(&texts));
+
+    assert_eq!(
+        audit.orphaned_subgraphs(),
+        [
+            ["cycle-a", "cycle-b"].map(Name).to_vec(),
+            ["opt-tool"].map(Name).to_vec(),
+            ["stale", "stale-lib"].map(Name).to_vec(),
+            ["virtual-impl", "virtual-user"].map(Name).to_vec(),
+        ],
+    );
+    assert_eq!(
+        audit.orphaned_subgraphs_honoring_optional(),
+        [
+            ["cycle-a", "cycle-b"].map(Name).to_vec(),
+            ["stale", "stale-lib"].map(Name).to_vec(),
+            ["virtual-impl", "virtual-user"].map(Name).to_vec(),
+        ],
+    );
+}
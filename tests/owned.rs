@@ -0,0 +1,93 @@
+pub mod _utils;
+
+use _utils::fixtures::{self, archives::official::DB_TEXTS};
+use arch_pkg_db::{
+    PackageDesc, Text,
+    desc::{EagerQuerier, FieldName},
+    write::desc_text,
+};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn typed_fields() {
+    let querier = EagerQuerier::parse(fixtures::core::BASH).unwrap();
+    let desc = PackageDesc::from_query(&querier).unwrap();
+    assert_eq!(desc.name.as_deref(), Some("bash"));
+    assert_eq!(desc.version.as_deref(), Some("5.2.026-2"));
+    assert_eq!(desc.compressed_size, Some(1898729));
+    assert_eq!(desc.installed_size, Some(9650379));
+    assert_eq!(
+        desc.md5_checksum.as_deref(),
+        Some("165f04122017ec76579594b17f15f8eb"),
+    );
+    assert_eq!(desc.build_date, Some(1705402084));
+    assert_eq!(desc.license, ["GPL-3.0-or-later"]);
+    assert_eq!(desc.architecture, ["x86_64"]);
+    assert_eq!(
+        desc.dependencies,
+        ["readline", "libreadline.so=8-64", "glibc", "ncurses"],
+    );
+    assert_eq!(desc.provides, ["sh"]);
+    assert!(desc.make_dependencies.is_empty());
+}
+
+#[test]
+fn render_canonical_text() {
+    for text in DB_TEXTS.iter() {
+        let querier = EagerQuerier::parse(text).unwrap();
+        let desc = PackageDesc::from_query(&querier).unwrap();
+        let rendered = desc.to_string();
+        assert_eq!(rendered, desc_text(&querier));
+
+        let reparsed = EagerQuerier::parse(&rendered).unwrap();
+        assert_eq!(PackageDesc::from_query(&reparsed).unwrap(), desc);
+    }
+}
+
+#[test]
+fn outlive_text() {
+    let desc = {
+        let text = Text::from("%NAME%\nfoo\n\n%VERSION%\n1.0-1\n\n%GROUPS%\nbar\nbaz\n");
+        let querier = EagerQuerier::parse(text.as_str()).unwrap();
+        PackageDesc::from_query(&querier).unwrap()
+    };
+    assert_eq!(
+        desc.to_string(),
+        "%NAME%\nfoo\n\n%VERSION%\n1.0-1\n\n%GROUPS%\nbar\nbaz\n\n",
+    );
+}
+
+#[test]
+fn invalid_number() {
+    let querier = EagerQuerier::parse("%NAME%\nfoo\n\n%ISIZE%\nlarge\n").unwrap();
+    let error = PackageDesc::from_query(&querier).unwrap_err();
+    assert_eq!(error.field(), FieldName::InstalledSize);
+    assert_eq!(
+        error.to_string(),
+        "Failed to parse %ISIZE% as a number: invalid digit found in string",
+    );
+}
+
+#[test]
+fn empty_number() {
+    let text = "%NAME%\nfoo\n\n%VERSION%\n1.0-1\n\n%CSIZE%\n\n%ISIZE%\n\n%BUILDDATE%\n\n";
+    let querier = EagerQuerier::parse(text).unwrap();
+    let desc = PackageDesc::from_query(&querier).unwrap();
+    assert_eq!(desc.compressed_size, None);
+    assert_eq!(desc.installed_size, None);
+    assert_eq!(desc.build_date, None);
+    assert_eq!(desc.to_string(), "%NAME%\nfoo\n\n%VERSION%\n1.0-1\n\n");
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_round_trip() {
+    let querier = EagerQuerier::parse(fixtures::extra::RUST).unwrap();
+    let desc = PackageDesc::from_query(&querier).unwrap();
+    let yaml = serde_yaml::to_string(&desc).unwrap();
+    let received: PackageDesc = serde_yaml::from_str(&yaml).unwrap();
+    assert_eq!(received, desc);
+
+    let partial: PackageDesc = serde_yaml::from_str("name: foo\nversion: 1.0-1\n").unwrap();
+    assert_eq!(partial.to_string(), "%NAME%\nfoo\n\n%VERSION%\n1.0-1\n\n");
+}
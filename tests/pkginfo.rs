@@ -0,0 +1,182 @@
+pub mod _utils;
+
+use _utils::{
+    fixtures::{
+        archives::packages::{BASH_PKG_TAR, BASH_PKG_TGZ, BASH_PKG_TXZ, BASH_PKG_TZST},
+        core::BASH,
+        packages,
+    },
+    temp::Temp,
+};
+use arch_pkg_db::{
+    EagerQueryDatabase, PackageDesc, Text, TextCollection,
+    desc::{EagerQuerier, Query},
+    pkginfo::{LoadPackageError, ParsePkgInfoError, is_package_file_name, read_pkginfo},
+    text::archive::LoadArchiveError,
+    value::Name,
+};
+use itertools::Itertools;
+use pretty_assertions::assert_eq;
+use std::io;
+
+/// Description of bash without the fields which only a repository database has.
+fn bash_desc() -> PackageDesc {
+    let querier: EagerQuerier = BASH.try_into().unwrap();
+    PackageDesc {
+        file_name: None,
+        compressed_size: None,
+        md5_checksum: None,
+        sha256_checksum: None,
+        pgp_signature: None,
+        ..PackageDesc::from_query(&querier).unwrap()
+    }
+}
+
+#[test]
+fn from_pkginfo() {
+    let desc = PackageDesc::from_pkginfo(packages::BASH).unwrap();
+    assert_eq!(desc, bash_desc());
+}
+
+#[test]
+fn from_package_reader() {
+    for archive in [&BASH_PKG_TAR, &BASH_PKG_TGZ, &BASH_PKG_TXZ, &BASH_PKG_TZST] {
+        assert_eq!(read_pkginfo(archive.as_slice()).unwrap(), packages::BASH);
+        let desc = PackageDesc::from_package_reader(archive.as_slice()).unwrap();
+        assert_eq!(desc, bash_desc());
+    }
+}
+
+#[test]
+fn from_package_file() {
+    let temp = Temp::package_dir();
+    let path = temp.join("bash-5.2.026-2-x86_64.pkg.tar.zst");
+    let desc = PackageDesc::from_package_file(&path).unwrap();
+    assert_eq!(
+        desc.file_name.as_deref(),
+        Some("bash-5.2.026-2-x86_64.pkg.tar.zst"),
+    );
+    assert_eq!(desc.compressed_size, Some(BASH_PKG_TZST.len() as u64));
+    assert_eq!(
+        PackageDesc {
+            file_name: None,
+            compressed_size: None,
+            ..desc
+        },
+        bash_desc(),
+    );
+
+    let error = PackageDesc::from_package_file(&temp.join("README")).unwrap_err();
+    assert_eq!(error.path(), temp.join("README"));
+    assert!(matches!(error.source(), LoadPackageError::Archive(_)));
+}
+
+#[test]
+fn from_package_dir() {
+    let temp = Temp::package_dir();
+    let sequential = TextCollection::from_package_dir(&temp).unwrap();
+    let parallel = TextCollection::par_from_package_dir(&temp).unwrap();
+    assert_eq!(sequential.len(), 2);
+    assert_eq!(
+        sequential.iter().map(Text::as_str).collect::<Vec<_>>(),
+        parallel.iter().map(Text::as_str).collect::<Vec<_>>(),
+    );
+
+    let db: EagerQueryDatabase = sequential.parse().unwrap();
+    assert_eq!(
+        db.names().sorted().collect::<Vec<_>>(),
+        [Name("bash"), Name("parallel-disk-usage")],
+    );
+    let pdu = db.get(Name("parallel-disk-usage")).unwrap();
+    assert_eq!(pdu.version().unwrap().as_str(), "0.21.1-1");
+    assert_eq!(
+        pdu.file_name().unwrap().as_str(),
+        "parallel-disk-usage-0.21.1-1-x86_64.pkg.tar.zst",
+    );
+    let dependencies: Vec<_> = pdu
+        .dependencies()
+        .into_iter()
+        .flatten()
+        .map(|dependency| dependency.as_str())
+        .collect();
+    assert_eq!(dependencies, ["gcc-libs"]);
+}
+
+#[test]
+fn missing_pkginfo() {
+    let error =
+        PackageDesc::from_package_reader(_utils::fixtures::archives::official::TZST.as_slice())
+            .unwrap_err();
+    assert!(
+        matches!(error, LoadPackageError::MissingPkgInfo),
+        "{error:?}"
+    );
+}
+
+#[test]
+fn invalid_pkginfo() {
+    assert_eq!(
+        PackageDesc::from_pkginfo("pkgname = foo\n\nnot a pair\n"),
+        Err(ParsePkgInfoError::InvalidLine {
+            line_number: 3,
+            line: "not a pair".to_string(),
+        }),
+    );
+    let error = PackageDesc::from_pkginfo("size = big\n").unwrap_err();
+    assert!(matches!(
+        error,
+        ParsePkgInfoError::InvalidNumber { key: "size", .. },
+    ));
+    assert_eq!(
+        error.to_string(),
+        "Failed to parse size as a number: invalid digit found in string",
+    );
+}
+
+#[test]
+fn package_file_names() {
+    assert!(is_package_file_name("bash-5.2.026-2-x86_64.pkg.tar.zst"));
+    assert!(is_package_file_name("foo-1.0-1-any.pkg.tar.xz"));
+    assert!(!is_package_file_name(
+        "bash-5.2.026-2-x86_64.pkg.tar.zst.sig"
+    ));
+    assert!(!is_package_file_name("core.db.tar.gz"));
+}
+
+#[test]
+fn truncated_after_read_ahead() {
+    // files before `.PKGINFO` push the truncation past the read-ahead
+    let content = [0; 8192];
+    let mut builder = tar::Builder::new(Vec::new());
+    for index in 0..64 {
+        let mut header = tar::Header::new_gnu();
+        header.set_size(content.len() as u64);
+        header.set_mode(0o644);
+        builder
+            .append_data(
+                &mut header,
+                format!("usr/share/blob-{index}"),
+                content.as_slice(),
+            )
+            .unwrap();
+    }
+    let mut header = tar::Header::new_gnu();
+    header.set_size(packages::BASH.len() as u64);
+    header.set_mode(0o644);
+    builder
+        .append_data(&mut header, ".PKGINFO", packages::BASH.as_bytes())
+        .unwrap();
+    // stored blocks let the decoder yield data before it reaches the truncation
+    let options = libflate::gzip::EncodeOptions::new().no_compression();
+    let mut encoder = libflate::gzip::Encoder::with_options(Vec::new(), options).unwrap();
+    io::Write::write_all(&mut encoder, &builder.into_inner().unwrap()).unwrap();
+    let mut archive = encoder.finish().into_result().unwrap();
+    archive.truncate(archive.len() / 2);
+    assert!(archive.len() > 128 * 1024);
+
+    let error = read_pkginfo(archive.as_slice()).unwrap_err();
+    assert!(
+        matches!(error, LoadPackageError::Archive(LoadArchiveError::Gzip(_))),
+        "{error:?}",
+    );
+}
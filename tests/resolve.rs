@@ -0,0 +1,233 @@
+pub mod _utils;
+
+use _utils::{MULTI_TEXTS, desc::desc, fixtures, fixtures::archives::official::DB_TEXTS};
+use arch_pkg_db::{
+    EagerMultiQueryDatabase, EagerQueryDatabase, Text, TextCollection,
+    desc::Query,
+    multi::WithRepositoryNameUtils,
+    resolve::{DependencySource, Resolver},
+    value::{Dependency, Name, RepositoryName},
+};
+use pretty_assertions::assert_eq;
+use std::cell::Cell;
+
+/// Create texts of installed packages which aren't included in the fixtures.
+fn installed_texts(extra: &[&'static str]) -> TextCollection {
+    let synthetic = [
+        desc("linux-api-headers", "6.16-1"),
+        desc("tzdata", "2025b-1"),
+        desc("filesystem", "2025.05.03-1"),
+        desc("gcc-libs", "15.2.1+r22+gc4e96a094636-1"),
+        desc("git", "2.51.0-1"),
+    ];
+    synthetic
+        .into_iter()
+        .map(|desc| Text::from(desc.build()))
+        .chain(extra.iter().copied().map(Text::from))
+        .collect()
+}
+
+fn names<'a>(queriers: impl IntoIterator<Item = impl Query<'a>>) -> Vec<&'a str> {
+    queriers
+        .into_iter()
+        .map(|querier| querier.name().unwrap().as_str())
+        .collect()
+}
+
+#[test]
+fn single_resolve() {
+    let texts: TextCollection = DB_TEXTS.iter().copied().map(Text::from).collect();
+    let db: EagerQueryDatabase = texts.parse().unwrap();
+    let installed_texts = installed_texts(&[]);
+    let installed: EagerQueryDatabase = installed_texts.parse().unwrap();
+
+    let install_set = Resolver::new(&db)
+        .with_installed(&installed)
+        .resolve([Dependency("bash-completion")])
+        .unwrap();
+    assert_eq!(
+        names(install_set),
+        ["glibc", "ncurses", "readline", "bash", "bash-completion"],
+    );
+}
+
+#[test]
+fn single_resolve_skip_installed() {
+    let texts: TextCollection = DB_TEXTS.iter().copied().map(Text::from).collect();
+    let db: EagerQueryDatabase = texts.parse().unwrap();
+    let installed_texts = installed_texts(&[fixtures::core::GLIBC, fixtures::core::BASH]);
+    let installed: EagerQueryDatabase = installed_texts.parse().unwrap();
+
+    let install_set = Resolver::new(&db)
+        .with_installed(&installed)
+        .resolve([Dependency("bash-completion"), Dependency("readline")])
+        .unwrap();
+    assert_eq!(
+        names(install_set),
+        ["bash-completion", "ncurses", "readline"],
+    );
+
+    // targets are always included even if installed
+    let install_set = Resolver::new(&db)
+        .with_installed(&installed)
+        .resolve([Dependency("bash"), Dependency("bash")])
+        .unwrap();
+    assert_eq!(names(install_set), ["ncurses", "readline", "bash"]);
+}
+
+#[test]
+fn single_resolve_provider() {
+    let texts: TextCollection = DB_TEXTS.iter().copied().map(Text::from).collect();
+    let db: EagerQueryDatabase = texts.parse().unwrap();
+    let installed_texts = installed_texts(&[fixtures::core::GLIBC]);
+    let installed: EagerQueryDatabase = installed_texts.parse().unwrap();
+
+    let install_set = Resolver::new(&db)
+        .with_installed(&installed)
+        .resolve([Dependency("libreadline.so>=8")])
+        .unwrap();
+    assert_eq!(names(install_set), ["ncurses", "readline"]);
+}
+
+#[test]
+fn single_resolve_unsatisfied() {
+    let texts: TextCollection = DB_TEXTS.iter().copied().map(Text::from).collect();
+    let db: EagerQueryDatabase = texts.parse().unwrap();
+
+    let error = Resolver::new(&db)
+        .resolve([Dependency("bash-completion"), Dependency("not-exist>=1")])
+        .unwrap_err();
+    let unsatisfied: Vec<_> = error
+        .unsatisfied()
+        .iter()
+        .map(|unsatisfied| (unsatisfied.dependency().as_str(), unsatisfied.chain()))
+        .collect();
+    dbg!(&unsatisfied);
+    let glibc_chain = ["bash-completion", "bash", "readline", "glibc"].map(Name);
+    let ncurses_chain = ["bash-completion", "bash", "readline", "ncurses"].map(Name);
+    assert_eq!(
+        unsatisfied,
+        [
+            ("linux-api-headers>=4.10", glibc_chain.as_slice()),
+            ("tzdata", glibc_chain.as_slice()),
+            ("filesystem", glibc_chain.as_slice()),
+            ("gcc-libs", ncurses_chain.as_slice()),
+            ("not-exist>=1", &[]),
+        ],
+    );
+    assert_eq!(
+        error.unsatisfied()[0].to_string(),
+        "linux-api-headers>=4.10 (required by bash-completion -> bash -> readline -> glibc)",
+    );
+    assert_eq!(error.unsatisfied()[4].to_string(), "not-exist>=1");
+}
+
+#[test]
+fn multi_resolve_prioritized() {
+    let db: EagerMultiQueryDatabase = MULTI_TEXTS.parse().unwrap();
+    let installed_texts = installed_texts(&[fixtures::core::PACMAN]);
+    let installed: EagerQueryDatabase = installed_texts.parse().unwrap();
+
+    let order = [
+        RepositoryName("personal"),
+        RepositoryName("core"),
+        RepositoryName("extra"),
+    ];
+    let install_set = Resolver::new(db.prioritized(&order))
+        .with_installed(&installed)
+        .resolve([Dependency("paru")])
+        .unwrap();
+    let install_set: Vec<_> = install_set
+        .into_iter()
+        .map(|querier| {
+            (
+                querier.name().unwrap().as_str(),
+                querier.repository_name().as_str(),
+            )
+        })
+        .collect();
+    assert_eq!(install_set, [("paru", "personal")]);
+}
+
+#[test]
+fn multi_resolve_latest() {
+    let db: EagerMultiQueryDatabase = MULTI_TEXTS.parse().unwrap();
+    let installed_texts = installed_texts(&[fixtures::core::PACMAN]);
+    let installed: EagerQueryDatabase = installed_texts.parse().unwrap();
+
+    let install_set = Resolver::new(db.latest())
+        .with_installed(&installed)
+        .resolve([Dependency("paru"), Dependency("parallel-disk-usage")])
+        .unwrap();
+    let install_set: Vec<_> = install_set
+        .into_iter()
+        .map(|querier| {
+            (
+                querier.name().unwrap().as_str(),
+                querier.repository_name().as_str(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        install_set,
+        [("paru", "derivative"), ("parallel-disk-usage", "extra")],
+    );
+}
+
+/// Source which counts how many times its packages are listed.
+struct CountingSource<'a> {
+    db: &'a EagerQueryDatabase<'a>,
+    listed: &'a Cell<usize>,
+}
+
+impl<'a> DependencySource<'a> for CountingSource<'a> {
+    type Querier = <&'a EagerQueryDatabase<'a> as DependencySource<'a>>::Querier;
+
+    fn packages(&self) -> impl Iterator<Item = Self::Querier> {
+        self.listed.set(self.listed.get() + 1);
+        self.db.packages()
+    }
+
+    fn choose_provider(
+        &self,
+        providers: impl Iterator<Item = Self::Querier>,
+    ) -> Option<Self::Querier> {
+        self.db.choose_provider(providers)
+    }
+}
+
+#[test]
+fn single_resolve_indexes_once() {
+    let texts: TextCollection = DB_TEXTS.iter().copied().map(Text::from).collect();
+    let db: EagerQueryDatabase = texts.parse().unwrap();
+    let listed = Cell::new(0);
+    let source = CountingSource {
+        db: &db,
+        listed: &listed,
+    };
+
+    let install_set = Resolver::new(source)
+        .resolve([
+            Dependency("bash-completion"),
+            Dependency("libreadline.so>=8"),
+        ])
+        .unwrap_err();
+    assert!(!install_set.unsatisfied().is_empty());
+    assert_eq!(listed.get(), 1);
+
+    let source = CountingSource {
+        db: &db,
+        listed: &listed,
+    };
+    // readline is chosen for its soname, the missing dependencies are underneath it
+    let error = Resolver::new(source)
+        .resolve([Dependency("libreadline.so>=8")])
+        .unwrap_err();
+    assert!(
+        error
+            .unsatisfied()
+            .iter()
+            .all(|unsatisfied| unsatisfied.chain().first() == Some(&Name("readline"))),
+    );
+    assert_eq!(listed.get(), 2);
+}
@@ -0,0 +1,117 @@
+pub mod _utils;
+
+use _utils::{MULTI_TEXTS, desc::desc, fixtures::archives::official::DB_TEXTS};
+use arch_pkg_db::{
+    EagerMultiQueryDatabase, EagerQueryDatabase, Text, TextCollection,
+    reverse::DependencyKind,
+    value::{DependencyName, Name, RepositoryName},
+};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn single_reverse_dependencies() {
+    let texts: TextCollection = DB_TEXTS.iter().copied().map(Text::from).collect();
+    let db: EagerQueryDatabase = texts.parse().unwrap();
+    let index = db.reverse_dependencies();
+
+    assert_eq!(
+        index.required_by(Name("glibc")),
+        ["bash", "ncurses", "pacman", "readline", "rust", "rustup"].map(Name),
+    );
+    assert_eq!(
+        index.required_by(Name("bash")),
+        ["bash-completion", "pacman", "rust"].map(Name),
+    );
+    assert_eq!(
+        index.required_by(Name("ncurses")),
+        ["bash", "readline"].map(Name),
+    );
+    assert_eq!(index.required_by(Name("bash-completion")), []);
+    assert_eq!(index.required_by(Name("not-exist")), []);
+
+    assert_eq!(index.optional_for(Name("bash-completion")), [Name("bash")]);
+    assert_eq!(index.optional_for(Name("bash")), [Name("ncurses")]);
+
+    assert_eq!(
+        index.dependents(DependencyKind::Depends, DependencyName("libreadline.so")),
+        [Name("bash")],
+    );
+    assert_eq!(
+        index.dependents(DependencyKind::Depends, DependencyName("sh")),
+        [],
+    );
+    assert_eq!(
+        index.dependents(DependencyKind::MakeDepends, DependencyName("cargo")),
+        ["parallel-disk-usage", "rustup"].map(Name),
+    );
+    assert_eq!(
+        index.dependents_of(DependencyKind::MakeDepends, Name("rust")),
+        ["parallel-disk-usage", "rust", "rustup"].map(Name),
+    );
+    assert_eq!(
+        index.dependents(DependencyKind::CheckDepends, DependencyName("gdb")),
+        [Name("rust")],
+    );
+    assert_eq!(
+        index.dependents(DependencyKind::OptDepends, DependencyName("gdb")),
+        ["rust", "rustup"].map(Name),
+    );
+}
+
+#[test]
+fn multi_reverse_dependencies() {
+    let db: EagerMultiQueryDatabase = MULTI_TEXTS.parse().unwrap();
+    let index = db.reverse_dependencies();
+
+    assert_eq!(
+        index.required_by(Name("pacman")),
+        [
+            (RepositoryName("derivative"), Name("paru")),
+            (RepositoryName("personal"), Name("paru")),
+        ],
+    );
+    assert_eq!(
+        index.required_by(Name("bash")),
+        [
+            (RepositoryName("extra"), Name("bash-completion")),
+            (RepositoryName("core"), Name("pacman")),
+            (RepositoryName("extra"), Name("rust")),
+        ],
+    );
+    assert_eq!(
+        index.dependents(DependencyKind::MakeDepends, DependencyName("cargo")),
+        [
+            (RepositoryName("extra"), Name("parallel-disk-usage")),
+            (RepositoryName("personal"), Name("parallel-disk-usage")),
+            (RepositoryName("derivative"), Name("paru")),
+            (RepositoryName("personal"), Name("paru")),
+            (RepositoryName("extra"), Name("rustup")),
+        ],
+    );
+    assert_eq!(
+        index.optional_for(Name("bat")),
+        [
+            (RepositoryName("derivative"), Name("paru")),
+            (RepositoryName("personal"), Name("paru")),
+        ],
+    );
+}
+
+#[test]
+fn single_reverse_dependencies_repeated_names() {
+    let texts: TextCollection = [
+        desc("foo", "1.0-1").depends(&["bar>=1", "bar<2", "baz"]),
+        desc("bar", "1.5-1").provides(&["baz", "baz=1"]),
+    ]
+    .into_iter()
+    .map(|desc| Text::from(desc.build()))
+    .collect();
+    let db: EagerQueryDatabase = texts.parse().unwrap();
+    let index = db.reverse_dependencies();
+
+    assert_eq!(
+        index.dependents(DependencyKind::Depends, DependencyName("bar")),
+        [Name("foo")],
+    );
+    assert_eq!(index.required_by(Name("bar")), [Name("foo")]);
+}
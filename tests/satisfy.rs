@@ -0,0 +1,196 @@
+pub mod _utils;
+
+use _utils::{MULTI_TEXTS, fixtures::archives::official::DB_TEXTS};
+use arch_pkg_db::{
+    EagerMultiQueryDatabase, EagerQueryDatabase, MemoMultiQueryDatabase, MemoQueryDatabase, Text,
+    TextCollection,
+    desc::{Query, QueryMut},
+    satisfy::{package_satisfies, provision_satisfies, vercmp},
+    value::{Dependency, DependencyList, Name, Version},
+};
+use core::cmp::Ordering;
+use itertools::Itertools;
+use pretty_assertions::assert_eq;
+
+#[test]
+fn vercmp_cases() {
+    use Ordering::{Equal, Greater, Less};
+
+    // taken from pacman's test suite for vercmp
+    let cases = [
+        ("1.5.0", "1.5.0", Equal),
+        ("1.5.1", "1.5.0", Greater),
+        ("1.5.1", "1.5", Greater),
+        ("1.5.0-1", "1.5.0-1", Equal),
+        ("1.5.0-1", "1.5.0-2", Less),
+        ("1.5.0-1", "1.5.1-1", Less),
+        ("1.5.0-2", "1.5.1-1", Less),
+        ("1.5-1", "1.5", Equal),
+        ("1.1-1", "1.1", Equal),
+        ("1.0-1", "1.1", Less),
+        ("1.1-1", "1.0", Greater),
+        ("1.5b-1", "1.5-1", Less),
+        ("1.5b", "1.5", Less),
+        ("1.5b-1", "1.5", Less),
+        ("1.5b", "1.5.1", Less),
+        ("1.0a", "1.0alpha", Less),
+        ("1.0alpha", "1.0b", Less),
+        ("1.0b", "1.0beta", Less),
+        ("1.0beta", "1.0rc", Less),
+        ("1.0rc", "1.0", Less),
+        ("1.5.a", "1.5", Greater),
+        ("1.5.b", "1.5.a", Greater),
+        ("1.5.1", "1.5.b", Greater),
+        ("1.5.b-1", "1.5.b", Equal),
+        ("1.5-1", "1.5.b", Less),
+        ("2.0", "2_0", Equal),
+        ("2.0_a", "2_0.a", Equal),
+        ("2.0a", "2.0.a", Less),
+        ("2___a", "2_a", Greater),
+        ("0:1.0", "0:1.0", Equal),
+        ("0:1.0", "0:1.1", Less),
+        ("1:1.0", "0:1.0", Greater),
+        ("1:1.0", "0:1.1", Greater),
+        ("1:1.0", "2:1.1", Less),
+        ("0:1.0", "1.0", Equal),
+        ("0:1.0", "1.1", Less),
+        ("0:1.1", "1.0", Greater),
+        ("1:1.0", "1.0", Greater),
+        ("1:1.0", "1.1", Greater),
+        ("1:1.1", "1.1", Greater),
+        ("1.0.0", "1.0", Greater),
+        ("1.0.0", "1.0.", Greater),
+        ("1.0..", "1.0.", Equal),
+        ("1.0.1", "1.0", Greater),
+        ("1.0..", "1.0", Greater),
+        ("1.0.", "1.0", Greater),
+        ("1.0", "1.0a", Greater),
+        ("001", "1", Equal),
+        ("2.42+r17+gd7274d718e6f-1", "2.38", Greater),
+    ];
+
+    for (left, right, expected) in cases {
+        eprintln!("CASE: {left} vs {right}");
+        assert_eq!(vercmp(Version(left), Version(right)), expected);
+        assert_eq!(vercmp(Version(right), Version(left)), expected.reverse());
+    }
+}
+
+#[test]
+fn provision_rules() {
+    let satisfies =
+        |dependency, provision| provision_satisfies(Dependency(dependency), Dependency(provision));
+    assert!(satisfies("sh", "sh"));
+    assert!(satisfies("sh", "sh=5"));
+    assert!(satisfies("sh=5", "sh=5"));
+    assert!(satisfies("sh>=4", "sh=5"));
+    assert!(!satisfies("sh=5", "sh"));
+    assert!(!satisfies("sh<5", "sh=5"));
+    assert!(!satisfies("sh>=4", "sh>=5"));
+    assert!(!satisfies("bash", "sh"));
+    assert!(satisfies("libalpm.so=15-64", "libalpm.so=15-64"));
+    assert!(satisfies("libalpm.so>=15", "libalpm.so=15-64"));
+}
+
+#[test]
+fn package_rules() {
+    let provides = Some(DependencyList::new("sh"));
+    let satisfies = |dependency, version| {
+        package_satisfies(
+            Dependency(dependency),
+            Some(Name("bash")),
+            version,
+            provides,
+        )
+    };
+    assert!(satisfies("bash", Some(Version("5.2.026-2"))));
+    assert!(satisfies("bash>=5.2", Some(Version("5.2.026-2"))));
+    assert!(satisfies("bash=5.2.026", Some(Version("5.2.026-2"))));
+    assert!(!satisfies("bash=5.2.026-1", Some(Version("5.2.026-2"))));
+    assert!(!satisfies("bash<5", Some(Version("5.2.026-2"))));
+    assert!(!satisfies("bash>=5", None));
+    assert!(satisfies("bash", None));
+    assert!(satisfies("sh", None));
+    assert!(!satisfies("sh=5", Some(Version("5.2.026-2"))));
+}
+
+fn single_satisfier_names<'a>(
+    db: &'a EagerQueryDatabase<'a>,
+    dependency: &'static str,
+) -> Vec<&'a str> {
+    db.satisfiers(Dependency(dependency))
+        .map(|querier| querier.name().unwrap().as_str())
+        .sorted()
+        .collect()
+}
+
+#[test]
+fn single_satisfiers() {
+    let texts: TextCollection = DB_TEXTS.iter().copied().map(Text::from).collect();
+    let db: EagerQueryDatabase = texts.parse().unwrap();
+    let satisfiers = |dependency| single_satisfier_names(&db, dependency);
+
+    assert_eq!(satisfiers("glibc"), ["glibc"]);
+    assert_eq!(satisfiers("glibc>=2.38"), ["glibc"]);
+    assert_eq!(satisfiers("glibc<2.38"), [] as [&str; 0]);
+    assert_eq!(satisfiers("sh"), ["bash"]);
+    assert_eq!(satisfiers("sh=5"), [] as [&str; 0]);
+    assert_eq!(satisfiers("libalpm.so=15-64"), ["pacman"]);
+    assert_eq!(satisfiers("libalpm.so>=16"), [] as [&str; 0]);
+    assert_eq!(satisfiers("cargo"), ["rust", "rustup"]);
+    assert_eq!(satisfiers("rust>=1:1.80"), ["rust"]);
+    assert_eq!(satisfiers("rust"), ["rust", "rustup"]);
+    assert_eq!(satisfiers("not-exist"), [] as [&str; 0]);
+}
+
+#[test]
+fn single_satisfiers_mut() {
+    let texts: TextCollection = DB_TEXTS.iter().copied().map(Text::from).collect();
+    let mut db: MemoQueryDatabase = texts.parse_mut().unwrap();
+    let satisfiers: Vec<_> = db
+        .satisfiers_mut(Dependency("libreadline.so>=8"))
+        .map(|querier| querier.name_mut().unwrap())
+        .collect();
+    assert_eq!(satisfiers, [Name("readline")]);
+}
+
+#[test]
+fn multi_satisfiers() {
+    let db: EagerMultiQueryDatabase = MULTI_TEXTS.parse().unwrap();
+    let satisfiers = |dependency| -> Vec<_> {
+        db.satisfiers(Dependency(dependency))
+            .map(|(repository, querier)| {
+                (
+                    repository.as_str(),
+                    querier.name().unwrap().as_str(),
+                    querier.version().unwrap().as_str(),
+                )
+            })
+            .sorted()
+            .collect()
+    };
+
+    assert_eq!(
+        satisfiers("paru"),
+        [
+            ("derivative", "paru", "2.1.0-1"),
+            ("personal", "paru", "2.0.3-1"),
+        ],
+    );
+    assert_eq!(satisfiers("paru>=2.1"), [("derivative", "paru", "2.1.0-1")],);
+    assert_eq!(
+        satisfiers("parallel-disk-usage<0.10"),
+        [("personal", "parallel-disk-usage", "0.9.2-1")],
+    );
+    assert_eq!(satisfiers("sh"), [("core", "bash", "5.2.026-2")]);
+}
+
+#[test]
+fn multi_satisfiers_mut() {
+    let mut db: MemoMultiQueryDatabase = MULTI_TEXTS.parse_mut().unwrap();
+    let satisfiers: Vec<_> = db
+        .satisfiers_mut(Dependency("paru<2.1"))
+        .map(|(repository, querier)| (repository.as_str(), querier.version_mut().unwrap().as_str()))
+        .collect();
+    assert_eq!(satisfiers, [("personal", "2.0.3-1")]);
+}
@@ -0,0 +1,186 @@
+pub mod _utils;
+
+use _utils::desc::desc;
+use arch_pkg_db::{
+    EagerMultiQueryDatabase, EagerQueryDatabase, MultiTextCollection, Text, TextCollection,
+    desc::Query,
+    search::{CaseSensitivity, SearchQuery, SearchRank},
+    value::{Name, RepositoryName},
+};
+use pretty_assertions::assert_eq;
+
+fn core_texts() -> TextCollection {
+    [
+        desc("bash", "1.0-1")
+            .description("The GNU Bourne Again shell")
+            .provides(&["sh"]),
+        desc("bash-completion", "1.0-1").description("Programmable completion for the bash shell"),
+        desc("zsh", "1.0-1")
+            .description("A very advanced and programmable command interpreter (shell)"),
+        desc("dash", "1.0-1")
+            .description("POSIX compliant shell that aims to be as small as possible")
+            .provides(&["sh"]),
+        desc("make", "1.0-1")
+            .description("GNU make utility to maintain groups of programs")
+            .groups(&["base-devel"]),
+    ]
+    .into_iter()
+    .map(|desc| Text::from(desc.build()))
+    .collect()
+}
+
+fn extra_texts() -> TextCollection {
+    [
+        desc("bash", "1.0-1")
+            .description("The GNU Bourne Again shell")
+            .provides(&["sh"]),
+        desc("fish", "1.0-1")
+            .description("Smart and user friendly shell intended mostly for interactive use"),
+    ]
+    .into_iter()
+    .map(|desc| Text::from(desc.build()))
+    .collect()
+}
+
+/// Names of the packages of a list of queriers.
+fn names<'a, Querier: Query<'a>>(queriers: impl IntoIterator<Item = Querier>) -> Vec<&'a str> {
+    queriers
+        .into_iter()
+        .map(|querier| querier.name().unwrap().as_str())
+        .collect()
+}
+
+#[test]
+fn single_search_terms() {
+    let texts = core_texts();
+    let db: EagerQueryDatabase = texts.parse().unwrap();
+
+    let query = SearchQuery::terms("bash", CaseSensitivity::Insensitive);
+    assert_eq!(names(db.search(&query)), ["bash", "bash-completion"]);
+
+    let query = SearchQuery::terms("SHELL", CaseSensitivity::Insensitive);
+    assert_eq!(
+        names(db.search(&query)),
+        ["bash", "bash-completion", "dash", "zsh"],
+    );
+
+    let query = SearchQuery::terms("SHELL", CaseSensitivity::Sensitive);
+    assert_eq!(names(db.search(&query)), [] as [&str; 0]);
+
+    let query = SearchQuery::terms("shell programmable", CaseSensitivity::Insensitive);
+    assert_eq!(names(db.search(&query)), ["bash-completion", "zsh"]);
+
+    let query = SearchQuery::terms("sh", CaseSensitivity::Insensitive);
+    assert_eq!(
+        names(db.search(&query)),
+        ["bash", "bash-completion", "dash", "zsh"],
+    );
+
+    let query = SearchQuery::terms("base-devel", CaseSensitivity::Insensitive);
+    assert_eq!(names(db.search(&query)), ["make"]);
+}
+
+#[test]
+fn single_search_rank() {
+    let texts = core_texts();
+    let db: EagerQueryDatabase = texts.parse().unwrap();
+    let rank = |query: &SearchQuery, name| query.rank(db.get(Name(name)).unwrap());
+
+    let query = SearchQuery::terms("bash", CaseSensitivity::Insensitive);
+    assert_eq!(rank(&query, "bash"), Some(SearchRank::ExactName));
+    assert_eq!(
+        rank(&query, "bash-completion"),
+        Some(SearchRank::NamePrefix)
+    );
+    assert_eq!(rank(&query, "zsh"), None);
+
+    let query = SearchQuery::terms("ash", CaseSensitivity::Insensitive);
+    assert_eq!(rank(&query, "dash"), Some(SearchRank::Name));
+
+    let query = SearchQuery::terms("posix", CaseSensitivity::Insensitive);
+    assert_eq!(rank(&query, "dash"), Some(SearchRank::Description));
+
+    let query = SearchQuery::terms("base-devel", CaseSensitivity::Insensitive);
+    assert_eq!(rank(&query, "make"), Some(SearchRank::Other));
+}
+
+#[test]
+fn multi_search_terms() {
+    let texts = MultiTextCollection::new()
+        .add_collection(RepositoryName("core"), core_texts())
+        .add_collection(RepositoryName("extra"), extra_texts());
+    let db: EagerMultiQueryDatabase = texts.parse().unwrap();
+
+    let query = SearchQuery::terms("bash", CaseSensitivity::Insensitive);
+    let hits: Vec<_> = db
+        .search(&query)
+        .into_iter()
+        .map(|(repository, querier)| (repository, querier.name().unwrap().as_str()))
+        .collect();
+    assert_eq!(
+        hits,
+        [
+            (RepositoryName("core"), "bash"),
+            (RepositoryName("extra"), "bash"),
+            (RepositoryName("core"), "bash-completion"),
+        ],
+    );
+
+    let query = SearchQuery::terms("interactive shell", CaseSensitivity::Insensitive);
+    let hits: Vec<_> = db
+        .search(&query)
+        .into_iter()
+        .map(|(repository, querier)| (repository, querier.name().unwrap().as_str()))
+        .collect();
+    assert_eq!(hits, [(RepositoryName("extra"), "fish")]);
+}
+
+#[cfg(feature = "regex")]
+#[test]
+fn single_search_regex() {
+    let texts = core_texts();
+    let db: EagerQueryDatabase = texts.parse().unwrap();
+
+    let query = SearchQuery::regex("^[bd]ash$", CaseSensitivity::Insensitive).unwrap();
+    assert_eq!(names(db.search(&query)), ["bash", "dash"]);
+
+    let query = SearchQuery::regex("gnu .+ shell", CaseSensitivity::Insensitive).unwrap();
+    assert_eq!(names(db.search(&query)), ["bash"]);
+
+    let query = SearchQuery::regex("gnu .+ shell", CaseSensitivity::Sensitive).unwrap();
+    assert_eq!(names(db.search(&query)), [] as [&str; 0]);
+
+    let query = SearchQuery::regex("sh", CaseSensitivity::Insensitive).unwrap();
+    assert_eq!(
+        names(db.search(&query)),
+        ["bash", "bash-completion", "dash", "zsh"],
+    );
+
+    let rank = |query: &SearchQuery, name| query.rank(db.get(Name(name)).unwrap());
+    let query = SearchQuery::regex("^bash", CaseSensitivity::Insensitive).unwrap();
+    assert_eq!(rank(&query, "bash"), Some(SearchRank::ExactName));
+    assert_eq!(
+        rank(&query, "bash-completion"),
+        Some(SearchRank::NamePrefix)
+    );
+    assert_eq!(rank(&query, "zsh"), None);
+
+    let query = SearchQuery::regex("ba|bash", CaseSensitivity::Insensitive).unwrap();
+    assert_eq!(rank(&query, "bash"), Some(SearchRank::ExactName));
+    assert_eq!(
+        rank(&query, "bash-completion"),
+        Some(SearchRank::NamePrefix)
+    );
+
+    let query = SearchQuery::regex("BASH", CaseSensitivity::Insensitive).unwrap();
+    assert_eq!(rank(&query, "bash"), Some(SearchRank::ExactName));
+
+    let query = SearchQuery::regex("(?x)bash # shell", CaseSensitivity::Insensitive).unwrap();
+    assert_eq!(rank(&query, "bash"), Some(SearchRank::ExactName));
+    assert_eq!(
+        rank(&query, "bash-completion"),
+        Some(SearchRank::NamePrefix)
+    );
+
+    assert!(SearchQuery::regex("(", CaseSensitivity::Insensitive).is_err());
+}
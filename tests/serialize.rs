@@ -0,0 +1,88 @@
+#![cfg(feature = "serde")]
+
+pub mod _utils;
+
+use _utils::MULTI_TEXTS;
+use arch_pkg_db::{
+    EagerMultiQueryDatabase, EagerQueryDatabase, Text, TextCollection,
+    desc::EagerQuerier,
+    serialize::SerializeQuerier,
+    value::{Name, RepositoryName},
+};
+use pretty_assertions::assert_eq;
+use text_block_macros::text_block_fnl;
+
+fn texts() -> TextCollection {
+    [
+        "%NAME%\nfoo\n\n%VERSION%\n1.0-1\n\n%DEPENDS%\nbar\nbaz>=1\n\n%REASON%\n1\n",
+        "%NAME%\nbar\n\n%VERSION%\n2.0-1\n\n%DESC%\nThe bar\n\n%ISIZE%\n1024\n\n%MD5SUM%\n00\n",
+    ]
+    .into_iter()
+    .map(Text::from)
+    .collect()
+}
+
+#[test]
+fn query_database() {
+    let texts = texts();
+    let db: EagerQueryDatabase = texts.parse().unwrap();
+    let yaml = serde_yaml::to_string(&db).unwrap();
+    assert_eq!(
+        yaml,
+        text_block_fnl! {
+            "bar:"
+            "  NAME: bar"
+            "  VERSION: 2.0-1"
+            "  DESC: The bar"
+            "  ISIZE: '1024'"
+            "  MD5SUM: '00'"
+            "foo:"
+            "  NAME: foo"
+            "  VERSION: 1.0-1"
+            "  DEPENDS:"
+            "  - bar"
+            "  - baz>=1"
+        },
+    );
+}
+
+#[test]
+fn querier() {
+    let querier = EagerQuerier::parse("%NAME%\nfoo\n\n%GROUPS%\n\n%ARCH%\nany\n").unwrap();
+    let yaml = serde_yaml::to_string(&SerializeQuerier(querier)).unwrap();
+    assert_eq!(yaml, "NAME: foo\nARCH:\n- any\n");
+}
+
+#[test]
+fn multi_query_database() {
+    let db: EagerMultiQueryDatabase = MULTI_TEXTS.parse().unwrap();
+    let value = serde_yaml::to_value(&db).unwrap();
+
+    let names: Vec<_> = value
+        .as_mapping()
+        .unwrap()
+        .keys()
+        .map(|key| key.as_str().unwrap())
+        .collect();
+    assert_eq!(names.first(), Some(&"bash"));
+    assert!(names.is_sorted());
+
+    let paru = &value["paru"];
+    let repositories: Vec<_> = paru
+        .as_mapping()
+        .unwrap()
+        .keys()
+        .map(|key| key.as_str().unwrap())
+        .collect();
+    assert_eq!(repositories, ["derivative", "personal"]);
+    assert_eq!(paru["derivative"]["VERSION"].as_str(), Some("2.1.0-1"));
+    assert_eq!(paru["personal"]["VERSION"].as_str(), Some("2.0.3-1"));
+
+    let latest = db.get(Name("paru")).unwrap().latest().unwrap();
+    let yaml = serde_yaml::to_value(latest).unwrap();
+    assert_eq!(yaml["REPOSITORY"].as_str(), Some("derivative"));
+    assert_eq!(yaml["NAME"].as_str(), Some("paru"));
+
+    let repository = serde_yaml::to_string(&RepositoryName("core")).unwrap();
+    assert_eq!(repository, "core\n");
+}
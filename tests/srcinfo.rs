@@ -0,0 +1,114 @@
+pub mod _utils;
+
+use _utils::{MULTI_TEXTS, fixtures::srcinfo::FOO};
+use arch_pkg_db::{
+    EagerMultiQueryDatabase, EagerQueryDatabase, MultiTextCollection, PackageDesc, TextCollection,
+    desc::Query,
+    srcinfo::{ParsedSrcinfo, parse_split_packages, split_packages},
+    value::{Architecture, Name, RepositoryName},
+};
+use pretty_assertions::assert_eq;
+
+fn strings(values: &[&str]) -> Vec<String> {
+    values.iter().copied().map(String::from).collect()
+}
+
+#[test]
+fn split_packages_without_architecture() {
+    let srcinfo = ParsedSrcinfo::try_from(FOO).unwrap();
+    let packages = split_packages(&srcinfo, None);
+    let common = PackageDesc {
+        base: Some("foo".to_string()),
+        version: Some("1:1.2.3-2".to_string()),
+        url: Some("https://example.org/foo".to_string()),
+        groups: strings(&["foo-suite"]),
+        license: strings(&["MIT"]),
+        make_dependencies: strings(&["cargo"]),
+        ..PackageDesc::default()
+    };
+    assert_eq!(
+        packages,
+        [
+            PackageDesc {
+                name: Some("foo".to_string()),
+                description: Some("Tools to work with foo".to_string()),
+                architecture: strings(&["x86_64", "aarch64"]),
+                dependencies: strings(&["glibc"]),
+                opt_dependencies: strings(&["bash-completion: for tab completion"]),
+                provides: strings(&["foo-cli"]),
+                ..common.clone()
+            },
+            PackageDesc {
+                name: Some("libfoo".to_string()),
+                description: Some("Library to work with foo".to_string()),
+                architecture: strings(&["any"]),
+                dependencies: strings(&["gcc-libs"]),
+                ..common
+            },
+        ],
+    );
+}
+
+#[test]
+fn architecture_specific_overrides() {
+    let dependencies = |architecture| -> Vec<(String, Vec<String>)> {
+        parse_split_packages(FOO, Some(Architecture(architecture)))
+            .unwrap()
+            .into_iter()
+            .map(|desc| (desc.name.unwrap(), desc.dependencies))
+            .collect()
+    };
+    assert_eq!(
+        dependencies("x86_64"),
+        [
+            ("foo".to_string(), strings(&["glibc", "foo-simd"])),
+            ("libfoo".to_string(), strings(&["gcc-libs", "libfoo-simd"])),
+        ],
+    );
+    assert_eq!(
+        dependencies("aarch64"),
+        [
+            ("foo".to_string(), strings(&["glibc", "foo-neon"])),
+            ("libfoo".to_string(), strings(&["gcc-libs", "foo-neon"])),
+        ],
+    );
+}
+
+#[test]
+fn insert_into_query_database() {
+    let texts = TextCollection::from_srcinfo(FOO, Some(Architecture("x86_64"))).unwrap();
+    let db: EagerQueryDatabase = texts.parse().unwrap();
+    let foo = db.get(Name("foo")).unwrap();
+    assert_eq!(foo.version().unwrap().as_str(), "1:1.2.3-2");
+    assert_eq!(foo.base().unwrap().as_str(), "foo");
+    let libfoo = db.get(Name("libfoo")).unwrap();
+    assert_eq!(
+        libfoo.description().unwrap().as_str(),
+        "Library to work with foo",
+    );
+}
+
+#[test]
+fn insert_into_multi_query_database() {
+    let mut multi_texts = MULTI_TEXTS.clone();
+    multi_texts
+        .extend_from_srcinfo(RepositoryName("aur"), FOO, Some(Architecture("x86_64")))
+        .unwrap();
+    let db: EagerMultiQueryDatabase = multi_texts.parse().unwrap();
+    let repositories: Vec<_> = db
+        .get(Name("libfoo"))
+        .unwrap()
+        .entries()
+        .map(|(repository, _)| repository.as_str())
+        .collect();
+    assert_eq!(repositories, ["aur"]);
+
+    let single = MultiTextCollection::from_srcinfo(RepositoryName("local-src"), FOO, None).unwrap();
+    let db: EagerMultiQueryDatabase = single.parse().unwrap();
+    assert_eq!(db.get(Name("foo")).unwrap().entries().count(), 1);
+}
+
+#[test]
+fn invalid_srcinfo() {
+    assert!(parse_split_packages("pkgbase = foo\nnot a line\n", None).is_err());
+}
@@ -0,0 +1,129 @@
+use _utils::temp::Temp;
+use arch_pkg_db::{
+    EagerMultiQueryDatabase, MultiTextCollection,
+    desc::EagerQuerier,
+    text::sync::SyncDir,
+    value::{Name, RepositoryName},
+};
+use itertools::Itertools;
+use pretty_assertions::assert_eq;
+use std::fs;
+
+fn repositories<'a>(multi_collection: &'a MultiTextCollection<'a>) -> Vec<&'a str> {
+    multi_collection
+        .iter()
+        .map(|(repository, _)| repository.as_str())
+        .dedup()
+        .collect()
+}
+
+fn assert_sync_db(db: &EagerMultiQueryDatabase<'_>) {
+    let repositories = |name| -> Vec<_> {
+        db.get(Name(name))
+            .unwrap()
+            .repositories()
+            .map(|repository| repository.as_str())
+            .sorted()
+            .collect()
+    };
+    assert_eq!(repositories("bash"), ["core"]);
+    assert_eq!(repositories("rust"), ["extra"]);
+    assert_eq!(repositories("paru"), ["derivative", "personal"]);
+    assert_eq!(repositories("parallel-disk-usage"), ["extra", "personal"],);
+}
+
+#[test]
+fn read() {
+    let temp = Temp::sync_dir();
+    let sync_dir = SyncDir::read(&temp).unwrap();
+    let listed = sync_dir
+        .iter()
+        .map(|db| {
+            (
+                db.repository().as_str(),
+                db.path().strip_prefix(&temp).unwrap(),
+            )
+        })
+        .map(|(repository, path)| (repository, path.to_str().unwrap()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        listed,
+        [
+            ("core", "core.db"),
+            ("derivative", "derivative.db"),
+            ("extra", "extra.db"),
+            ("personal", "personal.db"),
+        ],
+    );
+}
+
+#[test]
+fn from_sync_dir() {
+    let temp = Temp::sync_dir();
+    let sync_dir = SyncDir::read(&temp).unwrap();
+    let multi_collection = MultiTextCollection::from_sync_dir(&sync_dir).unwrap();
+    assert_eq!(
+        repositories(&multi_collection),
+        ["core", "derivative", "extra", "personal"],
+    );
+    let db = multi_collection.parse::<EagerQuerier>().unwrap();
+    assert_sync_db(&db);
+}
+
+#[test]
+fn par_from_sync_dir() {
+    let temp = Temp::sync_dir();
+    let sync_dir = SyncDir::read(&temp).unwrap();
+    let multi_collection = MultiTextCollection::par_from_sync_dir(&sync_dir).unwrap();
+    assert_eq!(
+        repositories(&multi_collection),
+        ["core", "derivative", "extra", "personal"],
+    );
+    let db = multi_collection.par_parse::<EagerQuerier>().unwrap();
+    assert_sync_db(&db);
+}
+
+#[test]
+fn select() {
+    let temp = Temp::sync_dir();
+    let mut sync_dir = SyncDir::read(&temp).unwrap();
+    sync_dir.select(&[
+        RepositoryName("personal"),
+        RepositoryName("core"),
+        RepositoryName("not-exist"),
+    ]);
+    let multi_collection = MultiTextCollection::par_from_sync_dir(&sync_dir).unwrap();
+    assert_eq!(repositories(&multi_collection), ["personal", "core"]);
+}
+
+#[test]
+fn retain() {
+    let temp = Temp::sync_dir();
+    let mut sync_dir = SyncDir::read(&temp).unwrap();
+    sync_dir.retain(|repository| repository.as_str() != "derivative");
+    sync_dir.sort_by(|a, b| b.repository().as_str().cmp(a.repository().as_str()));
+    let multi_collection = MultiTextCollection::from_sync_dir(&sync_dir).unwrap();
+    assert_eq!(
+        repositories(&multi_collection),
+        ["personal", "extra", "core"],
+    );
+}
+
+#[test]
+fn error_carries_path() {
+    let temp = Temp::sync_dir();
+    fs::write(temp.join("broken.db"), "not an archive at all").unwrap();
+    let sync_dir = SyncDir::read(&temp).unwrap();
+    let error = MultiTextCollection::from_sync_dir(&sync_dir).unwrap_err();
+    assert_eq!(error.path(), temp.join("broken.db"));
+    let error = MultiTextCollection::par_from_sync_dir(&sync_dir).unwrap_err();
+    assert_eq!(error.path(), temp.join("broken.db"));
+}
+
+#[test]
+fn read_missing_dir() {
+    let temp = Temp::sync_dir();
+    let path = temp.join("not-exist");
+    let error = SyncDir::read(&path).unwrap_err();
+    assert_eq!(error.path(), path);
+}
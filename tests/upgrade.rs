@@ -0,0 +1,107 @@
+pub mod _utils;
+
+use _utils::{MULTI_TEXTS, desc::desc};
+use arch_pkg_db::{
+    EagerMultiQueryDatabase, EagerQueryDatabase, Text, TextCollection,
+    upgrade::{UpgradePlan, VersionChange},
+};
+use pretty_assertions::assert_eq;
+
+fn local_texts() -> TextCollection {
+    [
+        desc("bash", "5.1.016-1"),
+        desc("glibc", "2.42+r17+gd7274d718e6f-1"),
+        desc("ncurses", "6.5-4"),
+        desc("alacritty-terminfo", "0.15.1-1"),
+        desc("paru", "2.2.0-1"),
+        desc("parallel-disk-usage", "0.21.1-1"),
+        desc("rustup", "1:1.0.0-1"),
+        desc("cargo-tree", "0.29.0-1"),
+        desc("rust-docs", "1:1.60.0-1"),
+        desc("my-aur-package", "1.0-1"),
+    ]
+    .into_iter()
+    .map(|desc| Text::from(desc.build()))
+    .collect()
+}
+
+#[test]
+fn upgrade_plan() {
+    let local_texts = local_texts();
+    let local: EagerQueryDatabase = local_texts.parse().unwrap();
+    let sync: EagerMultiQueryDatabase = MULTI_TEXTS.parse().unwrap();
+    let plan = UpgradePlan::new(&local, &sync);
+
+    fn changes<'a>(changes: &[VersionChange<'a>]) -> Vec<(&'a str, &'a str, &'a str, &'a str)> {
+        changes
+            .iter()
+            .map(|change| {
+                (
+                    change.name().as_str(),
+                    change.installed().as_str(),
+                    change.candidate().as_str(),
+                    change.repository().as_str(),
+                )
+            })
+            .collect()
+    }
+
+    assert_eq!(
+        changes(plan.upgrades()),
+        [("bash", "5.1.016-1", "5.2.026-2", "core")],
+    );
+    assert_eq!(
+        changes(plan.downgrades()),
+        [
+            ("paru", "2.2.0-1", "2.1.0-1", "derivative"),
+            ("rustup", "1:1.0.0-1", "1.28.2-3", "extra"),
+        ],
+    );
+
+    let foreign: Vec<_> = plan
+        .foreign()
+        .iter()
+        .map(|package| (package.name().as_str(), package.version().unwrap().as_str()))
+        .collect();
+    assert_eq!(
+        foreign,
+        [
+            ("alacritty-terminfo", "0.15.1-1"),
+            ("cargo-tree", "0.29.0-1"),
+            ("my-aur-package", "1.0-1"),
+            ("rust-docs", "1:1.60.0-1"),
+        ],
+    );
+
+    let replacements: Vec<_> = plan
+        .replacements()
+        .iter()
+        .map(|replacement| {
+            (
+                replacement.replaced().as_str(),
+                replacement.replacer().as_str(),
+                replacement.version().as_str(),
+                replacement.repository().as_str(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        replacements,
+        [("cargo-tree", "rust", "1:1.90.0-3", "extra")]
+    );
+
+    assert!(!plan.is_up_to_date());
+}
+
+#[test]
+fn up_to_date() {
+    let local_texts: TextCollection = [desc("bash", "5.2.026-2"), desc("paru", "2.1.0-1")]
+        .into_iter()
+        .map(|desc| Text::from(desc.build()))
+        .collect();
+    let local: EagerQueryDatabase = local_texts.parse().unwrap();
+    let sync: EagerMultiQueryDatabase = MULTI_TEXTS.parse().unwrap();
+    let plan = UpgradePlan::new(&local, &sync);
+    assert!(plan.is_up_to_date());
+    assert!(plan.foreign().is_empty());
+}
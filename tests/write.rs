@@ -0,0 +1,201 @@
+pub mod _utils;
+
+use _utils::fixtures::archives::{official::DB_TEXTS, official_files::FILES_TGZ};
+use arch_pkg_db::{
+    EagerQueryDatabase, FilesCollection, PackageDesc, Text, TextCollection,
+    desc::{EagerQuerier, Query},
+    value::{FileList, Name},
+    write::{Compression, InsertEntryError, SyncDbWriter, desc_text},
+};
+use itertools::Itertools;
+use pipe_trait::Pipe;
+use pretty_assertions::assert_eq;
+
+fn official_db() -> TextCollection {
+    DB_TEXTS.iter().copied().map(Text::from).collect()
+}
+
+fn assert_round_trip(compression: Compression) {
+    let texts = official_db();
+    let db: EagerQueryDatabase = texts.parse().unwrap();
+    let mut writer = SyncDbWriter::new();
+    for querier in db.queriers() {
+        writer.insert(querier).unwrap();
+    }
+    assert_eq!(writer.len(), 9);
+
+    let archive = writer.write_db(Vec::new(), compression).unwrap();
+    let read_texts = archive
+        .as_slice()
+        .pipe(TextCollection::from_archive)
+        .unwrap();
+    let read_db: EagerQueryDatabase = read_texts.parse().unwrap();
+
+    assert_eq!(
+        read_db.names().sorted().collect::<Vec<_>>(),
+        db.names().sorted().collect::<Vec<_>>(),
+    );
+    for (name, querier) in db.entries().map(|entry| entry.into_tuple()) {
+        let read = read_db.get(name).unwrap();
+        assert_eq!(desc_text(read), desc_text(querier));
+        assert_eq!(
+            read.version().map(|version| version.as_str()),
+            querier.version().map(|version| version.as_str()),
+        );
+    }
+}
+
+#[test]
+fn gz() {
+    assert_round_trip(Compression::Gzip);
+}
+
+#[test]
+fn xz() {
+    assert_round_trip(Compression::Xz);
+}
+
+#[test]
+fn zst() {
+    assert_round_trip(Compression::Zstd);
+}
+
+#[test]
+fn files_db() {
+    let collection = FILES_TGZ
+        .as_slice()
+        .pipe(FilesCollection::from_archive)
+        .unwrap();
+    let mut writer = SyncDbWriter::new();
+    for text in collection.iter() {
+        let querier: EagerQuerier = text.desc.as_str().try_into().unwrap();
+        let files = FileList::from_files_text(&text.files);
+        writer.insert_with_files(&querier, files).unwrap();
+    }
+
+    let archive = writer
+        .write_files_db(Vec::new(), Compression::Zstd)
+        .unwrap();
+    let read = archive
+        .as_slice()
+        .pipe(FilesCollection::from_archive)
+        .unwrap();
+    assert_eq!(read.len(), 9);
+    let db = read.parse::<EagerQuerier>().unwrap();
+    let owners: Vec<_> = db
+        .owners("/usr/bin/cargo")
+        .map(|entry| entry.name().as_str())
+        .sorted()
+        .collect();
+    assert_eq!(owners, ["rust", "rustup"]);
+    let files = db.files(Name("pacman")).unwrap().iter().count();
+    assert_eq!(
+        files,
+        collection
+            .iter()
+            .find(|text| text.desc.contains("\n%NAME%\npacman\n"))
+            .map(|text| FileList::from_files_text(&text.files).iter().count())
+            .unwrap(),
+    );
+}
+
+#[test]
+fn entry_paths() {
+    let texts = official_db();
+    let db: EagerQueryDatabase = texts.parse().unwrap();
+    let mut writer = SyncDbWriter::new();
+    for querier in db.queriers() {
+        writer.insert(querier).unwrap();
+    }
+    assert!(writer.remove("glibc"));
+    assert!(!writer.remove("glibc"));
+
+    let archive = writer.write_db(Vec::new(), Compression::Gzip).unwrap();
+    let tar = libflate::gzip::Decoder::new(archive.as_slice()).unwrap();
+    let paths: Vec<_> = tar::Archive::new(tar)
+        .entries()
+        .unwrap()
+        .map(|entry| {
+            entry
+                .unwrap()
+                .path()
+                .unwrap()
+                .to_string_lossy()
+                .into_owned()
+        })
+        .take(6)
+        .collect();
+    assert_eq!(
+        paths,
+        [
+            "bash-5.2.026-2/",
+            "bash-5.2.026-2/desc",
+            "bash-completion-2.14.0-2/",
+            "bash-completion-2.14.0-2/desc",
+            "ncurses-6.5-4/",
+            "ncurses-6.5-4/desc",
+        ],
+    );
+}
+
+#[test]
+fn insert_desc() {
+    let desc = PackageDesc {
+        name: Some("foo".to_string()),
+        version: Some("1.0-1".to_string()),
+        dependencies: vec!["bar".to_string()],
+        ..PackageDesc::default()
+    };
+    let mut writer = SyncDbWriter::new();
+    writer
+        .insert_desc_with_files(&desc, FileList("usr/bin/foo\n"))
+        .unwrap();
+
+    let archive = writer.write_files_db(Vec::new(), Compression::Xz).unwrap();
+    let read = archive
+        .as_slice()
+        .pipe(FilesCollection::from_archive)
+        .unwrap();
+    let text = read.iter().exactly_one().unwrap();
+    assert_eq!(
+        text.desc.as_str(),
+        "%NAME%\nfoo\n\n%VERSION%\n1.0-1\n\n%DEPENDS%\nbar\n\n",
+    );
+    assert_eq!(text.files.as_str(), "%FILES%\nusr/bin/foo\n");
+}
+
+#[test]
+fn missing_fields() {
+    let text = Text::from("%NAME%\nfoo\n");
+    let querier: EagerQuerier = text.as_str().try_into().unwrap();
+    let mut writer = SyncDbWriter::new();
+    assert_eq!(
+        writer.insert(&querier),
+        Err(InsertEntryError::MissingVersion),
+    );
+    assert!(writer.is_empty());
+}
+
+#[test]
+fn render_desc() {
+    let text = Text::from(
+        "%VERSION%\n1.0-1\n\n%DEPENDS%\nbar\nbaz>=1\n\n%NAME%\nfoo\n\n%DESC%\nFoo\n\n%REASON%\n1\n",
+    );
+    let querier: EagerQuerier = text.as_str().try_into().unwrap();
+    assert_eq!(
+        desc_text(&querier),
+        "%NAME%\nfoo\n\n%VERSION%\n1.0-1\n\n%DESC%\nFoo\n\n%DEPENDS%\nbar\nbaz>=1\n\n",
+    );
+}
+
+#[test]
+fn render_desc_checksums() {
+    let text = Text::from(
+        "%NAME%\nfoo\n\n%URL%\nhttps://example.com\n\n%SHA256SUM%\n4567\n\n%VERSION%\n1.0-1\n\n%MD5SUM%\n0123\n",
+    );
+    let querier: EagerQuerier = text.as_str().try_into().unwrap();
+    assert_eq!(
+        desc_text(&querier),
+        "%NAME%\nfoo\n\n%VERSION%\n1.0-1\n\n%MD5SUM%\n0123\n\n%SHA256SUM%\n4567\n\n%URL%\nhttps://example.com\n\n",
+    );
+}
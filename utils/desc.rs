@@ -0,0 +1,79 @@
+/// Start building the text of a synthetic `desc` file with a name and a version.
+pub fn desc(name: &str, version: &str) -> Desc {
+    Desc {
+        text: format!("%NAME%\n{name}\n\n%VERSION%\n{version}\n\n"),
+    }
+}
+
+/// Builder of the text of a synthetic `desc` file, created by [`desc`].
+#[derive(Debug, Clone)]
+#[must_use]
+pub struct Desc {
+    text: String,
+}
+
+impl Desc {
+    /// Append a field with one value per line, or nothing if `values` is empty.
+    pub fn field(mut self, field: &str, values: &[&str]) -> Self {
+        if !values.is_empty() {
+            self.text += &format!("%{field}%\n{}\n\n", values.join("\n"));
+        }
+        self
+    }
+
+    /// Append `%DESC%`.
+    pub fn description(self, description: &str) -> Self {
+        self.field("DESC", &[description])
+    }
+
+    /// Append `%GROUPS%`.
+    pub fn groups(self, groups: &[&str]) -> Self {
+        self.field("GROUPS", groups)
+    }
+
+    /// Append `%ARCH%`.
+    pub fn architectures(self, architectures: &[&str]) -> Self {
+        self.field("ARCH", architectures)
+    }
+
+    /// Append `%DEPENDS%`.
+    pub fn depends(self, depends: &[&str]) -> Self {
+        self.field("DEPENDS", depends)
+    }
+
+    /// Append `%OPTDEPENDS%`.
+    pub fn optdepends(self, optdepends: &[&str]) -> Self {
+        self.field("OPTDEPENDS", optdepends)
+    }
+
+    /// Append `%PROVIDES%`.
+    pub fn provides(self, provides: &[&str]) -> Self {
+        self.field("PROVIDES", provides)
+    }
+
+    /// Append `%CONFLICTS%`.
+    pub fn conflicts(self, conflicts: &[&str]) -> Self {
+        self.field("CONFLICTS", conflicts)
+    }
+
+    /// Append `%REPLACES%`.
+    pub fn replaces(self, replaces: &[&str]) -> Self {
+        self.field("REPLACES", replaces)
+    }
+
+    /// Append `%REASON%` of a locally installed package.
+    pub fn reason(self, reason: u8) -> Self {
+        self.field("REASON", &[&reason.to_string()])
+    }
+
+    /// Get the text.
+    pub fn build(self) -> String {
+        self.text
+    }
+}
+
+impl From<Desc> for String {
+    fn from(desc: Desc) -> Self {
+        desc.build()
+    }
+}
@@ -2,4 +2,7 @@ pub mod archives;
 pub mod core;
 pub mod derivative;
 pub mod extra;
+pub mod files;
+pub mod packages;
 pub mod personal;
+pub mod srcinfo;
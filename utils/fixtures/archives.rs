@@ -1 +1,4 @@
 pub mod official;
+pub mod official_files;
+pub mod packages;
+pub mod sync;
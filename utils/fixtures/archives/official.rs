@@ -6,9 +6,12 @@ use crate::{
     temp::Temp,
 };
 use build_fs_tree::{Build, FileSystemTree, MergeableFileSystemTree, dir, file};
+use bzip2::{Compression, write::BzEncoder};
 use libflate::gzip;
+use lz4_flex::frame::FrameEncoder;
 use lzma_rs::xz_compress;
 use pipe_trait::Pipe;
+use ruzstd::encoding::{CompressionLevel, compress_to_vec};
 use std::{io::Write, sync::LazyLock};
 use tree_to_archive::BuildTar;
 
@@ -73,6 +76,21 @@ pub static TXZ: LazyLock<Vec<u8>> = LazyLock::new(|| {
     xz
 });
 
+pub static TZST: LazyLock<Vec<u8>> =
+    LazyLock::new(|| compress_to_vec(TAR.as_slice(), CompressionLevel::Fastest));
+
+pub static TBZ2: LazyLock<Vec<u8>> = LazyLock::new(|| {
+    let mut encoder = BzEncoder::new(Vec::new(), Compression::fast());
+    encoder.write_all(TAR.as_slice()).unwrap();
+    encoder.finish().unwrap()
+});
+
+pub static TLZ4: LazyLock<Vec<u8>> = LazyLock::new(|| {
+    let mut encoder = FrameEncoder::new(Vec::new());
+    encoder.write_all(TAR.as_slice()).unwrap();
+    encoder.finish().unwrap()
+});
+
 impl Temp {
     /// Create a local db for official packages.
     pub fn official_db() -> Self {
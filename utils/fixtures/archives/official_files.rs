@@ -0,0 +1,127 @@
+use crate::{
+    fixtures::{
+        core::{BASH, GLIBC, NCURSES, PACMAN, READLINE},
+        extra::{BASH_COMPLETION, PARALLEL_DISK_USAGE, RUST, RUSTUP},
+        files,
+    },
+    temp::Temp,
+};
+use build_fs_tree::{Build, FileSystemTree, MergeableFileSystemTree, dir, file};
+use libflate::gzip;
+use pipe_trait::Pipe;
+use std::{fs, io::Write, os::unix::fs::symlink, sync::LazyLock};
+use tree_to_archive::BuildTar;
+
+pub static FILES_DB_TREE: LazyLock<FileSystemTree<&str, &str>> = LazyLock::new(|| {
+    dir! {
+        // core
+        "bash-5.2.026-2" => dir! {
+            "desc" => file!(BASH),
+            "files" => file!(files::BASH),
+        },
+        "glibc-2.42+r17+gd7274d718e6f-1" => dir! {
+            "desc" => file!(GLIBC),
+            "files" => file!(files::GLIBC),
+        },
+        "ncurses-6.5-4" => dir! {
+            "desc" => file!(NCURSES),
+            "files" => file!(files::NCURSES),
+        },
+        "pacman-7.0.0.r6.gc685ae6-6" => dir! {
+            "desc" => file!(PACMAN),
+            "files" => file!(files::PACMAN),
+        },
+        "readline-8.3.001-1" => dir! {
+            "desc" => file!(READLINE),
+            "files" => file!(files::READLINE),
+        },
+
+        // extra
+        "bash-completion-2.14.0-2" => dir! {
+            "desc" => file!(BASH_COMPLETION),
+            "files" => file!(files::BASH_COMPLETION),
+        },
+        "parallel-disk-usage-parallel-disk-usage-0.21.1-1" => dir! {
+            "desc" => file!(PARALLEL_DISK_USAGE),
+            "files" => file!(files::PARALLEL_DISK_USAGE),
+        },
+        "rust-1:1.90.0-3" => dir! {
+            "desc" => file!(RUST),
+            "files" => file!(files::RUST),
+        },
+        "rustup-1.28.2-3" => dir! {
+            "desc" => file!(RUSTUP),
+            "files" => file!(files::RUSTUP),
+        },
+    }
+});
+
+pub static FILES_TAR: LazyLock<Vec<u8>> = LazyLock::new(|| FILES_DB_TREE.build_tar().unwrap());
+
+pub static FILES_TGZ: LazyLock<Vec<u8>> = LazyLock::new(|| {
+    let mut encoder = gzip::Encoder::new(Vec::new()).unwrap();
+    encoder.write_all(FILES_TAR.as_slice()).unwrap();
+    encoder.finish().into_result().unwrap()
+});
+
+/// Relative path to the local pacman database from the root directory.
+pub const LOCAL_DB: &str = "var/lib/pacman/local";
+
+pub static ROOT_TREE: LazyLock<FileSystemTree<&str, &str>> = LazyLock::new(|| {
+    dir! {
+        "etc" => dir! {
+            "bash.bashrc" => file!("# /etc/bash.bashrc\n"),
+            "makepkg.conf" => file!("# modified\n"),
+            "pacman.conf" => file!("[options]\nArchitecture = auto\n"),
+        },
+        "usr" => dir! {
+            "bin" => dir! {
+                "bash" => file!("#!/bin/bash\necho bash\n"),
+            },
+            "share" => dir! {
+                "doc" => dir! {
+                    "bash" => dir! {
+                        "bash guide.txt" => file!("# /etc/bash.bashrc\n"),
+                    },
+                },
+            },
+        },
+        "var" => dir! {
+            "lib" => dir! {
+                "pacman" => dir! {
+                    "local" => dir! {
+                        "bash-5.2.026-2" => dir! {
+                            "desc" => file!(BASH),
+                            "files" => file!(files::BASH),
+                        },
+                        "pacman-7.0.0.r6.gc685ae6-6" => dir! {
+                            "desc" => file!(PACMAN),
+                            "files" => file!(files::PACMAN_LOCAL),
+                        },
+                        "rust-1:1.90.0-3" => dir! {
+                            "desc" => file!(RUST),
+                        },
+                    },
+                },
+            },
+        },
+    }
+});
+
+impl Temp {
+    /// Create a root directory with installed files and a local db which contains `files` and `mtree` files.
+    pub fn official_root() -> Self {
+        let temp = Temp::new("testing-official-root-");
+        ROOT_TREE
+            .clone()
+            .pipe(MergeableFileSystemTree::from)
+            .build(&temp)
+            .unwrap();
+        symlink("bash", temp.join("usr/bin/sh")).unwrap();
+        let mut encoder = gzip::Encoder::new(Vec::new()).unwrap();
+        encoder.write_all(files::BASH_MTREE.as_bytes()).unwrap();
+        let mtree = encoder.finish().into_result().unwrap();
+        fs::write(temp.join(LOCAL_DB).join("bash-5.2.026-2/mtree"), mtree).unwrap();
+        temp
+    }
+}
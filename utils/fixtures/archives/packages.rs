@@ -0,0 +1,75 @@
+use crate::{fixtures::packages, temp::Temp};
+use build_fs_tree::{FileSystemTree, dir, file};
+use libflate::gzip;
+use lzma_rs::xz_compress;
+use ruzstd::encoding::{CompressionLevel, compress_to_vec};
+use std::{fs, io::Write, sync::LazyLock};
+use tree_to_archive::BuildTar;
+
+pub static BASH_PKG_TREE: LazyLock<FileSystemTree<&str, &str>> = LazyLock::new(|| {
+    dir! {
+        ".BUILDINFO" => file!("format = 2\npkgname = bash\n"),
+        ".MTREE" => file!(""),
+        ".PKGINFO" => file!(packages::BASH),
+        "usr" => dir! {
+            "bin" => dir! {
+                "bash" => file!("#!/bin/fictional\n"),
+            },
+        },
+    }
+});
+
+pub static PARALLEL_DISK_USAGE_PKG_TREE: LazyLock<FileSystemTree<&str, &str>> =
+    LazyLock::new(|| {
+        dir! {
+            ".BUILDINFO" => file!("format = 2\npkgname = parallel-disk-usage\n"),
+            ".PKGINFO" => file!(packages::PARALLEL_DISK_USAGE),
+            "usr" => dir! {
+                "bin" => dir! {
+                    "pdu" => file!("#!/bin/fictional\n"),
+                },
+            },
+        }
+    });
+
+pub static BASH_PKG_TAR: LazyLock<Vec<u8>> = LazyLock::new(|| BASH_PKG_TREE.build_tar().unwrap());
+
+pub static BASH_PKG_TGZ: LazyLock<Vec<u8>> = LazyLock::new(|| {
+    let mut encoder = gzip::Encoder::new(Vec::new()).unwrap();
+    encoder.write_all(BASH_PKG_TAR.as_slice()).unwrap();
+    encoder.finish().into_result().unwrap()
+});
+
+pub static BASH_PKG_TXZ: LazyLock<Vec<u8>> = LazyLock::new(|| {
+    let mut xz = Vec::new();
+    xz_compress(&mut BASH_PKG_TAR.as_slice(), &mut xz).unwrap();
+    xz
+});
+
+pub static BASH_PKG_TZST: LazyLock<Vec<u8>> =
+    LazyLock::new(|| compress_to_vec(BASH_PKG_TAR.as_slice(), CompressionLevel::Fastest));
+
+pub static PARALLEL_DISK_USAGE_PKG_TZST: LazyLock<Vec<u8>> = LazyLock::new(|| {
+    let tar: Vec<u8> = PARALLEL_DISK_USAGE_PKG_TREE.build_tar().unwrap();
+    compress_to_vec(tar.as_slice(), CompressionLevel::Fastest)
+});
+
+impl Temp {
+    /// Create a directory of freshly built package files.
+    pub fn package_dir() -> Self {
+        let temp = Temp::new("testing-package-dir-");
+        let packages = [
+            ("bash-5.2.026-2-x86_64.pkg.tar.zst", &*BASH_PKG_TZST),
+            (
+                "parallel-disk-usage-0.21.1-1-x86_64.pkg.tar.zst",
+                &*PARALLEL_DISK_USAGE_PKG_TZST,
+            ),
+        ];
+        for (file_name, bytes) in packages {
+            fs::write(temp.join(file_name), bytes).unwrap();
+        }
+        fs::write(temp.join("bash-5.2.026-2-x86_64.pkg.tar.zst.sig"), b"signature").unwrap();
+        fs::write(temp.join("README"), b"not a package").unwrap();
+        temp
+    }
+}
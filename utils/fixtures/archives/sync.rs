@@ -0,0 +1,118 @@
+use crate::{
+    fixtures::{core, derivative, extra, personal},
+    temp::Temp,
+};
+use build_fs_tree::{FileSystemTree, dir, file};
+use libflate::gzip;
+use ruzstd::encoding::{CompressionLevel, compress_to_vec};
+use std::{fs, io::Write, path::Path, sync::LazyLock};
+use text_block_macros::text_block;
+use tree_to_archive::BuildTar;
+
+pub static CORE_TREE: LazyLock<FileSystemTree<&str, &str>> = LazyLock::new(|| {
+    dir! {
+        "bash-5.2.026-2" => dir! { "desc" => file!(core::BASH) },
+        "glibc-2.42+r17+gd7274d718e6f-1" => dir! { "desc" => file!(core::GLIBC) },
+        "ncurses-6.5-4" => dir! { "desc" => file!(core::NCURSES) },
+        "pacman-7.0.0.r6.gc685ae6-6" => dir! { "desc" => file!(core::PACMAN) },
+        "readline-8.3.001-1" => dir! { "desc" => file!(core::READLINE) },
+    }
+});
+
+pub static EXTRA_TREE: LazyLock<FileSystemTree<&str, &str>> = LazyLock::new(|| {
+    dir! {
+        "bash-completion-2.14.0-2" => dir! { "desc" => file!(extra::BASH_COMPLETION) },
+        "parallel-disk-usage-0.21.1-1" => dir! { "desc" => file!(extra::PARALLEL_DISK_USAGE) },
+        "rust-1:1.90.0-3" => dir! { "desc" => file!(extra::RUST) },
+        "rustup-1.28.2-3" => dir! { "desc" => file!(extra::RUSTUP) },
+    }
+});
+
+pub static DERIVATIVE_TREE: LazyLock<FileSystemTree<&str, &str>> = LazyLock::new(|| {
+    dir! {
+        "paru" => dir! { "desc" => file!(derivative::PARU) },
+    }
+});
+
+pub static PERSONAL_TREE: LazyLock<FileSystemTree<&str, &str>> = LazyLock::new(|| {
+    dir! {
+        "parallel-disk-usage" => dir! { "desc" => file!(personal::PARALLEL_DISK_USAGE) },
+        "paru" => dir! { "desc" => file!(personal::PARU) },
+    }
+});
+
+fn tar(tree: &FileSystemTree<&str, &str>) -> Vec<u8> {
+    tree.build_tar().unwrap()
+}
+
+fn gzip(tree: &FileSystemTree<&str, &str>) -> Vec<u8> {
+    let mut encoder = gzip::Encoder::new(Vec::new()).unwrap();
+    encoder.write_all(&tar(tree)).unwrap();
+    encoder.finish().into_result().unwrap()
+}
+
+/// Write `core.db`, `extra.db`, `derivative.db`, and `personal.db`, alongside files which aren't sync databases.
+fn write_sync_dir(path: &Path) {
+    fs::create_dir_all(path).unwrap();
+    fs::write(path.join("core.db"), gzip(&CORE_TREE)).unwrap();
+    fs::write(path.join("core.db.sig"), "not a database").unwrap();
+    fs::write(path.join("core.files"), gzip(&CORE_TREE)).unwrap();
+    let extra = compress_to_vec(tar(&EXTRA_TREE).as_slice(), CompressionLevel::Fastest);
+    fs::write(path.join("extra.db"), extra).unwrap();
+    fs::write(path.join("derivative.db"), gzip(&DERIVATIVE_TREE)).unwrap();
+    fs::write(path.join("personal.db"), tar(&PERSONAL_TREE)).unwrap();
+    fs::create_dir(path.join("download-abcdef")).unwrap();
+}
+
+pub static PACMAN_CONF: &str = text_block! {
+    "# See the pacman.conf(5) manpage for option and repository directives"
+    ""
+    "[options]"
+    "DBPath = /custom/db/ # trailing comment"
+    "Architecture = x86_64 x86_64_v3"
+    "CheckSpace"
+    ""
+    "[personal]"
+    "SigLevel = Optional TrustAll"
+    "Server = file:///srv/repo/personal"
+    ""
+    "[core]"
+    "Include = /etc/pacman.d/mirrorlist"
+    ""
+    "[extra]"
+    "Include = /etc/pacman.d/mirrorlist"
+    ""
+    "Include = /etc/pacman.d/repos/*.conf"
+};
+
+pub static MIRRORLIST: &str = text_block! {
+    "## Worldwide"
+    "Server = https://geo.mirror.pkgbuild.com/$repo/os/$arch"
+    "Server = https://mirror.rackspace.com/archlinux/$repo/os/$arch"
+};
+
+pub static DERIVATIVE_CONF: &str = text_block! {
+    "[derivative]"
+    "Server = https://example.com/$repo/$arch"
+};
+
+impl Temp {
+    /// Create a pacman sync directory with `core.db`, `extra.db`, `derivative.db`, and `personal.db`,
+    /// alongside files which aren't sync databases.
+    pub fn sync_dir() -> Self {
+        let temp = Temp::new("testing-sync-dir-");
+        write_sync_dir(&temp);
+        temp
+    }
+
+    /// Create a root directory with `/etc/pacman.conf`, its included files, and the sync databases it declares.
+    pub fn pacman_root() -> Self {
+        let temp = Temp::new("testing-pacman-root-");
+        fs::create_dir_all(temp.join("etc/pacman.d/repos")).unwrap();
+        fs::write(temp.join("etc/pacman.conf"), PACMAN_CONF).unwrap();
+        fs::write(temp.join("etc/pacman.d/mirrorlist"), MIRRORLIST).unwrap();
+        fs::write(temp.join("etc/pacman.d/repos/derivative.conf"), DERIVATIVE_CONF).unwrap();
+        write_sync_dir(&temp.join("custom/db/sync"));
+        temp
+    }
+}
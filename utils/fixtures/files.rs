@@ -0,0 +1,12 @@
+pub static BASH: &str = include_str!("files/bash.files");
+pub static BASH_COMPLETION: &str = include_str!("files/bash-completion.files");
+pub static GLIBC: &str = include_str!("files/glibc.files");
+pub static NCURSES: &str = include_str!("files/ncurses.files");
+pub static PACMAN: &str = include_str!("files/pacman.files");
+pub static PARALLEL_DISK_USAGE: &str = include_str!("files/parallel-disk-usage.files");
+pub static READLINE: &str = include_str!("files/readline.files");
+pub static RUST: &str = include_str!("files/rust.files");
+pub static RUSTUP: &str = include_str!("files/rustup.files");
+
+pub static PACMAN_LOCAL: &str = include_str!("files/pacman.local.files");
+pub static BASH_MTREE: &str = include_str!("files/bash.mtree");
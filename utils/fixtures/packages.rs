@@ -0,0 +1,2 @@
+pub static BASH: &str = include_str!("packages/bash.PKGINFO");
+pub static PARALLEL_DISK_USAGE: &str = include_str!("packages/parallel-disk-usage.PKGINFO");
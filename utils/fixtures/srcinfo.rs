@@ -0,0 +1 @@
+pub static FOO: &str = include_str!("srcinfo/foo.SRCINFO");
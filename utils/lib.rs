@@ -1,2 +1,3 @@
+pub mod desc;
 pub mod fixtures;
 pub mod temp;